@@ -1,12 +1,14 @@
-use crate::convert::{Bag, Collisions};
-use crate::feature_from::FeatureFrom;
-use crate::multiftzr::MultiFtzr;
+use crate::adapters::{Filter, FilterMap, Map};
+use crate::convert::{Bag, Collisions, Positions};
+use crate::error::FeatureError;
+use crate::feature_from::{FeatureFrom, TryFeatureFrom};
+use crate::multiftzr::{AndThen, Zip};
 use crate::HashedAs;
 use fxhash::hash32;
 use std::cmp;
 use std::collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet, LinkedList, VecDeque};
 use std::hash::{BuildHasher, Hash};
-use std::ops::AddAssign;
+use std::ops::{AddAssign, ControlFlow};
 use std::rc::Rc;
 use std::sync::Arc;
 
@@ -68,6 +70,34 @@ pub trait Ftzr<InputData> {
     /// ```
     fn push_tokens<Push: FnMut(Self::TokenGroup)>(&self, input: InputData, push: &mut Push);
 
+    /// Identical to [`Ftzr::push_tokens`], except that `push` can stop featurizing by returning `ControlFlow::Break(())`, which is then returned. Featurizers built on [`IterFtzr`] (and `map`, `filter`, `take`, `for_each`, etc. of them) stop right away. The others still visit the rest of the input, but nothing more is pushed.
+    /// # Example
+    ///```
+    ///use creature_feature::ftzrs::bislice;
+    ///use creature_feature::traits::*;
+    ///use std::ops::ControlFlow;
+    ///
+    ///let mut seen = vec![];
+    ///let flow = bislice().push_tokens_while("abcdef", &mut |gram: &[u8]| {
+    ///    seen.push(gram);
+    ///    if gram == b"bc" { ControlFlow::Break(()) } else { ControlFlow::Continue(()) }
+    ///});
+    ///assert!(flow.is_break());
+    ///assert_eq!(seen, vec![b"ab", b"bc"]);
+    /// ```
+    fn push_tokens_while<Push>(&self, input: InputData, push: &mut Push) -> ControlFlow<()>
+    where
+        Push: FnMut(Self::TokenGroup) -> ControlFlow<()>,
+    {
+        let mut flow = ControlFlow::Continue(());
+        self.push_tokens(input, &mut |t| {
+            if flow.is_continue() {
+                flow = push(t);
+            }
+        });
+        flow
+    }
+
     ///Identical to [`Ftzr::push_tokens`], except with implicit coercion via [`FeatureFrom<Self::TokenGroup>`]
     ///
     /// # Example
@@ -185,6 +215,194 @@ pub trait Ftzr<InputData> {
         //unimplemented!()
     }*/
 
+    /// Transforms each group of tokens with `f`, so that the new featurizer's `TokenGroup` is whatever `f` returns. Implements `IterFtzr` when `self` does (and `f` is `Clone`).
+    ///
+    /// The closure usually needs its argument type written out, because the input type isn't known yet.
+    /// # Example
+    /// ```
+    ///use creature_feature::ftzrs::{bislice, for_each, word_slice};
+    ///use creature_feature::traits::Ftzr;
+    ///
+    ///let lengths: Vec<usize> = word_slice(1).map(|w: &str| w.len()).featurize("one fish two fish");
+    ///assert_eq!(lengths, vec![3, 4, 3, 4]);
+    ///
+    ///let upper = bislice().map(|s: &[u8]| String::from_utf8_lossy(s).to_uppercase());
+    ///let upper: Vec<String> = for_each(upper).featurize("ab cd".split(' '));
+    ///assert_eq!(upper, vec!["AB", "CD"]);
+    /// ```
+    fn map<G, U>(self, f: G) -> Map<Self, G, InputData>
+    where
+        Self: Sized,
+        G: Fn(Self::TokenGroup) -> U,
+    {
+        Map::new(self, f)
+    }
+
+    /// Only keeps the groups of tokens for which `predicate` returns `true`. Implements `IterFtzr` when `self` does (and `predicate` is `Clone`).
+    /// # Example
+    /// ```
+    ///use creature_feature::ftzrs::{bigram, word_slice};
+    ///use creature_feature::traits::Ftzr;
+    ///
+    ///let long_words: Vec<&str> = word_slice(1).filter(|w: &&str| w.len() > 3).featurize("one fish two fish");
+    ///assert_eq!(long_words, vec!["fish", "fish"]);
+    ///
+    ///let no_spaces: Vec<String> = bigram().filter(|bg: &[u8; 2]| !bg.contains(&b' ')).featurize("ab cd");
+    ///assert_eq!(no_spaces, vec!["ab", "cd"]);
+    /// ```
+    fn filter<P>(self, predicate: P) -> Filter<Self, P, InputData>
+    where
+        Self: Sized,
+        P: Fn(&Self::TokenGroup) -> bool,
+    {
+        Filter::new(self, predicate)
+    }
+
+    /// Transforms each group of tokens with `f`, and drops those mapped to `None`. Implements `IterFtzr` when `self` does (and `f` is `Clone`).
+    /// # Example
+    /// ```
+    ///use creature_feature::ftzrs::word_slice;
+    ///use creature_feature::traits::Ftzr;
+    ///
+    ///let numbers: Vec<u32> = word_slice(1).filter_map(|w: &str| w.parse::<u32>().ok()).featurize("1 fish 2 fish");
+    ///assert_eq!(numbers, vec![1, 2]);
+    /// ```
+    fn filter_map<G, U>(self, f: G) -> FilterMap<Self, G, InputData>
+    where
+        Self: Sized,
+        G: Fn(Self::TokenGroup) -> Option<U>,
+    {
+        FilterMap::new(self, f)
+    }
+
+    /// Runs `self` and then `other` on the same input, like `featurizers!(self, other)`. The token groups are `EitherGroup`s, which can be featurized into `Merged<T>` (for a common type `T`) or `EitherGroup<T1, T2>`. Implements `IterFtzr` when both do.
+    /// # Example
+    /// ```
+    ///use creature_feature::convert::Merged;
+    ///use creature_feature::ftzrs::misc::EitherGroup::{self, Left, Right};
+    ///use creature_feature::ftzrs::{bigram, bislice, n_slice, whole};
+    ///use creature_feature::traits::Ftzr;
+    ///use creature_feature::HashedAs;
+    ///
+    ///let feats: Vec<Merged<String>> = bigram().and_then(n_slice(3)).featurize("abcd");
+    ///assert_eq!(feats.len(), 5);
+    ///
+    /// // without a common type, each side keeps its own feature type
+    ///let ftzr = bislice().and_then(whole()).and_then(bigram());
+    ///let feats: Vec<EitherGroup<EitherGroup<&str, String>, HashedAs<u64>>> = ftzr.featurize("abc");
+    ///for feat in feats {
+    ///    match feat {
+    ///        Left(Left(slice)) => assert_eq!(slice.len(), 2),
+    ///        Left(Right(whole)) => assert_eq!(whole, "abc"),
+    ///        Right(hashed) => println!("{:?}", hashed),
+    ///    }
+    ///}
+    /// ```
+    fn and_then<B>(self, other: B) -> AndThen<Self, B, InputData>
+    where
+        Self: Sized,
+        B: Ftzr<InputData>,
+    {
+        AndThen::new(self, other)
+    }
+
+    /// Runs `self` and `other` on the same input in lockstep, producing a pair of token groups and stopping at the shorter of the two. With `push_tokens`, the token groups of `other` are buffered in a `Vec` first, while `iterate_features` zips lazily (when both are `IterFtzr`).
+    /// # Example
+    /// ```
+    ///use creature_feature::ftzrs::{bigram, bislice};
+    ///use creature_feature::traits::Ftzr;
+    ///
+    ///let pairs: Vec<(&str, String)> = bislice().zip_with(bigram()).featurize("abc");
+    ///assert_eq!(pairs, vec![("ab", "ab".to_owned()), ("bc", "bc".to_owned())]);
+    /// ```
+    fn zip_with<B>(self, other: B) -> Zip<Self, B, InputData>
+    where
+        Self: Sized,
+        B: Ftzr<InputData>,
+    {
+        Zip::new(self, other)
+    }
+
+    /// Identical to [`Ftzr::push_tokens`], except that an input shorter than [`MinLen::min_len`] isn't featurized at all, and is an error instead.
+    ///
+    /// Without the check, short inputs are clamped: combinators like `bookends` and `gap_gram` produce the token groups that fit (possibly none). To skip short inputs, just ignore the error.
+    /// # Example
+    /// ```
+    ///use creature_feature::ftzrs::{bislice, bookends, gap_gram, n_slice};
+    ///use creature_feature::traits::Ftzr;
+    ///use creature_feature::FeatureError;
+    ///
+    ///let ftzr = gap_gram(n_slice(2), 1, n_slice(2));
+    ///
+    ///let feats: Result<Vec<(&str, &str)>, _> = ftzr.try_featurize("abcde");
+    ///assert_eq!(feats, Ok(vec![("ab", "de")]));
+    ///
+    ///let feats: Result<Vec<(&str, &str)>, _> = ftzr.try_featurize("abc");
+    ///assert_eq!(feats, Err(FeatureError::InputTooShort { len: 3, min: 5 }));
+    ///
+    /// // while the unchecked version emits what fits
+    ///let feats: Vec<(&str, &str)> = ftzr.featurize("abc");
+    ///assert!(feats.is_empty());
+    ///
+    ///let mut count = 0;
+    ///let short = bookends((bislice(), 3), (bislice(), 3)).try_push_tokens("ab", &mut |_| count += 1);
+    ///assert!(short.is_err());
+    ///assert_eq!(count, 0);
+    /// ```
+    fn try_push_tokens<Push>(&self, input: InputData, push: &mut Push) -> Result<(), FeatureError>
+    where
+        Self: MinLen,
+        InputData: InputLen,
+        Push: FnMut(Self::TokenGroup),
+    {
+        check_min_len(self, &input)?;
+        self.push_tokens(input, push);
+        Ok(())
+    }
+
+    ///Identical to [`Ftzr::featurize`], except that it never panics on untrusted input. Instead, it returns the first [`FeatureError`], and stops featurizing there (See [`Ftzr::push_tokens_while`]):
+    /// - the input is shorter than [`MinLen::min_len`] (See [`Ftzr::try_push_tokens`])
+    /// - a token group can't become a `Feature`, like bytes that aren't UTF-8 (See [`TryFeatureFrom`])
+    /// - a fixed-capacity accumulator is full (See [`Accumulates::try_accum_token`])
+    /// # Example
+    /// ```
+    ///use creature_feature::convert::Lossy;
+    ///use creature_feature::ftzrs::bislice;
+    ///use creature_feature::traits::Ftzr;
+    ///use creature_feature::FeatureError;
+    ///
+    ///let data: &[u8] = &[b'a', b'b', 0xff];
+    ///
+    ///let feats: Result<Vec<&str>, _> = bislice().try_featurize(data);
+    ///assert_eq!(feats, Err(FeatureError::InvalidUtf8 { offset: 1 }));
+    ///
+    /// // or substitute U+FFFD for the invalid bytes
+    ///let feats: Result<Vec<Lossy<String>>, _> = bislice().try_featurize(data);
+    ///assert_eq!(*feats.unwrap()[1], "b\u{FFFD}");
+    /// ```
+    fn try_featurize<Feature, A>(&self, input: InputData) -> Result<A, FeatureError>
+    where
+        Self: MinLen,
+        InputData: InputLen,
+        Feature: TryFeatureFrom<Self::TokenGroup>,
+        A: Accumulates<Feature>,
+    {
+        check_min_len(self, &input)?;
+        let mut state: A::State = Default::default();
+        let mut result = Ok(());
+        self.push_tokens_while(input, &mut |t: Self::TokenGroup| match Feature::try_from(t)
+            .and_then(|feat| A::try_accum_token(&mut state, feat))
+        {
+            Ok(()) => ControlFlow::Continue(()),
+            Err(e) => {
+                result = Err(e);
+                ControlFlow::Break(())
+            }
+        });
+        result?;
+        Ok(A::finish(state))
+    }
+
     #[allow(missing_docs)]
     fn as_fn<X: FeatureFrom<Self::TokenGroup>>(
         self,
@@ -195,19 +413,24 @@ pub trait Ftzr<InputData> {
         Arc::new(move |o| self.featurize(o))
     }
 
-    /*
-    fn and_then<T: Ftzr<InputData>>(self, ftzr: T) -> MultiFtzr<Self, T>
-    where
-        Self: Sized,
-    {
-        MultiFtzr(self, ftzr)
-    } */
-
     //fn derefed<'a>(&'a self) -> DerefedFtzr<'a, Self> {
     //    DerefedFtzr(self)
     //}
 }
 
+/// `Err(InputTooShort)` if `input` is shorter than `ftzr` needs
+fn check_min_len<F, I>(ftzr: &F, input: &I) -> Result<(), FeatureError>
+where
+    F: MinLen + ?Sized,
+    I: InputLen,
+{
+    let (len, min) = (input.input_len(), ftzr.min_len());
+    if len < min {
+        return Err(FeatureError::InputTooShort { len, min });
+    }
+    Ok(())
+}
+
 /// A featurizer that can only iterate over the original data.
 /// This is implemented for all featurizers in creature_feature for all of the following: `IterFtzr<&str>`,`IterFtzr<&String>`, `IterFtzr<&[T]>`,`IterFtzr<&Vec<T>>`,`IterFtzr<&[T; N]>`.
 ///
@@ -266,6 +489,71 @@ pub trait LinearFixed {
     fn chunk_size(&self) -> usize;
 }
 
+/// A featurizer with a minimum input length, below which it can only produce some (or none) of its token groups. Used by `Ftzr::try_featurize` and `Ftzr::try_push_tokens`
+///
+/// Implemented for the n-grams (as their chunk size), `bookends` (as the larger of its two sizes), and the combinators of them, which either add what they need (like `deltas` and `winnow`) or pass on the needs of their inner featurizers (like `map` and `strided`). `for_each` and `field` have no minimum, since they clamp each item (or field) on its own, and neither do `bpe` and `word_piece`, which tokenize any input in full.
+///
+/// The length of a `&str` is in bytes, so text featurizers like `char_slice(n)` and `word_slice(n)` ask for `n` bytes: a lower bound, since each char or word takes at least one.
+pub trait MinLen {
+    /// The shortest input that's fully featurized
+    fn min_len(&self) -> usize;
+}
+
+/// Input data with a length that can be compared to [`MinLen::min_len`]. A `&str` is measured in bytes, like `n_slice` and `n_gram` see it.
+pub trait InputLen {
+    /// The number of tokens in the input
+    fn input_len(&self) -> usize;
+}
+
+impl<T> InputLen for &[T] {
+    fn input_len(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T, const N: usize> InputLen for &[T; N] {
+    fn input_len(&self) -> usize {
+        N
+    }
+}
+
+impl<T> InputLen for &Vec<T> {
+    fn input_len(&self) -> usize {
+        self.len()
+    }
+}
+
+impl InputLen for &str {
+    fn input_len(&self) -> usize {
+        self.len()
+    }
+}
+
+impl InputLen for &String {
+    fn input_len(&self) -> usize {
+        self.len()
+    }
+}
+
+/// A featurizer that knows where each of its token groups came from. Used by `with_spans`
+///
+/// Spans are positions in the input, such as byte offsets for `&str` and indices for `&[T]`. Implemented for `SliceGram`, `NGram<N>`, `GapGram<A,B>`, `BookEnds<A,B>` and `ForEach`.
+pub trait SpanFtzr<InputData> {
+    /// Where a group of tokens came from. Usually `Range<usize>`, but `GapGram` has one range per group.
+    type Span;
+
+    /// Type of tokens visited by the featurizer. The same as `Ftzr::TokenGroup`
+    type TokenGroup;
+
+    /// Identical to [`Ftzr::push_tokens`], except each group of tokens is visited along with its span, shifted right by `offset`.
+    fn push_spans<Push: FnMut(Self::Span, Self::TokenGroup)>(
+        &self,
+        input: InputData,
+        offset: usize,
+        push: &mut Push,
+    );
+}
+
 /// A trait (inspired by `std::hash::Hasher`) that is the featurizer equivalent of `FromIterator`
 ///
 /// However, there are two key differences:
@@ -280,6 +568,12 @@ pub trait Accumulates<Token> {
     /// Visits one Token/TokenGroup. Isomorphic to a mutable continuation.
     fn accum_token(state: &mut Self::State, token: Token);
 
+    /// Like `accum_token`, but fails instead of dropping the token when the accumulator is full. Only fixed-capacity accumulators need to override it. Used by `Ftzr::try_featurize`
+    fn try_accum_token(state: &mut Self::State, token: Token) -> Result<(), FeatureError> {
+        Self::accum_token(state, token);
+        Ok(())
+    }
+
     /// Produces the final result
     fn finish(state: Self::State) -> Self;
 
@@ -297,6 +591,12 @@ impl<Token: Ord, K: Kind, const N: usize> Accumulates<Token>
         state.push(token);
     }
 
+    fn try_accum_token(state: &mut Self::State, token: Token) -> Result<(), FeatureError> {
+        state
+            .push(token)
+            .map_err(|_| FeatureError::CapacityExceeded { capacity: N })
+    }
+
     fn finish(state: Self::State) -> Self {
         state
     }
@@ -349,6 +649,11 @@ where
         B::accum_token(&mut state.1, token);
     }
 
+    fn try_accum_token(state: &mut Self::State, token: Token) -> Result<(), FeatureError> {
+        A::try_accum_token(&mut state.0, token.clone())?;
+        B::try_accum_token(&mut state.1, token)
+    }
+
     fn finish(state: Self::State) -> Self {
         (A::finish(state.0), B::finish(state.1))
     }
@@ -518,6 +823,47 @@ impl<Token: Ord, N: Default + AddAssign + From<u8>> Accumulates<Token> for Bag<B
     }
 }
 
+impl<Token, V, S> Accumulates<Token> for Positions<HashMap<Token, V, S>>
+where
+    Token: Eq + Hash,
+    V: Accumulates<u32>,
+    S: Default + BuildHasher,
+{
+    type State = (u32, HashMap<Token, V::State, S>);
+    fn accum_token(state: &mut Self::State, token: Token) {
+        let (position, map) = state;
+        V::accum_token(map.entry(token).or_default(), *position);
+        *position += 1;
+    }
+    fn finish(state: Self::State) -> Self {
+        Positions(
+            state
+                .1
+                .into_iter()
+                .map(|(k, v)| (k, V::finish(v)))
+                .collect(),
+        )
+    }
+}
+
+impl<Token: Ord, V: Accumulates<u32>> Accumulates<Token> for Positions<BTreeMap<Token, V>> {
+    type State = (u32, BTreeMap<Token, V::State>);
+    fn accum_token(state: &mut Self::State, token: Token) {
+        let (position, map) = state;
+        V::accum_token(map.entry(token).or_default(), *position);
+        *position += 1;
+    }
+    fn finish(state: Self::State) -> Self {
+        Positions(
+            state
+                .1
+                .into_iter()
+                .map(|(k, v)| (k, V::finish(v)))
+                .collect(),
+        )
+    }
+}
+
 impl<Token, K, V, S> Accumulates<Token> for HashMap<K, V, S>
 where
     S: Default + BuildHasher,
@@ -589,6 +935,19 @@ impl<'a> Accumulates<&'a [char]> for String {
     }
 }
 
+impl Accumulates<char> for String {
+    type State = Self;
+
+    #[inline]
+    fn accum_token(state: &mut Self::State, token: char) {
+        state.push(token)
+    }
+    #[inline]
+    fn finish(state: Self::State) -> Self {
+        state
+    }
+}
+
 impl<'a> Accumulates<&'a [u8]> for String {
     type State = Self;
 
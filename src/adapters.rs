@@ -0,0 +1,644 @@
+use crate::accum_ftzr::{Ftzr, IterFtzr, LinearFixed, MinLen};
+use crate::feature_from::FeatureFrom;
+use crate::hashedfeature::HashedAs;
+use fxhash::FxHashSet;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::hash::Hash;
+use std::iter;
+use std::marker::PhantomData;
+use std::mem;
+use std::ops::ControlFlow;
+use std::sync::Mutex;
+
+/// A featurizer combinator that transforms each token group with a closure. Created with `Ftzr::map`
+#[derive(Copy, Clone)]
+pub struct Map<F, G, Meta> {
+    ftzr: F,
+    f: G,
+    tags: PhantomData<Meta>,
+}
+
+/// A featurizer combinator that only keeps the token groups matching a predicate. Created with `Ftzr::filter`
+#[derive(Copy, Clone)]
+pub struct Filter<F, P, Meta> {
+    ftzr: F,
+    predicate: P,
+    tags: PhantomData<Meta>,
+}
+
+/// A featurizer combinator that transforms each token group with a closure, and drops those mapped to `None`. Created with `Ftzr::filter_map`
+#[derive(Copy, Clone)]
+pub struct FilterMap<F, G, Meta> {
+    ftzr: F,
+    f: G,
+    tags: PhantomData<Meta>,
+}
+
+impl<F, G, Meta> Map<F, G, Meta> {
+    pub(crate) fn new(ftzr: F, f: G) -> Self {
+        Map {
+            ftzr,
+            f,
+            tags: PhantomData,
+        }
+    }
+}
+
+impl<F, P, Meta> Filter<F, P, Meta> {
+    pub(crate) fn new(ftzr: F, predicate: P) -> Self {
+        Filter {
+            ftzr,
+            predicate,
+            tags: PhantomData,
+        }
+    }
+}
+
+impl<F, G, Meta> FilterMap<F, G, Meta> {
+    pub(crate) fn new(ftzr: F, f: G) -> Self {
+        FilterMap {
+            ftzr,
+            f,
+            tags: PhantomData,
+        }
+    }
+}
+
+/// Closures aren't `Debug`, so only the inner featurizer is shown
+impl<F: fmt::Debug, G, Meta> fmt::Debug for Map<F, G, Meta> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Map")
+            .field("ftzr", &self.ftzr)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<F: fmt::Debug, P, Meta> fmt::Debug for Filter<F, P, Meta> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Filter")
+            .field("ftzr", &self.ftzr)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<F: fmt::Debug, G, Meta> fmt::Debug for FilterMap<F, G, Meta> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FilterMap")
+            .field("ftzr", &self.ftzr)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<Origin, F, G, U> Ftzr<Origin> for Map<F, G, Origin>
+where
+    F: Ftzr<Origin>,
+    G: Fn(F::TokenGroup) -> U,
+{
+    type TokenGroup = U;
+    fn push_tokens<Push>(&self, origin: Origin, push: &mut Push)
+    where
+        Push: FnMut(Self::TokenGroup),
+    {
+        self.ftzr
+            .push_tokens(origin, &mut |token| push((self.f)(token)))
+    }
+
+    fn push_tokens_while<Push>(&self, origin: Origin, push: &mut Push) -> ControlFlow<()>
+    where
+        Push: FnMut(Self::TokenGroup) -> ControlFlow<()>,
+    {
+        self.ftzr
+            .push_tokens_while(origin, &mut |token| push((self.f)(token)))
+    }
+}
+
+impl<Origin, F, P> Ftzr<Origin> for Filter<F, P, Origin>
+where
+    F: Ftzr<Origin>,
+    P: Fn(&F::TokenGroup) -> bool,
+{
+    type TokenGroup = F::TokenGroup;
+    fn push_tokens<Push>(&self, origin: Origin, push: &mut Push)
+    where
+        Push: FnMut(Self::TokenGroup),
+    {
+        self.ftzr.push_tokens(origin, &mut |token| {
+            if (self.predicate)(&token) {
+                push(token)
+            }
+        })
+    }
+
+    fn push_tokens_while<Push>(&self, origin: Origin, push: &mut Push) -> ControlFlow<()>
+    where
+        Push: FnMut(Self::TokenGroup) -> ControlFlow<()>,
+    {
+        self.ftzr.push_tokens_while(origin, &mut |token| {
+            if (self.predicate)(&token) {
+                push(token)
+            } else {
+                ControlFlow::Continue(())
+            }
+        })
+    }
+}
+
+impl<Origin, F, G, U> Ftzr<Origin> for FilterMap<F, G, Origin>
+where
+    F: Ftzr<Origin>,
+    G: Fn(F::TokenGroup) -> Option<U>,
+{
+    type TokenGroup = U;
+    fn push_tokens<Push>(&self, origin: Origin, push: &mut Push)
+    where
+        Push: FnMut(Self::TokenGroup),
+    {
+        self.ftzr.push_tokens(origin, &mut |token| {
+            if let Some(token) = (self.f)(token) {
+                push(token)
+            }
+        })
+    }
+
+    fn push_tokens_while<Push>(&self, origin: Origin, push: &mut Push) -> ControlFlow<()>
+    where
+        Push: FnMut(Self::TokenGroup) -> ControlFlow<()>,
+    {
+        self.ftzr
+            .push_tokens_while(origin, &mut |token| match (self.f)(token) {
+                Some(token) => push(token),
+                None => ControlFlow::Continue(()),
+            })
+    }
+}
+
+/// The closure is cloned into each iterator
+impl<Origin, F, G, U> IterFtzr<Origin> for Map<F, G, Origin>
+where
+    F: IterFtzr<Origin>,
+    G: Fn(F::TokenGroup) -> U + Clone,
+{
+    type TokenGroup = U;
+    type Iter = iter::Map<F::Iter, G>;
+    fn iterate_features(&self, origin: Origin) -> Self::Iter {
+        self.ftzr.iterate_features(origin).map(self.f.clone())
+    }
+}
+
+impl<Origin, F, P> IterFtzr<Origin> for Filter<F, P, Origin>
+where
+    F: IterFtzr<Origin>,
+    P: Fn(&F::TokenGroup) -> bool + Clone,
+{
+    type TokenGroup = F::TokenGroup;
+    type Iter = iter::Filter<F::Iter, P>;
+    fn iterate_features(&self, origin: Origin) -> Self::Iter {
+        self.ftzr
+            .iterate_features(origin)
+            .filter(self.predicate.clone())
+    }
+}
+
+impl<Origin, F, G, U> IterFtzr<Origin> for FilterMap<F, G, Origin>
+where
+    F: IterFtzr<Origin>,
+    G: Fn(F::TokenGroup) -> Option<U> + Clone,
+{
+    type TokenGroup = U;
+    type Iter = iter::FilterMap<F::Iter, G>;
+    fn iterate_features(&self, origin: Origin) -> Self::Iter {
+        self.ftzr
+            .iterate_features(origin)
+            .filter_map(self.f.clone())
+    }
+}
+
+/// Mapping doesn't change where each token group came from
+impl<F: LinearFixed, G, Meta> LinearFixed for Map<F, G, Meta> {
+    fn chunk_size(&self) -> usize {
+        self.ftzr.chunk_size()
+    }
+}
+
+impl<F: MinLen, G, Meta> MinLen for Map<F, G, Meta> {
+    fn min_len(&self) -> usize {
+        self.ftzr.min_len()
+    }
+}
+
+impl<F: MinLen, P, Meta> MinLen for Filter<F, P, Meta> {
+    fn min_len(&self) -> usize {
+        self.ftzr.min_len()
+    }
+}
+
+impl<F: MinLen, G, Meta> MinLen for FilterMap<F, G, Meta> {
+    fn min_len(&self) -> usize {
+        self.ftzr.min_len()
+    }
+}
+
+/// A featurizer combinator that only keeps the first occurrence of each token group in an input. Created with `unique(ftzr)`
+#[derive(Default)]
+pub struct Unique<F, T> {
+    ftzr: F,
+    seen: Mutex<FxHashSet<T>>,
+}
+
+impl<F: Clone, T> Clone for Unique<F, T> {
+    fn clone(&self) -> Self {
+        unique(self.ftzr.clone())
+    }
+}
+
+impl<F: fmt::Debug, T> fmt::Debug for Unique<F, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Unique")
+            .field("ftzr", &self.ftzr)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A featurizer combinator that only keeps the first `k` token groups of an input. Created with `take(ftzr, k)`
+#[derive(Hash, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Take<F> {
+    ftzr: F,
+    k: usize,
+}
+
+/// A featurizer combinator that drops the first `k` token groups of an input. Created with `skip(ftzr, k)`
+#[derive(Hash, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Skip<F> {
+    ftzr: F,
+    k: usize,
+}
+
+/// Drops repeated token groups within each input, keeping the first-seen order, so that any container (even a `Vec`) gets unique features per document. The token groups seen so far are cloned into a hash set (so cheap token groups like `&str` and `[u8; N]` work best), and distinct token groups are never dropped.
+///
+/// The hash set is cleared and reused for every input, so repeated calls don't allocate. A call that finds it in use, on another thread or because `push` featurizes with the same `unique` again, gets a set of its own. Since the set holds token groups, a `Unique` featurizes inputs of a single lifetime, and iterating with `iterate_features` always gives each iterator its own set.
+/// ```
+/// use creature_feature::ftzrs::{bigram, for_each, unique, whole};
+/// use creature_feature::traits::Ftzr;
+///
+/// let words: Vec<&str> = for_each(unique(whole())).featurize("one fish two fish".split(' '));
+/// assert_eq!(words.len(), 4); // each word is its own input
+///
+/// let ftzr = unique(for_each(whole()));
+/// let words: Vec<&str> = ftzr.featurize("one fish two fish".split(' '));
+/// assert_eq!(words, vec!["one", "fish", "two"]);
+///
+/// let grams: Vec<String> = unique(bigram()).featurize("abab");
+/// assert_eq!(grams, vec!["ab", "ba"]);
+/// ```
+pub fn unique<F, T>(ftzr: F) -> Unique<F, T> {
+    Unique {
+        ftzr,
+        seen: Mutex::default(),
+    }
+}
+
+impl<F, T> Unique<F, T> {
+    /// Takes the reused hash set, or a new one if it's in use
+    fn take_seen(&self) -> FxHashSet<T> {
+        let mut seen = match self.seen.try_lock() {
+            Ok(mut seen) => mem::take(&mut *seen),
+            Err(_) => FxHashSet::default(),
+        };
+        seen.clear();
+        seen
+    }
+
+    /// Puts the hash set back for the next call, without the token groups of this one
+    fn put_seen(&self, mut seen: FxHashSet<T>) {
+        seen.clear();
+        if let Ok(mut slot) = self.seen.try_lock() {
+            *slot = seen;
+        }
+    }
+}
+
+/// Only keeps the first `k` token groups of each input. The inner featurizer is stopped as soon as `k` token groups are produced (see [`Ftzr::push_tokens_while`]), when it's built on `IterFtzr`. Other featurizers, like `bins` or `word_piece`, still visit the rest of the input, but nothing more is kept.
+/// ```
+/// use creature_feature::ftzrs::{bislice, skip, take, unique};
+/// use creature_feature::traits::Ftzr;
+///
+/// let grams: Vec<&str> = take(bislice(), 2).featurize("abcdef");
+/// assert_eq!(grams, vec!["ab", "bc"]);
+///
+/// let grams: Vec<&str> = take(skip(bislice(), 1), 2).featurize("abcdef");
+/// assert_eq!(grams, vec!["bc", "cd"]);
+///
+/// // the first two unique grams, rather than the unique grams among the first two
+/// let grams: Vec<&str> = take(unique(bislice()), 2).featurize("aaab");
+/// assert_eq!(grams, vec!["aa", "ab"]);
+/// ```
+pub fn take<F>(ftzr: F, k: usize) -> Take<F> {
+    Take { ftzr, k }
+}
+
+/// Drops the first `k` token groups of each input. (See [`take`])
+pub fn skip<F>(ftzr: F, k: usize) -> Skip<F> {
+    Skip { ftzr, k }
+}
+
+/// A featurizer combinator that keeps about one in `n` distinct token groups, chosen by their hash. Created with `sample(ftzr, n)`
+#[derive(Hash, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Sample<F> {
+    ftzr: F,
+    n: usize,
+}
+
+/// Keeps a token group when its `HashedAs<u64>` falls in the lowest `1/n` of the hash space, so about one in `n` distinct token groups is kept. The choice only depends on the token group, so the same ones are kept in every input and every run, which keeps sampled features comparable between documents (unlike `take`, which keeps whatever comes first). A `n` of `0` is the same as `1`, which keeps everything.
+/// ```
+/// use creature_feature::ftzrs::{bislice, sample};
+/// use creature_feature::traits::Ftzr;
+///
+/// let text = "the quick brown fox jumps over the lazy dog";
+/// let all: Vec<&str> = bislice().featurize(text);
+/// let some: Vec<&str> = sample(bislice(), 4).featurize(text);
+/// assert!(!some.is_empty() && some.len() < all.len());
+///
+/// // a gram is either kept in every input, or in none
+/// let again: Vec<&str> = sample(bislice(), 4).featurize("lazy dog");
+/// for gram in bislice().featurize::<&str, Vec<_>>("lazy dog") {
+///     assert_eq!(again.contains(&gram), some.contains(&gram));
+/// }
+/// ```
+pub fn sample<F>(ftzr: F, n: usize) -> Sample<F> {
+    Sample { ftzr, n }
+}
+
+/// Whether `token` is in the lowest `1/n` of the hash space
+fn is_sampled<T: Hash>(token: &T, n: usize) -> bool {
+    let HashedAs(hash): HashedAs<u64> = FeatureFrom::from(token);
+    hash <= u64::MAX / n.max(1) as u64
+}
+
+impl<Origin, F, T> Ftzr<Origin> for Unique<F, T>
+where
+    F: Ftzr<Origin, TokenGroup = T>,
+    T: Hash + Eq + Clone,
+{
+    type TokenGroup = T;
+    fn push_tokens<Push>(&self, origin: Origin, push: &mut Push)
+    where
+        Push: FnMut(Self::TokenGroup),
+    {
+        let mut seen = self.take_seen();
+        self.ftzr.push_tokens(origin, &mut |token| {
+            if seen.insert(token.clone()) {
+                push(token)
+            }
+        });
+        self.put_seen(seen);
+    }
+
+    fn push_tokens_while<Push>(&self, origin: Origin, push: &mut Push) -> ControlFlow<()>
+    where
+        Push: FnMut(Self::TokenGroup) -> ControlFlow<()>,
+    {
+        let mut seen = self.take_seen();
+        let flow = self.ftzr.push_tokens_while(origin, &mut |token| {
+            if seen.insert(token.clone()) {
+                push(token)
+            } else {
+                ControlFlow::Continue(())
+            }
+        });
+        self.put_seen(seen);
+        flow
+    }
+}
+
+impl<Origin, F> Ftzr<Origin> for Take<F>
+where
+    F: Ftzr<Origin>,
+{
+    type TokenGroup = F::TokenGroup;
+    fn push_tokens<Push>(&self, origin: Origin, push: &mut Push)
+    where
+        Push: FnMut(Self::TokenGroup),
+    {
+        self.push_tokens_while(origin, &mut |token| {
+            push(token);
+            ControlFlow::Continue(())
+        });
+    }
+
+    fn push_tokens_while<Push>(&self, origin: Origin, push: &mut Push) -> ControlFlow<()>
+    where
+        Push: FnMut(Self::TokenGroup) -> ControlFlow<()>,
+    {
+        if self.k == 0 {
+            return ControlFlow::Continue(());
+        }
+        let mut left = self.k;
+        // reaching `k` stops the inner featurizer, but only `push` stopping is passed on
+        let mut flow = ControlFlow::Continue(());
+        self.ftzr.push_tokens_while(origin, &mut |token| {
+            left -= 1;
+            flow = push(token);
+            if left == 0 {
+                ControlFlow::Break(())
+            } else {
+                flow
+            }
+        });
+        flow
+    }
+}
+
+impl<Origin, F> Ftzr<Origin> for Skip<F>
+where
+    F: Ftzr<Origin>,
+{
+    type TokenGroup = F::TokenGroup;
+    fn push_tokens<Push>(&self, origin: Origin, push: &mut Push)
+    where
+        Push: FnMut(Self::TokenGroup),
+    {
+        let mut skipped = 0;
+        self.ftzr.push_tokens(origin, &mut |token| {
+            if skipped < self.k {
+                skipped += 1;
+            } else {
+                push(token)
+            }
+        })
+    }
+
+    fn push_tokens_while<Push>(&self, origin: Origin, push: &mut Push) -> ControlFlow<()>
+    where
+        Push: FnMut(Self::TokenGroup) -> ControlFlow<()>,
+    {
+        let mut skipped = 0;
+        self.ftzr.push_tokens_while(origin, &mut |token| {
+            if skipped < self.k {
+                skipped += 1;
+                ControlFlow::Continue(())
+            } else {
+                push(token)
+            }
+        })
+    }
+}
+
+impl<Origin, F> Ftzr<Origin> for Sample<F>
+where
+    F: Ftzr<Origin>,
+    F::TokenGroup: Hash,
+{
+    type TokenGroup = F::TokenGroup;
+    fn push_tokens<Push>(&self, origin: Origin, push: &mut Push)
+    where
+        Push: FnMut(Self::TokenGroup),
+    {
+        self.ftzr.push_tokens(origin, &mut |token| {
+            if is_sampled(&token, self.n) {
+                push(token)
+            }
+        })
+    }
+
+    fn push_tokens_while<Push>(&self, origin: Origin, push: &mut Push) -> ControlFlow<()>
+    where
+        Push: FnMut(Self::TokenGroup) -> ControlFlow<()>,
+    {
+        self.ftzr.push_tokens_while(origin, &mut |token| {
+            if is_sampled(&token, self.n) {
+                push(token)
+            } else {
+                ControlFlow::Continue(())
+            }
+        })
+    }
+}
+
+/// The associated iterator for `Unique`, which has its own hash set
+#[derive(Clone, Debug)]
+pub struct UniqueIter<I: Iterator> {
+    iter: I,
+    seen: FxHashSet<I::Item>,
+}
+
+impl<I> Iterator for UniqueIter<I>
+where
+    I: Iterator,
+    I::Item: Hash + Eq + Clone,
+{
+    type Item = I::Item;
+    fn next(&mut self) -> Option<Self::Item> {
+        let seen = &mut self.seen;
+        self.iter.find(|token| seen.insert(token.clone()))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
+
+impl<Origin, F, T> IterFtzr<Origin> for Unique<F, T>
+where
+    F: IterFtzr<Origin, TokenGroup = T>,
+    T: Hash + Eq + Clone,
+{
+    type TokenGroup = T;
+    type Iter = UniqueIter<F::Iter>;
+    fn iterate_features(&self, origin: Origin) -> Self::Iter {
+        UniqueIter {
+            iter: self.ftzr.iterate_features(origin),
+            seen: FxHashSet::default(),
+        }
+    }
+}
+
+impl<Origin, F> IterFtzr<Origin> for Take<F>
+where
+    F: IterFtzr<Origin>,
+{
+    type TokenGroup = F::TokenGroup;
+    type Iter = iter::Take<F::Iter>;
+    fn iterate_features(&self, origin: Origin) -> Self::Iter {
+        self.ftzr.iterate_features(origin).take(self.k)
+    }
+}
+
+impl<Origin, F> IterFtzr<Origin> for Skip<F>
+where
+    F: IterFtzr<Origin>,
+{
+    type TokenGroup = F::TokenGroup;
+    type Iter = iter::Skip<F::Iter>;
+    fn iterate_features(&self, origin: Origin) -> Self::Iter {
+        self.ftzr.iterate_features(origin).skip(self.k)
+    }
+}
+
+/// The associated iterator for `Sample`
+#[derive(Clone, Debug)]
+pub struct SampleIter<I> {
+    iter: I,
+    n: usize,
+}
+
+impl<I> Iterator for SampleIter<I>
+where
+    I: Iterator,
+    I::Item: Hash,
+{
+    type Item = I::Item;
+    fn next(&mut self) -> Option<Self::Item> {
+        let n = self.n;
+        self.iter.find(|token| is_sampled(token, n))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
+
+impl<Origin, F> IterFtzr<Origin> for Sample<F>
+where
+    F: IterFtzr<Origin>,
+    F::TokenGroup: Hash,
+{
+    type TokenGroup = F::TokenGroup;
+    type Iter = SampleIter<F::Iter>;
+    fn iterate_features(&self, origin: Origin) -> Self::Iter {
+        SampleIter {
+            iter: self.ftzr.iterate_features(origin),
+            n: self.n,
+        }
+    }
+}
+
+impl<F: MinLen, T> MinLen for Unique<F, T> {
+    fn min_len(&self) -> usize {
+        self.ftzr.min_len()
+    }
+}
+
+/// The first `k` token groups of a shorter input are still kept
+impl<F: MinLen> MinLen for Take<F> {
+    fn min_len(&self) -> usize {
+        self.ftzr.min_len()
+    }
+}
+
+/// The first `k` token groups are dropped no matter how long the input is
+impl<F: MinLen> MinLen for Skip<F> {
+    fn min_len(&self) -> usize {
+        self.ftzr.min_len()
+    }
+}
+
+impl<F: MinLen> MinLen for Sample<F> {
+    fn min_len(&self) -> usize {
+        self.ftzr.min_len()
+    }
+}
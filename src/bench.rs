@@ -2,6 +2,7 @@ use crate::accum_ftzr::*;
 use crate::hashedfeature::*;
 use crate::n_gram::*;
 use crate::n_slice::*;
+use crate::rolling_hash::*;
 use crate::tokengroup::chars_of;
 //use senor_borroso::hasfeatures::HasFeatures;
 use std::time::Instant;
@@ -83,6 +84,16 @@ pub(crate) fn bench() {
                 },
             ); */
 
+            bench_single(
+                $n,
+                &"rolling_hash + Vec<HashedAs<u64>> (from &str)",
+                bigstring,
+                |s| {
+                    let v: Vec<HashedAs<u64>> = rolling_hash($n).featurize(s);
+                    v.len()
+                },
+            );
+
             bench_single(
                 $n,
                 &"n_gram + Vec<HashedAs<u64>> (from &str)",
@@ -203,6 +214,16 @@ pub(crate) fn bench() {
                 },
             );
 
+            bench_single(
+                $n,
+                &"rolling_hash + Vec<HashedAs<u64>> (from Vec<char>)",
+                &chars,
+                |s| {
+                    let v: Vec<HashedAs<u64>> = rolling_hash($n).featurize(s);
+                    v.len()
+                },
+            );
+
             /*
             bench_single(
                 $n,
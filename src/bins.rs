@@ -0,0 +1,197 @@
+use crate::accum_ftzr::{Ftzr, MinLen};
+use crate::feature_from::{try_via_from, FeatureFrom};
+use crate::internal::impl_push_slice_ftrzs;
+use crate::numeric::Numeric;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A featurizer that quantizes numbers into bins. Created with `uniform_bins`, `log_bins`, `quantile_bins` or `bin_edges`
+///
+/// Bins are separated by sorted edges: a value `x` is in bin `i` when `edges[i - 1] <= x < edges[i]`, so values below the first edge are in bin `0` and values from the last edge up are in the last bin. NaN is skipped. Works on a single number or on a slice of numbers (of any [`Numeric`](crate::ftzrs::misc::Numeric) type), producing one [`Bin`] per value.
+///
+/// To mix numeric columns with text features in the same `HashedAs<u64>` space, featurize each column with [`field`](crate::ftzrs::field), which tags its tokens with the column's name so equal bin ids of different columns stay different:
+/// ```
+/// use creature_feature::convert::Merged;
+/// use creature_feature::featurizers;
+/// use creature_feature::ftzrs::{field, log_bins, trigram};
+/// use creature_feature::traits::Ftzr;
+/// use creature_feature::HashedAs;
+/// use std::collections::HashSet;
+///
+/// struct Listing {
+///     title: String,
+///     price: f64,
+/// }
+///
+/// // a fn, since a closure can't return a borrow of its argument
+/// fn title(l: &Listing) -> &str {
+///     &l.title
+/// }
+///
+/// let ftzr = featurizers!(
+///     field("title", title, trigram()),
+///     field("price", |l: &Listing| l.price, log_bins(1.0, 10_000.0, 8)),
+/// );
+///
+/// let listing = Listing { title: "red bike".to_string(), price: 120.0 };
+/// let feats: HashSet<Merged<HashedAs<u64>>> = ftzr.featurize(&listing);
+/// assert_eq!(feats.len(), 7);
+/// ```
+#[derive(Clone, PartialEq, PartialOrd, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Bins {
+    edges: Vec<f64>,
+}
+
+/// The id of a bin, counting from `0`. This is the TokenGroup of [`Bins`], and it can be used with `HashedAs`, `Bag`, etc.
+#[derive(Hash, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Bin(pub u32);
+
+/// `k` bins of equal width between `min` and `max`. Values outside of `min..max` go to the first or last bin.
+/// ```
+/// use creature_feature::ftzrs::misc::Bin;
+/// use creature_feature::ftzrs::uniform_bins;
+/// use creature_feature::traits::Ftzr;
+///
+/// let feats: Vec<Bin> = uniform_bins(0.0, 1.0, 4).featurize(&[0.1, 0.3, 0.5, 0.99, 7.0]);
+/// assert_eq!(feats, vec![Bin(0), Bin(1), Bin(2), Bin(3), Bin(3)]);
+/// ```
+pub fn uniform_bins(min: f64, max: f64, k: usize) -> Bins {
+    bin_edges(
+        (1..k)
+            .map(|i| min + (max - min) * (i as f64 / k as f64))
+            .collect(),
+    )
+}
+
+/// `k` bins between `min` and `max` (both positive) that are equally wide on a log scale. Good for prices, counts and durations.
+/// ```
+/// use creature_feature::ftzrs::misc::Bin;
+/// use creature_feature::ftzrs::log_bins;
+/// use creature_feature::traits::Ftzr;
+///
+/// let feats: Vec<Bin> = log_bins(1.0, 1000.0, 3).featurize(&[5.0, 50.0, 500.0]);
+/// assert_eq!(feats, vec![Bin(0), Bin(1), Bin(2)]);
+/// ```
+pub fn log_bins(min: f64, max: f64, k: usize) -> Bins {
+    let (lo, hi) = (min.ln(), max.ln());
+    bin_edges(
+        (1..k)
+            .map(|i| (lo + (hi - lo) * (i as f64 / k as f64)).exp())
+            .collect(),
+    )
+}
+
+/// Up to `k` bins that each hold about the same number of values from `sample`. Edges that would be repeated (because of repeated values in `sample`) are only kept once, so there may be fewer than `k` bins. NaN is ignored.
+/// ```
+/// use creature_feature::ftzrs::misc::Bin;
+/// use creature_feature::ftzrs::quantile_bins;
+/// use creature_feature::traits::Ftzr;
+///
+/// let sample = [1, 2, 3, 4, 100, 200, 300, 400];
+/// let feats: Vec<Bin> = quantile_bins(&sample, 2).featurize(&[3.0, 150.0]);
+/// assert_eq!(feats, vec![Bin(0), Bin(1)]);
+/// ```
+pub fn quantile_bins<F: Numeric>(sample: &[F], k: usize) -> Bins {
+    let mut sorted: Vec<f64> = sample
+        .iter()
+        .map(|x| x.to_f64())
+        .filter(|x: &f64| !x.is_nan())
+        .collect();
+    sorted.sort_by(f64::total_cmp);
+    if sorted.is_empty() {
+        return bin_edges(Vec::new());
+    }
+    bin_edges((1..k).map(|i| sorted[i * sorted.len() / k]).collect())
+}
+
+/// Bins with explicit edges. The edges are sorted, and NaN or repeated edges are dropped.
+/// ```
+/// use creature_feature::ftzrs::misc::Bin;
+/// use creature_feature::ftzrs::bin_edges;
+/// use creature_feature::traits::Ftzr;
+///
+/// let ages = bin_edges(vec![18.0, 65.0, 13.0]);
+/// let feats: Vec<Bin> = ages.featurize(&[8, 13, 40, 90]);
+/// assert_eq!(feats, vec![Bin(0), Bin(1), Bin(2), Bin(3)]);
+/// ```
+pub fn bin_edges(mut edges: Vec<f64>) -> Bins {
+    edges.retain(|e| !e.is_nan());
+    edges.sort_by(f64::total_cmp);
+    edges.dedup();
+    Bins { edges }
+}
+
+impl Bins {
+    /// The bin of a single value, or `None` for NaN
+    pub fn bin(&self, x: f64) -> Option<Bin> {
+        if x.is_nan() {
+            None
+        } else {
+            Some(Bin(self.edges.partition_point(|e| *e <= x) as u32))
+        }
+    }
+
+    /// The sorted edges between bins
+    pub fn edges(&self) -> &[f64] {
+        &self.edges
+    }
+
+    /// The total number of bins, which is one more than the number of edges
+    pub fn num_bins(&self) -> usize {
+        self.edges.len() + 1
+    }
+}
+
+impl<'a, F: Numeric> Ftzr<&'a [F]> for Bins {
+    type TokenGroup = Bin;
+    fn push_tokens<Push>(&self, origin: &'a [F], push: &mut Push)
+    where
+        Push: FnMut(Self::TokenGroup),
+    {
+        for x in origin.iter() {
+            if let Some(bin) = self.bin(x.to_f64()) {
+                push(bin)
+            }
+        }
+    }
+}
+
+impl_push_slice_ftrzs!(Bins);
+
+/// Each value is binned on its own
+impl MinLen for Bins {
+    fn min_len(&self) -> usize {
+        0
+    }
+}
+
+impl<F: Numeric> Ftzr<F> for Bins {
+    type TokenGroup = Bin;
+    fn push_tokens<Push>(&self, origin: F, push: &mut Push)
+    where
+        Push: FnMut(Self::TokenGroup),
+    {
+        if let Some(bin) = self.bin(origin.to_f64()) {
+            push(bin)
+        }
+    }
+}
+
+impl FeatureFrom<Bin> for Bin {
+    fn from(token_group: Bin) -> Self {
+        token_group
+    }
+}
+
+impl FeatureFrom<Bin> for u32 {
+    fn from(token_group: Bin) -> Self {
+        token_group.0
+    }
+}
+
+try_via_from! {
+    [] Bin => Bin;
+    [] Bin => u32;
+}
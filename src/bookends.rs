@@ -1,11 +1,12 @@
-use crate::accum_ftzr::{Ftzr, IterFtzr};
+use crate::accum_ftzr::{Ftzr, IterFtzr, MinLen, SpanFtzr};
 use crate::convert::Merged;
-use crate::feature_from::FeatureFrom;
+use crate::error::FeatureError;
+use crate::feature_from::{FeatureFrom, TryFeatureFrom};
 use crate::internal::impl_ftrzs_2;
 use crate::n_gram::NGram;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
-use std::ops::Deref;
+use std::ops::{ControlFlow, Deref};
 
 /// `Bookends<A,B>` is a featurizer combinator that will run 'A' on the beggining of the data and run 'B' on the end of the data.
 /// Its main purpose is to make it easier to handle prefixes and suffices. Created by `bookends`
@@ -18,7 +19,7 @@ pub struct BookEnds<A, B> {
     back_size: usize,
 }
 
-/// A tool to featurize prefixes and suffixes. `bookends((a, n), (b, m))` will run featurizer `a` for the first `n` tokens and will run featurizer `b` for the last `m` tokens. All tokens between are skipped.
+/// A tool to featurize prefixes and suffixes. `bookends((a, n), (b, m))` will run featurizer `a` for the first `n` tokens and will run featurizer `b` for the last `m` tokens. All tokens between are skipped. If the input has fewer than `n` (or `m`) tokens, that end is the whole input.
 /// # Example
 /// ```
 ///use creature_feature::ftzrs::misc::FrontBack;
@@ -55,12 +56,31 @@ where
     fn iterate_features(&self, origin: &'a [T]) -> Self::Iter {
         BookEndsIter(
             true,
-            self.front.iterate_features(&origin[..self.front_size]),
+            self.front.iterate_features(self.front_of(origin)),
             self.back
-                .iterate_features(&origin[origin.len() - self.back_size..]),
+                .iterate_features(&origin[self.back_start(origin)..]),
         )
     }
 }
+
+impl<A, B> BookEnds<A, B> {
+    /// The first `front_size` tokens, or all of them if there aren't enough
+    fn front_of<'a, T>(&self, origin: &'a [T]) -> &'a [T] {
+        &origin[..self.front_size.min(origin.len())]
+    }
+
+    /// Where the last `back_size` tokens start, or `0` if there aren't enough
+    fn back_start<T>(&self, origin: &[T]) -> usize {
+        origin.len().saturating_sub(self.back_size)
+    }
+}
+
+/// Shorter inputs are clamped, so that an end is the whole input (see `bookends`)
+impl<A, B> MinLen for BookEnds<A, B> {
+    fn min_len(&self) -> usize {
+        self.front_size.max(self.back_size)
+    }
+}
 /*
 impl<'a, TA: 'a, TB: 'a, A, B> IterFtzr<&'a str> for BookEnds<A, B>
 where
@@ -194,6 +214,44 @@ where
     }
 }
 
+impl<A, B, Ax, Bx> TryFeatureFrom<FrontBack<A, B>> for Result<Ax, Bx>
+where
+    Ax: TryFeatureFrom<A>,
+    Bx: TryFeatureFrom<B>,
+{
+    fn try_from(x: FrontBack<A, B>) -> Result<Self, FeatureError> {
+        Ok(match x {
+            FrontBack::Front(a) => Ok(TryFeatureFrom::try_from(a)?),
+            FrontBack::Back(a) => Err(TryFeatureFrom::try_from(a)?),
+        })
+    }
+}
+
+impl<A, B, C> TryFeatureFrom<FrontBack<A, B>> for Merged<C>
+where
+    C: TryFeatureFrom<A> + TryFeatureFrom<B>,
+{
+    fn try_from(x: FrontBack<A, B>) -> Result<Self, FeatureError> {
+        Ok(Merged(match x {
+            FrontBack::Front(a) => TryFeatureFrom::try_from(a)?,
+            FrontBack::Back(a) => TryFeatureFrom::try_from(a)?,
+        }))
+    }
+}
+
+impl<A, B, Ax, Bx> TryFeatureFrom<FrontBack<A, B>> for FrontBack<Ax, Bx>
+where
+    Ax: TryFeatureFrom<A>,
+    Bx: TryFeatureFrom<B>,
+{
+    fn try_from(x: FrontBack<A, B>) -> Result<Self, FeatureError> {
+        Ok(match x {
+            FrontBack::Front(a) => FrontBack::Front(TryFeatureFrom::try_from(a)?),
+            FrontBack::Back(a) => FrontBack::Back(TryFeatureFrom::try_from(a)?),
+        })
+    }
+}
+
 impl<A, B, Ax, Bx> FeatureFrom<FrontBack<A, B>> for FrontBack<Ax, Bx>
 where
     Ax: FeatureFrom<A>,
@@ -246,4 +304,91 @@ where
             push(t)
         }
     }
+
+    fn push_tokens_while<Push>(&self, origin: Origin, push: &mut Push) -> ControlFlow<()>
+    where
+        Push: FnMut(Self::TokenGroup) -> ControlFlow<()>,
+    {
+        self.iterate_features(origin).try_for_each(push)
+    }
+}
+
+impl<'a, T, A, B> SpanFtzr<&'a [T]> for BookEnds<A, B>
+where
+    A: SpanFtzr<&'a [T]>,
+    B: SpanFtzr<&'a [T], Span = A::Span>,
+{
+    type Span = A::Span;
+    type TokenGroup = FrontBack<A::TokenGroup, B::TokenGroup>;
+    fn push_spans<Push>(&self, origin: &'a [T], offset: usize, push: &mut Push)
+    where
+        Push: FnMut(Self::Span, Self::TokenGroup),
+    {
+        let back_start = self.back_start(origin);
+        self.front
+            .push_spans(self.front_of(origin), offset, &mut |span, t| {
+                push(span, FrontBack::Front(t))
+            });
+        self.back.push_spans(
+            &origin[back_start..],
+            offset + back_start,
+            &mut |span, t| push(span, FrontBack::Back(t)),
+        );
+    }
+}
+
+impl<'a, A, B> SpanFtzr<&'a str> for BookEnds<A, B>
+where
+    Self: SpanFtzr<&'a [u8]>,
+{
+    type Span = <Self as SpanFtzr<&'a [u8]>>::Span;
+    type TokenGroup = <Self as SpanFtzr<&'a [u8]>>::TokenGroup;
+    fn push_spans<Push>(&self, origin: &'a str, offset: usize, push: &mut Push)
+    where
+        Push: FnMut(Self::Span, Self::TokenGroup),
+    {
+        self.push_spans(origin.as_bytes(), offset, push)
+    }
+}
+
+impl<'a, A, B> SpanFtzr<&'a String> for BookEnds<A, B>
+where
+    Self: SpanFtzr<&'a [u8]>,
+{
+    type Span = <Self as SpanFtzr<&'a [u8]>>::Span;
+    type TokenGroup = <Self as SpanFtzr<&'a [u8]>>::TokenGroup;
+    fn push_spans<Push>(&self, origin: &'a String, offset: usize, push: &mut Push)
+    where
+        Push: FnMut(Self::Span, Self::TokenGroup),
+    {
+        self.push_spans(origin.as_bytes(), offset, push)
+    }
+}
+
+impl<'a, T, A, B> SpanFtzr<&'a Vec<T>> for BookEnds<A, B>
+where
+    Self: SpanFtzr<&'a [T]>,
+{
+    type Span = <Self as SpanFtzr<&'a [T]>>::Span;
+    type TokenGroup = <Self as SpanFtzr<&'a [T]>>::TokenGroup;
+    fn push_spans<Push>(&self, origin: &'a Vec<T>, offset: usize, push: &mut Push)
+    where
+        Push: FnMut(Self::Span, Self::TokenGroup),
+    {
+        self.push_spans(origin.as_slice(), offset, push)
+    }
+}
+
+impl<'a, T, A, B, const N: usize> SpanFtzr<&'a [T; N]> for BookEnds<A, B>
+where
+    Self: SpanFtzr<&'a [T]>,
+{
+    type Span = <Self as SpanFtzr<&'a [T]>>::Span;
+    type TokenGroup = <Self as SpanFtzr<&'a [T]>>::TokenGroup;
+    fn push_spans<Push>(&self, origin: &'a [T; N], offset: usize, push: &mut Push)
+    where
+        Push: FnMut(Self::Span, Self::TokenGroup),
+    {
+        self.push_spans(&origin[..], offset, push)
+    }
 }
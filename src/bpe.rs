@@ -0,0 +1,434 @@
+use crate::accum_ftzr::{Ftzr, MinLen};
+use crate::convert::Lossy;
+use crate::error::FeatureError;
+use crate::feature_from::{try_from_utf8, try_via_from, FeatureFrom, TryFeatureFrom};
+use crate::internal::impl_push_ftrzs;
+use crate::n_gram::bigram;
+use std::borrow::Cow;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::fmt;
+
+/// The number of ids reserved for single bytes. The first merge has this id.
+const BYTE_IDS: u32 = 256;
+
+/// A byte-pair encoding (BPE) tokenizer. Created with `bpe(words, num_merges)` or `BytePairEncoder::from_merges`
+///
+/// Ids `0..256` are single bytes, and merge `i` creates id `256 + i`. Encoding starts from the bytes of the input and repeatedly applies the earliest learned merge that's present (left to right), so it reproduces the segmentation seen while training. Each [`BpePiece`] can be featurized into a `u32` id, a `&[u8]`, a `&str` or a `String`. Byte-level merges can split a multi-byte char when it wasn't seen in training, so prefer `u32` or `&[u8]` for unicode input. A split char panics as a `&str` or `String`, just like `n_slice`, so use `Lossy<String>` to get U+FFFD in its place, or `Ftzr::try_featurize` to get an `InvalidUtf8` error.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct BytePairEncoder {
+    merges: Vec<(u32, u32)>,
+    ranks: HashMap<(u32, u32), u32>,
+    pieces: Vec<Vec<u8>>,
+}
+
+/// The token group of a `BytePairEncoder`: one piece of the input and its id. (See [`BytePairEncoder`])
+#[derive(Hash, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Debug)]
+pub struct BpePiece<'a> {
+    /// The bytes of the input that make up this piece
+    pub piece: &'a [u8],
+    /// The id of the piece in the vocabulary
+    pub id: u32,
+}
+
+/// Why a merge table couldn't be read by `BytePairEncoder::from_merges`. Lines are counted from `1`.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum MergeTableError {
+    /// The line isn't two ids separated by whitespace
+    Malformed {
+        /// The line number
+        line: usize,
+    },
+    /// The line uses an id that isn't a byte or the result of an earlier merge
+    UnknownId {
+        /// The line number
+        line: usize,
+        /// The unknown id
+        id: u32,
+    },
+    /// The line repeats an earlier merge
+    Duplicate {
+        /// The line number
+        line: usize,
+    },
+}
+
+impl fmt::Display for MergeTableError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MergeTableError::Malformed { line } => {
+                write!(f, "line {}: expected two ids separated by whitespace", line)
+            }
+            MergeTableError::UnknownId { line, id } => {
+                write!(
+                    f,
+                    "line {}: id {} isn't a byte or an earlier merge",
+                    line, id
+                )
+            }
+            MergeTableError::Duplicate { line } => {
+                write!(f, "line {}: repeats an earlier merge", line)
+            }
+        }
+    }
+}
+
+impl std::error::Error for MergeTableError {}
+
+/// Learns up to `num_merges` byte-pair merges from a corpus of words, and returns the encoder. Each step merges the most frequent adjacent pair of ids (ties go to the smallest pair, so training is deterministic), and training stops early once no pair is left. Words are usually split on whitespace first, so that no merge crosses a word boundary. (Compare to `BytePairEncoder::train_bytes`)
+/// ```
+/// use creature_feature::ftzrs::bpe;
+/// use creature_feature::ftzrs::misc::BytePairEncoder;
+/// use creature_feature::traits::Ftzr;
+///
+/// let corpus = "low lower lowest newer wider new";
+/// let encoder = bpe(corpus.split_whitespace(), 10);
+///
+/// let pieces: Vec<&str> = encoder.featurize("lowers");
+/// assert_eq!(pieces, vec!["lower", "s"]);
+///
+/// let ids: Vec<u32> = encoder.featurize("lowers");
+/// assert_eq!(encoder.piece(ids[0]), Some(&b"lower"[..]));
+///
+/// // the merge table can be saved and loaded again
+/// let table = encoder.to_merges();
+/// assert_eq!(BytePairEncoder::from_merges(&table), Ok(encoder));
+/// ```
+pub fn bpe<'a, I>(words: I, num_merges: usize) -> BytePairEncoder
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    BytePairEncoder::train_bytes(words.into_iter().map(str::as_bytes), num_merges)
+}
+
+impl BytePairEncoder {
+    /// Like `bpe`, but for a corpus of byte strings
+    pub fn train_bytes<'a, I>(words: I, num_merges: usize) -> Self
+    where
+        I: IntoIterator<Item = &'a [u8]>,
+    {
+        let mut freqs: HashMap<&'a [u8], usize> = HashMap::new();
+        for word in words {
+            *freqs.entry(word).or_insert(0) += 1;
+        }
+        let mut words: Vec<(Vec<u32>, usize)> = freqs
+            .into_iter()
+            .map(|(word, freq)| (word.iter().map(|b| *b as u32).collect(), freq))
+            .collect();
+
+        // the count of each pair, and the words it might be in, kept up to date after each merge
+        let mut counts: HashMap<[u32; 2], usize> = HashMap::new();
+        let mut places: HashMap<[u32; 2], Vec<usize>> = HashMap::new();
+        for (idx, (word, freq)) in words.iter().enumerate() {
+            add_pairs(word, *freq, &mut counts, &mut |pair| {
+                places.entry(pair).or_default().push(idx)
+            });
+        }
+        // the most frequent pair (the smallest on ties), with stale entries skipped when popped
+        let mut queue: BinaryHeap<(usize, Reverse<[u32; 2]>)> = counts
+            .iter()
+            .map(|(pair, count)| (*count, Reverse(*pair)))
+            .collect();
+
+        let mut encoder = BytePairEncoder::default();
+        while encoder.num_merges() < num_merges {
+            let [left, right] = match queue.pop() {
+                Some((count, Reverse(pair))) if counts.get(&pair) == Some(&count) => pair,
+                Some(_) => continue,
+                None => break,
+            };
+            let id = encoder.push_merge(left, right);
+            let mut affected = places.remove(&[left, right]).unwrap_or_default();
+            affected.sort_unstable();
+            affected.dedup();
+            let mut changed: Vec<[u32; 2]> = Vec::new();
+            for idx in affected {
+                let (word, freq) = &mut words[idx];
+                remove_pairs(word, *freq, &mut counts, &mut |pair| changed.push(pair));
+                merge_all(word, left, right, id);
+                add_pairs(word, *freq, &mut counts, &mut |pair| {
+                    changed.push(pair);
+                    places.entry(pair).or_default().push(idx)
+                });
+            }
+            changed.sort_unstable();
+            changed.dedup();
+            for pair in changed {
+                if let Some(count) = counts.get(&pair) {
+                    queue.push((*count, Reverse(pair)))
+                }
+            }
+        }
+        encoder
+    }
+
+    /// Reads a merge table written by `to_merges`: one merge per line, as the two ids being merged. Blank lines are skipped.
+    pub fn from_merges(table: &str) -> Result<Self, MergeTableError> {
+        let mut encoder = BytePairEncoder::default();
+        for (idx, text) in table.lines().enumerate() {
+            let line = idx + 1;
+            let mut ids = text.split_whitespace();
+            let (left, right) = match (ids.next(), ids.next(), ids.next()) {
+                (None, _, _) => continue,
+                (Some(left), Some(right), None) => (left, right),
+                _ => return Err(MergeTableError::Malformed { line }),
+            };
+            let mut parse = |id: &str| -> Result<u32, MergeTableError> {
+                let id: u32 = id
+                    .parse()
+                    .map_err(|_| MergeTableError::Malformed { line })?;
+                if id < encoder.vocab_size() as u32 {
+                    Ok(id)
+                } else {
+                    Err(MergeTableError::UnknownId { line, id })
+                }
+            };
+            let (left, right) = (parse(left)?, parse(right)?);
+            if encoder.ranks.contains_key(&(left, right)) {
+                return Err(MergeTableError::Duplicate { line });
+            }
+            encoder.push_merge(left, right);
+        }
+        Ok(encoder)
+    }
+
+    /// Writes the merge table, in the order the merges were learned, so that it can be read by `from_merges`
+    pub fn to_merges(&self) -> String {
+        self.merges
+            .iter()
+            .map(|(left, right)| format!("{} {}\n", left, right))
+            .collect()
+    }
+
+    /// The bytes of the piece with this id, or `None` if the id is out of range
+    pub fn piece(&self, id: u32) -> Option<&[u8]> {
+        self.pieces.get(id as usize).map(Vec::as_slice)
+    }
+
+    /// The number of merges that were learned
+    pub fn num_merges(&self) -> usize {
+        self.merges.len()
+    }
+
+    /// The number of ids, which is `256` plus the number of merges
+    pub fn vocab_size(&self) -> usize {
+        self.pieces.len()
+    }
+
+    fn push_merge(&mut self, left: u32, right: u32) -> u32 {
+        let id = self.pieces.len() as u32;
+        let mut piece = self.pieces[left as usize].clone();
+        piece.extend_from_slice(&self.pieces[right as usize]);
+        self.ranks.insert((left, right), self.merges.len() as u32);
+        self.merges.push((left, right));
+        self.pieces.push(piece);
+        id
+    }
+}
+
+impl Default for BytePairEncoder {
+    /// An encoder without any merges, which produces one piece per byte
+    fn default() -> Self {
+        BytePairEncoder {
+            merges: Vec::new(),
+            ranks: HashMap::new(),
+            pieces: (0..BYTE_IDS).map(|b| vec![b as u8]).collect(),
+        }
+    }
+}
+
+/// Adds `freq` to the count of each adjacent pair in `word`, calling `on_pair` with each one
+fn add_pairs<F>(word: &[u32], freq: usize, counts: &mut HashMap<[u32; 2], usize>, on_pair: &mut F)
+where
+    F: FnMut([u32; 2]),
+{
+    bigram().push_tokens(word, &mut |pair| {
+        *counts.entry(pair).or_insert(0) += freq;
+        on_pair(pair)
+    })
+}
+
+/// Takes `freq` from the count of each adjacent pair in `word` (dropping the ones that reach `0`), calling `on_pair` with each one
+fn remove_pairs<F>(
+    word: &[u32],
+    freq: usize,
+    counts: &mut HashMap<[u32; 2], usize>,
+    on_pair: &mut F,
+) where
+    F: FnMut([u32; 2]),
+{
+    bigram().push_tokens(word, &mut |pair: [u32; 2]| {
+        if let Some(count) = counts.get_mut(&pair) {
+            *count -= freq;
+            if *count == 0 {
+                counts.remove(&pair);
+            }
+        }
+        on_pair(pair)
+    })
+}
+
+/// Replaces each occurrence of `left, right` in `ids` with `id`, from left to right, in one pass
+fn merge_all(ids: &mut Vec<u32>, left: u32, right: u32, id: u32) {
+    let (mut read, mut write) = (0, 0);
+    while read < ids.len() {
+        if read + 1 < ids.len() && ids[read] == left && ids[read + 1] == right {
+            ids[write] = id;
+            read += 2;
+        } else {
+            ids[write] = ids[read];
+            read += 1;
+        }
+        write += 1;
+    }
+    ids.truncate(write);
+}
+
+/// One piece of the input while encoding, as a node of a doubly linked list over the bytes
+#[derive(Copy, Clone)]
+struct Node {
+    id: u32,
+    prev: usize,
+    next: usize,
+    /// Merged into the node before it
+    merged: bool,
+}
+
+/// Marks the ends of the linked list of `Node`s
+const NONE: usize = usize::MAX;
+
+impl<'a> Ftzr<&'a [u8]> for BytePairEncoder {
+    type TokenGroup = BpePiece<'a>;
+    fn push_tokens<Push>(&self, origin: &'a [u8], push: &mut Push)
+    where
+        Push: FnMut(Self::TokenGroup),
+    {
+        // each node starts where its first byte was, so a piece is `origin[i..nodes[i].next]`
+        let mut nodes: Vec<Node> = (0..origin.len())
+            .map(|i| Node {
+                id: origin[i] as u32,
+                prev: i.wrapping_sub(1),
+                next: if i + 1 < origin.len() { i + 1 } else { NONE },
+                merged: false,
+            })
+            .collect();
+        let rank_at = |nodes: &[Node], i: usize| -> Option<u32> {
+            let next = nodes[i].next;
+            if next == NONE {
+                return None;
+            }
+            self.ranks.get(&(nodes[i].id, nodes[next].id)).copied()
+        };
+        // the earliest merge first, and the leftmost pair first within a merge. A merge only
+        // creates pairs of later merges, so this is the same as applying one merge at a time.
+        let mut queue: BinaryHeap<Reverse<(u32, usize)>> = (0..nodes.len())
+            .filter_map(|i| rank_at(&nodes, i).map(|rank| Reverse((rank, i))))
+            .collect();
+        while let Some(Reverse((rank, i))) = queue.pop() {
+            // skip pairs that were merged away, or changed by an earlier merge
+            if nodes[i].merged || rank_at(&nodes, i) != Some(rank) {
+                continue;
+            }
+            let right = nodes[i].next;
+            let after = nodes[right].next;
+            nodes[i].id = BYTE_IDS + rank;
+            nodes[i].next = after;
+            if after != NONE {
+                nodes[after].prev = i;
+            }
+            nodes[right].merged = true;
+            if let Some(rank) = rank_at(&nodes, i) {
+                queue.push(Reverse((rank, i)))
+            }
+            let before = nodes[i].prev;
+            if before != NONE {
+                if let Some(rank) = rank_at(&nodes, before) {
+                    queue.push(Reverse((rank, before)))
+                }
+            }
+        }
+        let mut i = if nodes.is_empty() { NONE } else { 0 };
+        while i != NONE {
+            let end = match nodes[i].next {
+                NONE => origin.len(),
+                next => next,
+            };
+            push(BpePiece {
+                piece: &origin[i..end],
+                id: nodes[i].id,
+            });
+            i = nodes[i].next;
+        }
+    }
+}
+
+impl_push_ftrzs!(BytePairEncoder);
+
+/// Every input, even an empty one, is encoded in full
+impl MinLen for BytePairEncoder {
+    fn min_len(&self) -> usize {
+        0
+    }
+}
+
+impl<'a> FeatureFrom<BpePiece<'a>> for BpePiece<'a> {
+    fn from(token_group: BpePiece<'a>) -> Self {
+        token_group
+    }
+}
+
+impl<'a> FeatureFrom<BpePiece<'a>> for u32 {
+    fn from(token_group: BpePiece<'a>) -> Self {
+        token_group.id
+    }
+}
+
+impl<'a> FeatureFrom<BpePiece<'a>> for &'a [u8] {
+    fn from(token_group: BpePiece<'a>) -> Self {
+        token_group.piece
+    }
+}
+
+/// Panics on a piece that splits a multi-byte character, like `FeatureFrom<&[u8]> for &str`
+impl<'a> FeatureFrom<BpePiece<'a>> for &'a str {
+    fn from(token_group: BpePiece<'a>) -> Self {
+        FeatureFrom::from(token_group.piece)
+    }
+}
+
+/// Panics on a piece that splits a multi-byte character, like `FeatureFrom<&[u8]> for String`
+impl<'a> FeatureFrom<BpePiece<'a>> for String {
+    fn from(token_group: BpePiece<'a>) -> Self {
+        FeatureFrom::from(token_group.piece)
+    }
+}
+
+impl<'a> FeatureFrom<BpePiece<'a>> for Lossy<Cow<'a, str>> {
+    fn from(token_group: BpePiece<'a>) -> Self {
+        FeatureFrom::from(token_group.piece)
+    }
+}
+
+impl<'a> FeatureFrom<BpePiece<'a>> for Lossy<String> {
+    fn from(token_group: BpePiece<'a>) -> Self {
+        FeatureFrom::from(token_group.piece)
+    }
+}
+
+impl<'a> TryFeatureFrom<BpePiece<'a>> for &'a str {
+    fn try_from(token_group: BpePiece<'a>) -> Result<Self, FeatureError> {
+        try_from_utf8(token_group.piece)
+    }
+}
+
+impl<'a> TryFeatureFrom<BpePiece<'a>> for String {
+    fn try_from(token_group: BpePiece<'a>) -> Result<Self, FeatureError> {
+        try_from_utf8(token_group.piece).map(str::to_owned)
+    }
+}
+
+try_via_from! {
+    ['a] BpePiece<'a> => BpePiece<'a>;
+    ['a] BpePiece<'a> => u32;
+    ['a] BpePiece<'a> => &'a [u8];
+}
@@ -0,0 +1,105 @@
+use crate::accum_ftzr::{Ftzr, IterFtzr, MinLen};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::ops::ControlFlow;
+use std::str::Chars;
+
+/// The type of a fixed-length n-gram over the code points of a `&str`. Created by `char_gram::<N>()`
+#[derive(Hash, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CharGram<const N: usize>();
+
+/// The associated iterator for `<CharGram<N> as IterFtzr<&str>>::Iter`
+#[derive(Clone, Debug)]
+pub struct CharGramIter<'a, const N: usize> {
+    primed: bool,
+    window: [char; N],
+    chars: Chars<'a>,
+}
+
+impl<'a, const N: usize> Iterator for CharGramIter<'a, N> {
+    type Item = [char; N];
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.primed {
+            for slot in self.window.iter_mut() {
+                *slot = self.chars.next()?;
+            }
+            self.primed = true;
+            return Some(self.window);
+        }
+        let chr = self.chars.next()?;
+        if N > 0 {
+            self.window.rotate_left(1);
+            self.window[N - 1] = chr;
+        }
+        Some(self.window)
+    }
+}
+
+impl<'a, const N: usize> IterFtzr<&'a str> for CharGram<N> {
+    type TokenGroup = [char; N];
+    type Iter = CharGramIter<'a, N>;
+
+    fn iterate_features(&self, origin: &'a str) -> Self::Iter {
+        CharGramIter {
+            primed: false,
+            window: ['\0'; N],
+            chars: origin.chars(),
+        }
+    }
+}
+
+impl<'a, const N: usize> IterFtzr<&'a String> for CharGram<N> {
+    type TokenGroup = [char; N];
+    type Iter = CharGramIter<'a, N>;
+
+    fn iterate_features(&self, origin: &'a String) -> Self::Iter {
+        self.iterate_features(origin.as_str())
+    }
+}
+
+/// n-grams over the characters of a `&str` that respect UTF-8 boundaries, produces owned data (like `String`) or multiple `[char; N]`. There is no need to first collect the input into a `Vec<char>`. (Compare to `char_slice`)
+/// ```
+/// use creature_feature::ftzrs::char_gram;
+/// use creature_feature::traits::Ftzr;
+/// use creature_feature::HashedAs;
+///
+/// let feats: Vec<[char; 2]> = char_gram::<2>().featurize("über");
+/// assert_eq!(feats, vec![['ü', 'b'], ['b', 'e'], ['e', 'r']]);
+///
+/// let feats: Vec<String> = char_gram::<2>().featurize("über");
+/// let feats: Vec<HashedAs<u64>> = char_gram::<2>().featurize("über");
+/// ```
+pub fn char_gram<const N: usize>() -> CharGram<N> {
+    CharGram::<N>()
+}
+
+impl<Origin, const N: usize> Ftzr<Origin> for CharGram<N>
+where
+    Self: IterFtzr<Origin>,
+{
+    type TokenGroup = <Self as IterFtzr<Origin>>::TokenGroup;
+    fn push_tokens<Push>(&self, origin: Origin, push: &mut Push)
+    where
+        Push: FnMut(Self::TokenGroup),
+    {
+        for t in self.iterate_features(origin) {
+            push(t)
+        }
+    }
+
+    fn push_tokens_while<Push>(&self, origin: Origin, push: &mut Push) -> ControlFlow<()>
+    where
+        Push: FnMut(Self::TokenGroup) -> ControlFlow<()>,
+    {
+        self.iterate_features(origin).try_for_each(push)
+    }
+}
+
+/// `N` chars, which take at least `N` bytes of a `&str`
+impl<const N: usize> MinLen for CharGram<N> {
+    fn min_len(&self) -> usize {
+        N
+    }
+}
@@ -0,0 +1,195 @@
+use crate::accum_ftzr::{Ftzr, IterFtzr, MinLen};
+use crate::convert::Lossy;
+use crate::feature_from::{try_via_from, FeatureFrom};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::hash::{Hash, Hasher};
+use std::ops::{ControlFlow, Deref};
+
+/// A fixed-length n-gram over the code points of a `&str`. Created with `char_slice(n)`
+#[derive(Hash, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CharSlice {
+    n: usize,
+}
+
+/// n-grams over the characters of a `&str` that respect UTF-8 boundaries, produces borrowed `&str` windows of exactly `n` code points. Unlike `n_slice`, it will never panic on multilingual text and never allocates. (Compare to `char_gram`)
+/// ```
+/// use creature_feature::ftzrs::char_slice;
+/// use creature_feature::traits::Ftzr;
+///
+/// let feats: Vec<&str> = char_slice(2).featurize("añob");
+/// assert_eq!(feats, vec!["añ", "ño", "ob"]);
+///
+/// let feats: Vec<String> = char_slice(3).featurize("日本語です");
+/// assert_eq!(feats, vec!["日本語", "本語で", "語です"]);
+/// ```
+///
+/// Windows are hashed as their characters, so `HashedAs` features agree with `char_gram`, just like `n_slice` and `n_gram` agree. `char_slice(0)` produces one empty window per position, like `n_slice(0)`, `grapheme_slice(0)` and `word_slice(0)`.
+pub fn char_slice(n: usize) -> CharSlice {
+    CharSlice { n }
+}
+
+/// The TokenGroup for `CharSlice`, a borrowed window of code points. It derefs to `str`, but is hashed like the `[char; N]` from `char_gram`.
+#[derive(Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CharWindow<'a>(pub &'a str, usize);
+
+impl<'a> CharWindow<'a> {
+    /// A window covering all of `text`
+    pub fn new(text: &'a str) -> Self {
+        CharWindow(text, text.chars().count())
+    }
+}
+
+impl<'a> Deref for CharWindow<'a> {
+    type Target = str;
+    fn deref(&self) -> &Self::Target {
+        self.0
+    }
+}
+
+/// Identical to `Hash for [char]`
+impl<'a> Hash for CharWindow<'a> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // the length is known up front, so the text is only decoded once
+        self.1.hash(state);
+        for chr in self.0.chars() {
+            chr.hash(state);
+        }
+    }
+}
+
+impl<'a> FeatureFrom<CharWindow<'a>> for CharWindow<'a> {
+    fn from(token_group: CharWindow<'a>) -> Self {
+        token_group
+    }
+}
+
+impl<'a> FeatureFrom<CharWindow<'a>> for &'a str {
+    fn from(token_group: CharWindow<'a>) -> Self {
+        token_group.0
+    }
+}
+
+impl<'a> FeatureFrom<CharWindow<'a>> for String {
+    fn from(token_group: CharWindow<'a>) -> Self {
+        token_group.0.to_owned()
+    }
+}
+
+impl<'a> FeatureFrom<CharWindow<'a>> for Cow<'a, str> {
+    fn from(token_group: CharWindow<'a>) -> Self {
+        Cow::Borrowed(token_group.0)
+    }
+}
+
+impl<'a> FeatureFrom<CharWindow<'a>> for Vec<char> {
+    fn from(token_group: CharWindow<'a>) -> Self {
+        token_group.0.chars().collect()
+    }
+}
+
+impl<'a> FeatureFrom<CharWindow<'a>> for Lossy<String> {
+    fn from(token_group: CharWindow<'a>) -> Self {
+        Lossy(token_group.0.to_owned())
+    }
+}
+
+try_via_from! {
+    ['a] CharWindow<'a> => CharWindow<'a>;
+    ['a] CharWindow<'a> => &'a str;
+    ['a] CharWindow<'a> => String;
+    ['a] CharWindow<'a> => Cow<'a, str>;
+    ['a] CharWindow<'a> => Vec<char>;
+}
+
+/// The associated iterator for `<CharSlice as IterFtzr<&str>>::Iter`
+#[derive(Hash, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CharSliceIter<'a> {
+    n: usize,
+    start: usize,
+    end: Option<usize>,
+    data: &'a str,
+}
+
+#[inline]
+fn char_len_at(s: &str, idx: usize) -> Option<usize> {
+    s[idx..].chars().next().map(char::len_utf8)
+}
+
+impl<'a> Iterator for CharSliceIter<'a> {
+    type Item = CharWindow<'a>;
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let end = self.end?;
+        let ret = CharWindow(&self.data[self.start..end], self.n);
+        self.end = char_len_at(self.data, end).map(|len| end + len);
+        if let Some(len) = char_len_at(self.data, self.start) {
+            self.start += len;
+        }
+        Some(ret)
+    }
+}
+
+impl<'a> IterFtzr<&'a str> for CharSlice {
+    type TokenGroup = CharWindow<'a>;
+    type Iter = CharSliceIter<'a>;
+
+    fn iterate_features(&self, origin: &'a str) -> Self::Iter {
+        let end = match self.n {
+            0 => Some(0),
+            n => origin
+                .char_indices()
+                .nth(n - 1)
+                .map(|(idx, chr)| idx + chr.len_utf8()),
+        };
+        CharSliceIter {
+            n: self.n,
+            start: 0,
+            end,
+            data: origin,
+        }
+    }
+}
+
+impl<'a> IterFtzr<&'a String> for CharSlice {
+    type TokenGroup = CharWindow<'a>;
+    type Iter = CharSliceIter<'a>;
+
+    fn iterate_features(&self, origin: &'a String) -> Self::Iter {
+        self.iterate_features(origin.as_str())
+    }
+}
+
+impl<Origin> Ftzr<Origin> for CharSlice
+where
+    Self: IterFtzr<Origin>,
+{
+    type TokenGroup = <Self as IterFtzr<Origin>>::TokenGroup;
+    fn push_tokens<Push>(&self, origin: Origin, push: &mut Push)
+    where
+        Push: FnMut(Self::TokenGroup),
+    {
+        for t in self.iterate_features(origin) {
+            push(t)
+        }
+    }
+
+    fn push_tokens_while<Push>(&self, origin: Origin, push: &mut Push) -> ControlFlow<()>
+    where
+        Push: FnMut(Self::TokenGroup) -> ControlFlow<()>,
+    {
+        self.iterate_features(origin).try_for_each(push)
+    }
+}
+
+/// `n` chars, which take at least `n` bytes of a `&str`
+impl MinLen for CharSlice {
+    fn min_len(&self) -> usize {
+        self.n
+    }
+}
@@ -2,7 +2,7 @@
 
 use std::collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet, LinkedList, VecDeque};
 
-use crate::feature_from::FeatureFrom;
+use crate::feature_from::{FeatureFrom, TryFeatureFrom};
 use crate::tokengroup::*;
 use crate::whole_empty::*;
 
@@ -11,11 +11,51 @@ use crate::convert::*;
 use crate::for_each::*;
 
 use crate::multiftzr::*;
+use EitherGroup::{Left, Right};
 
 use crate::accum_ftzr::*;
 
 use crate::n_slice::*;
 
+use crate::n_range::*;
+
+use crate::skip_gram::*;
+
+use crate::strided::*;
+
+use crate::padded::*;
+
+use crate::spans::*;
+
+use crate::rolling_hash::*;
+
+use crate::winnow::*;
+
+use crate::sax::*;
+
+use crate::bins::*;
+
+use crate::field::*;
+
+use crate::deltas::*;
+
+use crate::bpe::*;
+
+use crate::word_piece::*;
+
+use crate::stemmed::*;
+
+use crate::normalized::*;
+
+use crate::stopwords::*;
+
+use crate::adapters::*;
+
+use crate::error::FeatureError;
+
+use std::borrow::Cow;
+use std::ops::{ControlFlow, Range};
+
 use crate::gap_gram::*;
 
 use crate::n_gram::*;
@@ -24,6 +64,15 @@ use crate::hashedfeature::HashedAs;
 
 use crate::bookends::*;
 
+use crate::char_gram::*;
+
+use crate::char_slice::*;
+
+use crate::word_gram::*;
+
+#[cfg(feature = "graphemes")]
+use crate::grapheme::*;
+
 fn featurize<Out, Origin, F: Ftzr<Origin>>(f: F, o: Origin) -> Vec<Out>
 where
     Out: FeatureFrom<F::TokenGroup>,
@@ -205,10 +254,9 @@ pub(crate) fn run_checks() {
 "#;
     let doc_iter = doc.lines().map(|line| line.split_ascii_whitespace());
 
-    //TODO
-    //let _feats: Vec<Merged<HashedAs>> =
-    //    for_each(for_each(bislice.and_then(n_slice(3)))).featurize(doc_iter);
-    //let _feats: Vec<Merged<String>> = n_gram::<2>().and_then(n_gram::<3>()).featurize(doc);
+    let _feats: Vec<Merged<HashedAs<u64>>> =
+        for_each(for_each(bislice.and_then(n_slice(3)))).featurize(doc_iter);
+    let _feats: Vec<Merged<String>> = n_gram::<2>().and_then(n_gram::<3>()).featurize(doc);
     let _feats: Vec<Merged<String>> = featurizers!(bigram, n_gram::<3>()).featurize(doc);
     let _feats: Vec<Merged<&str>> =
         for_each(featurizers!(bislice, n_slice(3))).featurize(doc.split_ascii_whitespace());
@@ -243,6 +291,2380 @@ pub(crate) fn run_checks() {
         gap_gram(every_other, 1, every_other).featurize(&nums);
     let _feats: Vec<([[i32; 1]; 4])> = gap_gram(every_other, 1, every_other).featurize(&nums);
 
+    ////////////////////////////
+    //         unicode &str
+    ////////////////////////////
+    {
+        let jp = "日本語です";
+        test_vec_feats!(
+            char_slice(2).featurize(jp),
+            &str,
+            &["日本", "本語", "語で", "です"]
+        );
+        test_vec_feats!(char_slice(5).featurize(jp), String, &["日本語です"]);
+        test_vec_feats!(char_slice(6).featurize(jp), &str, Vec::<&str>::new());
+        test_vec_feats!(char_slice(1).featurize(""), &str, Vec::<&str>::new());
+        test_vec_feats!(
+            char_gram::<2>().featurize(&jp.to_owned()),
+            String,
+            &["日本", "本語", "語で", "です"]
+        );
+        test_vec_feats!(
+            char_gram::<3>().featurize("ab"),
+            [char; 3],
+            Vec::<[char; 3]>::new()
+        );
+        test_vec_feats!(char_slice(0).featurize("añ"), &str, &["", "", ""]);
+        test_vec_feats!(char_gram::<0>().featurize("añ"), [char; 0], &[[], [], []]);
+
+        // the same window has the same hash, like `n_slice` and `n_gram`
+        let sliced: Vec<HashedAs<u64>> = char_slice(2).featurize(jp);
+        let grams: Vec<HashedAs<u64>> = char_gram::<2>().featurize(jp);
+        assert_eq!(sliced, grams);
+        let sliced: Vec<HashedAs<u32>> = char_slice(1).featurize("añ");
+        let grams: Vec<HashedAs<u32>> = char_gram::<1>().featurize("añ");
+        assert_eq!(sliced, grams);
+        test_vec_feats!(
+            char_slice(2).featurize("añb"),
+            Vec<char>,
+            &[vec!['a', 'ñ'], vec!['ñ', 'b']]
+        );
+    }
+
+    ////////////////////////////
+    //    n_range
+    ////////////////////////////
+    {
+        test_vec_feats!(
+            n_range(2, 3).featurize(ak),
+            &str,
+            &[
+                "ab", "abc", "bc", "bcd", "cd", "cde", "de", "def", "ef", "efg", "fg", "fgh", "gh",
+                "ghi", "hi", "hij", "ij", "ijk", "jk"
+            ]
+        );
+        test_vec_feats!(
+            n_range(2, 2).featurize(ak),
+            HashedAs<u64>,
+            ak_bigrams_feat64
+        );
+        test_vec_feats!(
+            n_range(0, 1).featurize(&[1, 2]),
+            Vec<i32>,
+            &[vec![1], vec![2]]
+        );
+        test_vec_feats!(n_range(3, 2).featurize(ak), &str, Vec::<&str>::new());
+        test_vec_feats!(n_range(5, 8).featurize("abcd"), &str, Vec::<&str>::new());
+        test_vec_feats!(n_range(1, 8).featurize(""), &str, Vec::<&str>::new());
+        test_vec_feats!(
+            n_range(2, 2).featurize(&n_usize_12[..]),
+            &[usize],
+            bigrams_12_usize
+        );
+        let _feats: Vec<&[usize]> = n_range(1, 3).featurize(n_usize_12);
+        let _feats: Vec<Vec<usize>> = n_range(1, 3).featurize(&n_usize_12.to_vec());
+        let _feats: Vec<String> = n_range(1, 3).featurize(&ak.to_owned());
+        let _feats: Vec<Vec<u8>> = bislice.featurize(ak);
+    }
+
+    ////////////////////////////
+    //    skip_gram
+    ////////////////////////////
+    {
+        test_vec_feats!(
+            skip_gram(1..=2, 1..=2, 1..=1).featurize("abcde"),
+            (&str, usize, &str),
+            &[
+                ("a", 1, "c"),
+                ("a", 2, "d"),
+                ("ab", 1, "d"),
+                ("ab", 2, "e"),
+                ("b", 1, "d"),
+                ("b", 2, "e"),
+                ("bc", 1, "e"),
+                ("c", 1, "e"),
+            ]
+        );
+        // with a single gap, it's the same as gap_gram
+        let a: Vec<(&str, &str)> = skip_gram(2..=2, 3..=3, 2..=2).featurize(ak);
+        let b: Vec<(&str, &str)> = gap_gram(bislice, 3, bislice).featurize(ak);
+        assert_eq!(a, b);
+        test_vec_feats!(
+            skip_gram(1..=1, 0..=0, 1..=1).featurize(ak),
+            (&str, &str),
+            {
+                let v: Vec<(&str, &str)> = gap_gram(n_slice(1), 0, n_slice(1)).featurize(ak);
+                v
+            }
+        );
+        test_vec_feats!(
+            skip_gram(1..=1, 0..=3, 1..=1).featurize("ab"),
+            (&str, &str),
+            &[("a", "b")]
+        );
+        test_vec_feats!(
+            skip_gram(1..=1, 20..=30, 1..=1).featurize(ak),
+            (&str, &str),
+            Vec::<(&str, &str)>::new()
+        );
+        test_vec_feats!(
+            skip_gram(1..=1, 0..=3, 1..=1).featurize(""),
+            (&str, &str),
+            Vec::<(&str, &str)>::new()
+        );
+        let _feats: Vec<HashedAs<u64>> = skip_gram(1..=1, 0..=3, 1..=1).featurize(n_usize_12);
+        let _feats: Vec<(Vec<usize>, Vec<usize>)> =
+            skip_gram(1..=1, 0..=3, 1..=1).featurize(&n_usize_12.to_vec());
+
+        // gaps wider than u16::MAX are kept exactly
+        let long = format!("a{}b", "_".repeat(70_000));
+        test_vec_feats!(
+            skip_gram(1..=1, 70_000..=70_000, 1..=1).featurize(&long),
+            (&str, usize, &str),
+            &[("a", 70_000, "b")]
+        );
+        let mut gaps = Vec::new();
+        gap_gram(n_slice(1), 70_000, n_slice(1))
+            .push_tokens(long.as_str(), &mut |pair: GapPair<&[u8], &[u8]>| {
+                gaps.push(pair.gap())
+            });
+        assert_eq!(gaps, vec![70_000]);
+    }
+
+    ////////////////////////////
+    //    strided
+    ////////////////////////////
+    {
+        // a stride of 1 (or 0) is the same as the plain featurizer
+        let a: Vec<&str> = n_slice(3).stride(1).featurize(ak);
+        let b: Vec<&str> = n_slice(3).featurize(ak);
+        assert_eq!(a, b);
+        let a: Vec<[u8; 3]> = n_gram::<3>().stride(0).featurize(ak);
+        let b: Vec<[u8; 3]> = n_gram::<3>().featurize(ak);
+        assert_eq!(a, b);
+
+        test_vec_feats!(
+            n_slice(2).stride(3).featurize("abcdefg"),
+            &str,
+            &["ab", "de"]
+        );
+        test_vec_feats!(
+            n_slice(2).stride(3).tail(ShortTail).featurize("abcdefg"),
+            &str,
+            &["ab", "de", "g"]
+        );
+        test_vec_feats!(
+            n_slice(2).stride(3).tail(ShortTail).featurize("abcdef"),
+            &str,
+            &["ab", "de"]
+        );
+        // overlapping windows only get a tail if the end isn't covered yet
+        test_vec_feats!(
+            n_slice(4).stride(2).tail(ShortTail).featurize("abcdefgh"),
+            &str,
+            &["abcd", "cdef", "efgh"]
+        );
+        test_vec_feats!(
+            n_slice(4).stride(2).tail(ShortTail).featurize("abcdefghi"),
+            &str,
+            &["abcd", "cdef", "efgh", "ghi"]
+        );
+        let padded_tail: Vec<[Pad<i32>; 4]> = n_gram::<4>()
+            .stride(2)
+            .tail(PadTail)
+            .featurize(&[1, 2, 3, 0, 5, 6, 0]);
+        assert_eq!(padded_tail.len(), 3);
+        assert_eq!(
+            padded_tail[2],
+            [Pad::Token(5), Pad::Token(6), Pad::Token(0), Pad::End]
+        );
+        // real zeros are never confused with padding
+        assert_eq!(padded_tail[0].iter().filter(|t| t.is_token()).count(), 4);
+        test_vec_feats!(
+            n_slice(3).stride(3).tail(PadTail).featurize("abcdefg"),
+            String,
+            &["abc", "def", "g\u{FDD1}\u{FDD1}"]
+        );
+        test_vec_feats!(
+            n_slice(3).stride(3).tail(PadTail).featurize("abcdef"),
+            String,
+            &["abc", "def"]
+        );
+        // input shorter than a single window
+        test_vec_feats!(
+            n_slice(4).stride(2).featurize("ab"),
+            &str,
+            Vec::<&str>::new()
+        );
+        test_vec_feats!(
+            n_slice(4).stride(2).tail(ShortTail).featurize("ab"),
+            &str,
+            &["ab"]
+        );
+        test_vec_feats!(
+            strided(trigram(), 5).tail(PadTail).featurize("ab"),
+            String,
+            &["ab\u{FDD1}"]
+        );
+        test_vec_feats!(
+            strided(trislice(), 5).tail(ShortTail).featurize(""),
+            &str,
+            Vec::<&str>::new()
+        );
+        // huge steps and windows end the iteration instead of overflowing
+        test_vec_feats!(
+            n_slice(2).stride(usize::MAX).featurize("abcd"),
+            &str,
+            &["ab"]
+        );
+        test_vec_feats!(
+            n_gram::<2>()
+                .stride(usize::MAX)
+                .tail(PadTail)
+                .featurize("abc"),
+            String,
+            &["ab"]
+        );
+        test_vec_feats!(
+            n_slice(usize::MAX)
+                .stride(usize::MAX)
+                .tail(ShortTail)
+                .featurize("ab"),
+            &str,
+            &["ab"]
+        );
+        test_vec_feats!(
+            n_slice(usize::MAX).stride(2).featurize("ab"),
+            &str,
+            Vec::<&str>::new()
+        );
+
+        let _feats: Vec<HashedAs<u64>> = n_slice(8).stride(4).featurize(n_usize_12);
+        let _feats: Vec<Vec<usize>> = n_slice(8).stride(4).featurize(&n_usize_12.to_vec());
+        let _feats: Vec<HashedAs<u32>> = n_gram::<8>().stride(4).featurize(&n_usize_12.to_vec());
+        let _feats: Vec<[usize; 8]> = n_gram::<8>().stride(4).featurize(n_usize_12);
+
+        // whole windows don't need `T: Default`
+        #[derive(Copy, Clone, PartialEq, Debug)]
+        struct NoDefault(u8);
+        let tokens = [NoDefault(1), NoDefault(2), NoDefault(3)];
+        let feats: Vec<[NoDefault; 2]> = n_gram::<2>().stride(2).featurize(&tokens);
+        assert_eq!(feats, vec![[NoDefault(1), NoDefault(2)]]);
+        let feats: Vec<[Pad<NoDefault>; 2]> =
+            n_gram::<2>().stride(2).tail(PadTail).featurize(&tokens);
+        assert_eq!(feats[1], [Pad::Token(NoDefault(3)), Pad::End]);
+    }
+
+    ////////////////////////////
+    //    padded
+    ////////////////////////////
+    {
+        test_vec_feats!(
+            padded(n_gram::<2>(), 1, 1).featurize("ab"),
+            String,
+            &["\u{FDD0}a", "ab", "b\u{FDD1}"]
+        );
+        test_vec_feats!(
+            padded(n_slice(2), 1, 1).featurize("ab"),
+            String,
+            &["\u{FDD0}a", "ab", "b\u{FDD1}"]
+        );
+        // the same as fastText's `<where>`
+        test_vec_feats!(
+            padded(n_gram::<3>(), 1, 1).featurize("where"),
+            String,
+            &["\u{FDD0}wh", "whe", "her", "ere", "re\u{FDD1}"]
+        );
+        test_vec_feats!(
+            padded(n_slice(3), 2, 1).featurize("ab"),
+            String,
+            &["\u{FDD0}\u{FDD0}a", "\u{FDD0}ab", "ab\u{FDD1}"]
+        );
+        test_vec_feats!(
+            padded(n_slice(3), 2, 1).featurize(&['a', 'b'][..]),
+            String,
+            &["\u{FDD0}\u{FDD0}a", "\u{FDD0}ab", "ab\u{FDD1}"]
+        );
+        // grams of only padding are skipped
+        test_vec_feats!(
+            padded(n_gram::<2>(), 3, 3).featurize("a"),
+            String,
+            &["\u{FDD0}a", "a\u{FDD1}"]
+        );
+        test_vec_feats!(
+            padded(n_slice(2), 3, 3).featurize("a"),
+            String,
+            &["\u{FDD0}a", "a\u{FDD1}"]
+        );
+        test_vec_feats!(
+            padded(n_gram::<2>(), 1, 1).featurize(""),
+            String,
+            Vec::<String>::new()
+        );
+        test_vec_feats!(
+            padded(n_slice(2), 1, 1).featurize(""),
+            String,
+            Vec::<String>::new()
+        );
+        // no padding is the same as the plain featurizer
+        let a: Vec<String> = padded(n_gram::<3>(), 0, 0).featurize(ak);
+        let b: Vec<String> = n_gram::<3>().featurize(ak);
+        assert_eq!(a, b);
+        let a: Vec<String> = padded(n_slice(3), 0, 0).featurize(ak);
+        assert_eq!(a, b);
+        // a gram longer than the padded input
+        test_vec_feats!(
+            padded(n_slice(5), 1, 1).featurize("ab"),
+            String,
+            Vec::<String>::new()
+        );
+        test_vec_feats!(
+            padded(char_gram::<2>(), 1, 1).featurize("日本"),
+            String,
+            &["\u{FDD0}日", "日本", "本\u{FDD1}"]
+        );
+        // sentinels never collide with the text
+        let a: Vec<String> = padded(n_gram::<2>(), 1, 1).featurize("^a$");
+        let b: Vec<String> = padded(n_gram::<2>(), 1, 1).featurize("a");
+        assert!(b.iter().all(|f| !a.contains(f)));
+        assert_eq!(START_SENTINEL.to_string(), "\u{FDD0}");
+        // byte windows that split a character are lossy as `Lossy<String>`
+        test_vec_feats!(
+            padded(n_slice(2), 1, 0).featurize("é"),
+            Lossy<String>,
+            &[Lossy("\u{FDD0}\u{FFFD}".to_owned()), Lossy("é".to_owned())]
+        );
+        test_vec_feats!(
+            padded(n_gram::<2>(), 0, 1).featurize("é"),
+            Lossy<String>,
+            &[Lossy("é".to_owned()), Lossy("\u{FFFD}\u{FDD1}".to_owned())]
+        );
+        assert_eq!(
+            padded(n_slice(2), 1, 0).try_featurize::<String, Vec<_>>("é"),
+            Err(FeatureError::InvalidUtf8 { offset: 1 })
+        );
+        let mut checked = Vec::new();
+        padded(n_gram::<2>(), 1, 1).push_tokens("é", &mut |t: [Pad<u8>; 2]| {
+            checked.push(<String as TryFeatureFrom<_>>::try_from(t))
+        });
+        assert_eq!(
+            checked,
+            vec![
+                Err(FeatureError::InvalidUtf8 { offset: 1 }),
+                Ok("é".to_owned()),
+                Err(FeatureError::InvalidUtf8 { offset: 0 })
+            ]
+        );
+        test_vec_feats!(
+            padded(n_slice(2), 1, 1).featurize(&[1, 2][..]),
+            Vec<Pad<i32>>,
+            &[
+                vec![Pad::Start, Pad::Token(1)],
+                vec![Pad::Token(1), Pad::Token(2)],
+                vec![Pad::Token(2), Pad::End]
+            ]
+        );
+        let _feats: Vec<HashedAs<u64>> = padded(n_gram::<2>(), 1, 1).featurize(n_usize_12);
+        let _feats: Vec<HashedAs<u32>> = padded(n_slice(2), 1, 1).featurize(&n_usize_12.to_vec());
+        let _feats: Vec<[Pad<usize>; 2]> =
+            padded(n_gram::<2>(), 1, 1).featurize(&n_usize_12.to_vec());
+        let _feats: Vec<HashedAs<u64>> = padded(char_gram::<3>(), 2, 2).featurize(&ak.to_string());
+    }
+
+    ////////////////////////////
+    //    spans
+    ////////////////////////////
+    {
+        let text = "one fish two";
+        let feats: Vec<(Range<usize>, &str)> = with_spans(n_slice(3)).featurize(text);
+        for (span, feat) in feats.iter() {
+            assert_eq!(&text[span.clone()], *feat);
+        }
+        let feats: Vec<(Range<usize>, String)> = with_spans(n_gram::<3>()).featurize(text);
+        for (span, feat) in feats.iter() {
+            assert_eq!(&text[span.clone()], feat);
+        }
+        test_vec_feats!(
+            with_spans(gap_gram(n_slice(1), 2, n_slice(2))).featurize("abcdef"),
+            ((Range<usize>, Range<usize>), (&str, &str)),
+            &[((0..1, 3..5), ("a", "de")), ((1..2, 4..6), ("b", "ef"))]
+        );
+        test_vec_feats!(
+            with_spans(bookends((n_slice(2), 3), (n_gram::<2>(), 3))).featurize("abcdef"),
+            (Range<usize>, FrontBack<&str, String>),
+            &[
+                (0..2, FrontBack::Front("ab")),
+                (1..3, FrontBack::Front("bc")),
+                (3..5, FrontBack::Back("de".to_string())),
+                (4..6, FrontBack::Back("ef".to_string()))
+            ]
+        );
+        test_vec_feats!(
+            with_spans(bookends((n_slice(2), 3), (n_slice(2), 3)))
+                .featurize(&[1, 2, 3, 4, 5, 6, 7]),
+            (Range<usize>, FrontBack<&[i32], &[i32]>),
+            &[
+                (0..2, FrontBack::Front(&[1, 2][..])),
+                (1..3, FrontBack::Front(&[2, 3][..])),
+                (4..6, FrontBack::Back(&[5, 6][..])),
+                (5..7, FrontBack::Back(&[6, 7][..]))
+            ]
+        );
+        // offsets are relative to the whole sentence
+        let text = "  the cat  sat";
+        let feats: Vec<(Range<usize>, &str)> =
+            with_spans(for_each(n_slice(2))).featurize(word_indices(text));
+        assert_eq!(feats.len(), 6);
+        for (span, feat) in feats.iter() {
+            assert_eq!(&text[span.clone()], *feat);
+        }
+        assert_eq!(feats[0], (2..4, "th"));
+        assert_eq!(feats[5], (12..14, "at"));
+        let feats: Vec<((Range<usize>, Range<usize>), (&str, &str))> =
+            with_spans(for_each(gap_gram(n_slice(1), 1, n_slice(1)))).featurize(word_indices(text));
+        assert_eq!(feats[2], ((11..12, 13..14), ("s", "t")));
+        // words from separate buffers, out of address order
+        let (b, a) = ("b".to_owned(), "a".to_owned());
+        let feats: Vec<(Range<usize>, &str)> =
+            with_spans(for_each(n_slice(1))).featurize(vec![(0, b.as_str()), (5, a.as_str())]);
+        assert_eq!(feats, vec![(0..1, "b"), (5..6, "a")]);
+        let feats: Vec<(Range<usize>, [i32; 2])> =
+            with_spans(for_each(n_gram::<2>())).featurize(vec![(10, &[1, 2, 3][..])]);
+        assert_eq!(feats, vec![(10..12, [1, 2]), (11..13, [2, 3])]);
+        test_vec_feats!(
+            word_indices(" a\tbc \n").collect::<Vec<_>>(),
+            (usize, &str),
+            &[(1, "a"), (3, "bc")]
+        );
+        test_vec_feats!(word_indices("   ").collect::<Vec<_>>(), (usize, &str), &[]);
+
+        let _feats: Vec<HashedAs<u64>> = with_spans(n_slice(2)).featurize(n_usize_12);
+        let _feats: Vec<(Range<usize>, HashedAs<u64>)> =
+            with_spans(n_gram::<2>()).featurize(&n_usize_12.to_vec());
+        let _feats: Vec<(Range<usize>, [usize; 2])> =
+            with_spans(n_gram::<2>()).featurize(n_usize_12);
+        let _feats: Vec<(Range<usize>, String)> =
+            with_spans(n_gram::<2>()).featurize(&text.to_string());
+    }
+
+    ////////////////////////////
+    //    positions
+    ////////////////////////////
+    {
+        let index: Positions<HashMap<&str, Vec<u32>>> = n_slice(1).featurize("abcabca");
+        assert_eq!(index["a"], vec![0, 3, 6]);
+        assert_eq!(index["c"], vec![2, 5]);
+        let index: Positions<BTreeMap<String, BTreeSet<u32>>> = n_gram::<2>().featurize("abab");
+        let expected: BTreeMap<String, BTreeSet<u32>> = vec![
+            ("ab".to_string(), vec![0, 2].into_iter().collect()),
+            ("ba".to_string(), vec![1].into_iter().collect()),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(index.0, expected);
+        // positions count every token group, across all words
+        let index: Positions<HashMap<HashedAs<u64>, VecDeque<u32>>> =
+            for_each(n_slice(2)).featurize("ab cab".split_ascii_whitespace());
+        assert_eq!(index.len(), 2);
+        assert_eq!(
+            index[&FeatureFrom::from(&b"ab"[..])],
+            VecDeque::from(vec![0, 2])
+        );
+        let index: Positions<HashMap<&str, Vec<u32>>> = n_slice(2).featurize("");
+        assert!(index.is_empty());
+    }
+
+    ////////////////////////////
+    //    rolling_hash
+    ////////////////////////////
+    {
+        // every rolled hash is the same as hashing the window from scratch
+        fn from_scratch<T: std::hash::Hash>(window: &[T]) -> u64 {
+            window.iter().fold(0, |h, t| {
+                h.wrapping_mul(ROLLING_BASE).wrapping_add(fxhash::hash64(t))
+            })
+        }
+        for n in [1, 2, 3, 8, 20] {
+            let rolled: Vec<u64> = rolling_hash(n).featurize(ak);
+            let windows: Vec<&[u8]> = n_slice(n).featurize(ak);
+            assert_eq!(rolled.len(), windows.len());
+            for (r, w) in rolled.iter().zip(windows.iter()) {
+                assert_eq!(*r, from_scratch(w));
+            }
+            let rolled: Vec<u64> = rolling_hash(n).featurize(n_usize_12);
+            let windows: Vec<&[usize]> = n_slice(n).featurize(n_usize_12);
+            for (r, w) in rolled.iter().zip(windows.iter()) {
+                assert_eq!(*r, from_scratch(w));
+            }
+        }
+        // pinned literals, so the raw values stay reproducible across versions
+        let rolled: Vec<u64> = rolling_hash(1).featurize("a");
+        assert_eq!(rolled, vec![fxhash::hash64(&b'a')]);
+        let a: Vec<HashedAs<u64>> = rolling_hash(3).featurize("abcabc");
+        assert_eq!(a[0], a[3]);
+        assert_ne!(a[0], a[1]);
+        let rolled: Vec<Rolled> = rolling_hash(3).featurize("abcabc");
+        let b: Vec<HashedAs<u64>> = rolled.into_iter().map(FeatureFrom::from).collect();
+        assert_eq!(a, b);
+        let c: Vec<HashedAs<u64>> = n_slice(3).featurize("abcabc");
+        assert_ne!(a, c);
+        let pinned: Vec<u64> = rolling_hash(3).featurize("abcd");
+        assert_eq!(pinned, vec![451789407742485338, 15055163352961530363]);
+        let pinned: Vec<u64> = rolling_hash(2).featurize(&[1u64, 2, 3][..]);
+        assert_eq!(pinned, vec![4323810397130592345, 2775839787697182237]);
+        test_vec_feats!(rolling_hash(0).featurize(ak), u64, Vec::<u64>::new());
+        test_vec_feats!(rolling_hash(4).featurize("abc"), u64, Vec::<u64>::new());
+        let _feats: Vec<HashedAs<u32>> = rolling_hash(8).featurize(&ak.to_string());
+        let _feats: Vec<HashedAs<u8>> = rolling_hash(8).featurize(&n_usize_12.to_vec());
+        let _feats: HashSet<HashedAs<u64>> = rolling_hash(8).featurize(&chars_of(ak));
+    }
+
+    ////////////////////////////
+    //    winnow
+    ////////////////////////////
+    {
+        // the same as taking the rightmost minimum of every window by hand
+        fn naive(hashes: &[u64], w: usize) -> Vec<(u64, usize)> {
+            let mut out: Vec<(u64, usize)> = Vec::new();
+            for (start, window) in hashes.windows(w).enumerate() {
+                let (i, h) = window
+                    .iter()
+                    .enumerate()
+                    .rev()
+                    .min_by_key(|(_, h)| **h)
+                    .unwrap();
+                if out.last().map_or(true, |(_, p)| *p != start + i) {
+                    out.push((*h, start + i));
+                }
+            }
+            out
+        }
+        for w in [1, 2, 4, 7] {
+            let hashes: Vec<HashedAs<u64>> = rolling_hash(3).featurize(ak);
+            let hashes: Vec<u64> = hashes.into_iter().map(|h| h.0).collect();
+            let prints: Vec<(HashedAs<u64>, usize)> = winnow(rolling_hash(3), w).featurize(ak);
+            let prints: Vec<(u64, usize)> = prints.into_iter().map(|(h, p)| (h.0, p)).collect();
+            assert_eq!(prints, naive(&hashes, w));
+        }
+        // ties go to the rightmost
+        let prints: Vec<(HashedAs<u64>, usize)> = winnow(n_slice(1), 3).featurize("aaaaa");
+        let positions: Vec<usize> = prints.iter().map(|(_, p)| *p).collect();
+        assert_eq!(positions, vec![2, 3, 4]);
+        // inputs shorter than one window get one fingerprint
+        let prints: Vec<u64> = winnow(n_slice(2), 10).featurize("abcd");
+        let hashes: Vec<HashedAs<u64>> = n_slice(2).featurize("abcd");
+        assert_eq!(prints, vec![hashes.iter().min().unwrap().0]);
+        test_vec_feats!(
+            winnow(n_slice(2), 10).featurize("a"),
+            u64,
+            Vec::<u64>::new()
+        );
+        // any shared substring of length >= w + k - 1 has a shared fingerprint
+        let (k, w) = (4, 5);
+        let shared = "the quick brown";
+        for i in 0..=(shared.len() - (w + k - 1)) {
+            let sub = &shared[i..i + w + k - 1];
+            let a = format!("xyzzy {} plugh", sub);
+            let b = format!("{} lorem ipsum", sub);
+            let fa: HashSet<HashedAs<u64>> = winnow(n_slice(k), w).featurize(a.as_str());
+            let fb: HashSet<HashedAs<u64>> = winnow(n_slice(k), w).featurize(b.as_str());
+            assert!(fa.intersection(&fb).count() > 0);
+        }
+        let _feats: Vec<HashedAs<u32>> = winnow(n_gram::<3>(), 4).featurize(n_usize_12);
+        let _feats: BTreeSet<HashedAs<u16>> =
+            winnow(for_each(n_slice(2)), 4).featurize(sentence.split_ascii_whitespace());
+    }
+
+    ////////////////////////////
+    //    sax
+    ////////////////////////////
+    {
+        let word = sax(4, 4).featurize::<char, String>(&[1.0, 1.0, 2.0, 2.0, 3.0, 3.0, 4.0, 4.0]);
+        assert_eq!(word, "abcd");
+        let word = sax(4, 4).featurize::<char, String>(&[1, 1, 2, 2, 3, 3, 4, 4]);
+        assert_eq!(word, "abcd");
+        let word = sax(4, 4).featurize::<char, String>(&[4.0f32, 3.0, 2.0, 1.0]);
+        assert_eq!(word, "dcba");
+        // flat series sit on the middle of the alphabet
+        let word = sax(3, 4).featurize::<char, String>(&[5.0; 10]);
+        assert_eq!(word, "ccc");
+        let word = sax(3, 3).featurize::<char, String>(&[5.0, 5.001, 5.0]);
+        assert_eq!(word, "bbb");
+        // more segments than points
+        let word = sax(4, 4).featurize::<char, String>(&[0.0, 10.0]);
+        assert_eq!(word, "aadd");
+        // fractional segments are the same as repeating every point `segments` times
+        let series = [3.0, -1.5, 2.25, 8.0, 0.5, 0.0, -4.0];
+        for segments in 1..6 {
+            for alphabet in [2, 3, 5, 10] {
+                let repeated: Vec<f64> = series
+                    .iter()
+                    .flat_map(|x| std::iter::repeat(*x).take(segments))
+                    .collect();
+                let a: Vec<u8> = sax(segments, alphabet).featurize(&series);
+                let b: Vec<u8> = sax(segments, alphabet).featurize(&repeated);
+                assert_eq!(a, b);
+                assert_eq!(a.len(), segments);
+            }
+        }
+        let word: Vec<u8> = sax(20, 100).featurize(&series);
+        assert!(word.iter().all(|c| (b'a'..=b'z').contains(c)));
+        let word = sax(3, 1).featurize::<char, String>(&series);
+        assert_eq!(word, "aaa");
+        let word: Vec<u8> = sax(3, 4).featurize(&Vec::<f64>::new());
+        assert!(word.is_empty());
+        let _feats: HashSet<HashedAs<u64>> = sax(4, 4).featurize(&series);
+        let _feats: Bag<HashMap<u8, usize>> = sax(4, 4).featurize(&series.to_vec());
+        let symbols: Vec<SaxSymbol> = sax(4, 4).featurize(&series);
+        assert_eq!(symbols.len(), 4);
+        assert_eq!(
+            sax(4, 4).iterate_features(&series[..]).size_hint(),
+            (4, Some(4))
+        );
+        let word = sax(3, 4).featurize::<SaxSymbol, String>(&series);
+        assert_eq!(word.len(), 3);
+        // every integer type, including the ones that don't fit in an f64 exactly
+        let word = sax(4, 4).featurize::<char, String>(&[1i64, 1, 2, 2, 3, 3, 4, 4]);
+        assert_eq!(word, "abcd");
+        let word = sax(2, 2).featurize::<char, String>(&[u64::MAX, u64::MAX, 0, 0]);
+        assert_eq!(word, "ba");
+        let word = sax(2, 2).featurize::<char, String>(&vec![7usize, 7, 9, 9]);
+        assert_eq!(word, "ab");
+        let word = sax(2, 2).featurize::<char, String>(&[i64::MIN, i64::MAX]);
+        assert_eq!(word, "ab");
+        // one word per window
+        let ramps = [1, 2, 3, 4, 3, 2, 1];
+        let words: Vec<String> = sax(2, 2).over(n_gram::<4>()).featurize(&ramps);
+        assert_eq!(words, vec!["ab", "ab", "ba", "ba"]);
+        let words: Vec<SaxWord> = sax(2, 2).over(n_slice(4)).featurize(&ramps.to_vec());
+        assert_eq!(
+            words,
+            vec![
+                SaxWord("ab".into()),
+                SaxWord("ab".into()),
+                SaxWord("ba".into()),
+                SaxWord("ba".into())
+            ]
+        );
+        let words: Vec<String> = sax(2, 2)
+            .over(n_slice(4))
+            .iterate_features(&ramps[..])
+            .map(|w| w.0)
+            .collect();
+        assert_eq!(words.len(), 4);
+        let words: Vec<Vec<u8>> = sax(2, 2).over(n_gram::<4>()).featurize(&ramps[..3]);
+        assert!(words.is_empty());
+        let _feats: HashSet<HashedAs<u64>> = sax(2, 2).over(n_gram::<4>()).featurize(&ramps);
+        let _feats: Bag<HashMap<String, u8>> = sax(3, 5).over(n_slice(5)).featurize(&series);
+    }
+
+    ////////////////////////////
+    //    bins
+    ////////////////////////////
+    {
+        let bins = uniform_bins(-1.0, 1.0, 4);
+        assert_eq!(bins.edges(), &[-0.5, 0.0, 0.5]);
+        assert_eq!(bins.num_bins(), 4);
+        test_vec_feats!(
+            bins.featurize(&[-9.0, -0.5, -0.1, 0.0, 0.4999, 0.5, f64::INFINITY]),
+            u32,
+            &[0, 1, 1, 2, 2, 3, 3]
+        );
+        test_vec_feats!(bins.featurize(&[f64::NAN, 0.1]), Bin, &[Bin(2)]);
+        test_vec_feats!(bins.featurize(0.75f32), Bin, &[Bin(3)]);
+        test_vec_feats!(bins.featurize(f64::NAN), Bin, Vec::<Bin>::new());
+        test_vec_feats!(
+            bins.featurize(&vec![-1i32, 0, 1]),
+            Bin,
+            &[Bin(0), Bin(2), Bin(3)]
+        );
+        test_vec_feats!(
+            uniform_bins(0.0, 1.0, 1).featurize(&[-5.0, 5.0]),
+            Bin,
+            &[Bin(0), Bin(0)]
+        );
+
+        let bins = log_bins(1.0, 10_000.0, 4);
+        for (edge, expected) in bins.edges().iter().zip([10.0, 100.0, 1000.0]) {
+            assert!((edge - expected).abs() < 1e-9);
+        }
+        test_vec_feats!(
+            bins.featurize(&[0.0, 2.0, 20.0, 2e3, 2e6]),
+            u32,
+            &[0, 0, 1, 3, 3]
+        );
+
+        // each bin gets about the same number of the sample
+        let sample: Vec<f64> = (0..1000).map(|x| ((x * 7919) % 1000) as f64).collect();
+        let bins = quantile_bins(&sample, 10);
+        assert_eq!(bins.num_bins(), 10);
+        let counts: Bag<HashMap<Bin, usize>> = bins.featurize(&sample);
+        assert!(counts.values().all(|c| *c == 100));
+        let bins = quantile_bins(&[1.0, 1.0, 1.0, 1.0, 2.0, f64::NAN], 4);
+        assert_eq!(bins.edges(), &[1.0]);
+        assert_eq!(quantile_bins(&Vec::<f32>::new(), 4).num_bins(), 1);
+
+        let bins = bin_edges(vec![3.0, f64::NAN, 1.0, 3.0, 2.0]);
+        assert_eq!(bins.edges(), &[1.0, 2.0, 3.0]);
+
+        let _feats: HashSet<HashedAs<u64>> = bins.featurize(&[1.0f32, 2.5]);
+        let _feats: Vec<Merged<HashedAs<u64>>> =
+            featurizers!(uniform_bins(0.0, 1.0, 10), log_bins(1.0, 100.0, 10)).featurize(0.5);
+        // a single number of any type
+        test_vec_feats!(bin_edges(vec![18.0, 65.0]).featurize(40u8), Bin, &[Bin(1)]);
+        test_vec_feats!(
+            bin_edges(vec![18.0, 65.0]).featurize(u64::MAX),
+            Bin,
+            &[Bin(2)]
+        );
+        test_vec_feats!(bin_edges(vec![18.0, 65.0]).featurize(-3i64), Bin, &[Bin(0)]);
+        test_vec_feats!(
+            bin_edges(vec![1.0]).featurize(&[0usize, 1, 2]),
+            Bin,
+            &[Bin(0), Bin(1), Bin(1)]
+        );
+    }
+
+    ////////////////////////////
+    //    field
+    ////////////////////////////
+    {
+        // the same bin of two columns stays two features
+        let ages = uniform_bins(0.0, 100.0, 4);
+        let ftzr = featurizers!(
+            field("age", |(age, _): (u32, u32)| age, ages.clone()),
+            field("weight", |(_, weight): (u32, u32)| weight, ages.clone())
+        );
+        let feats: Vec<Merged<HashedAs<u64>>> = ftzr.featurize((30, 30));
+        assert_eq!(feats.len(), 2);
+        assert_ne!(feats[0], feats[1]);
+        let feats: Vec<(&str, Bin)> =
+            field("age", |(age, _): (u32, u32)| age, ages).featurize((30, 90));
+        assert_eq!(feats, vec![("age", Bin(1))]);
+
+        // text and numbers from one record
+        struct Row {
+            name: String,
+            scores: Vec<f64>,
+        }
+        fn name(row: &Row) -> &str {
+            &row.name
+        }
+        fn scores(row: &Row) -> &[f64] {
+            &row.scores
+        }
+        let row = Row {
+            name: "abc".to_string(),
+            scores: vec![0.1, 0.9],
+        };
+        let ftzr = field("name", name, bigram);
+        assert_eq!(ftzr.name(), "name");
+        let feats: Vec<(&str, String)> = ftzr.featurize(&row);
+        assert_eq!(
+            feats,
+            vec![("name", "ab".to_string()), ("name", "bc".to_string())]
+        );
+        let feats: Vec<(&str, String)> = ftzr
+            .iterate_features(&row)
+            .map(|(n, t)| (n, FeatureFrom::from(t)))
+            .collect();
+        assert_eq!(feats.len(), 2);
+        let ftzr = field("scores", scores, uniform_bins(0.0, 1.0, 2));
+        let feats: Vec<(&str, u32)> = ftzr.featurize(&row);
+        assert_eq!(feats, vec![("scores", 0), ("scores", 1)]);
+        let _feats: HashSet<HashedAs<u64>> =
+            featurizers!(field("name", name, n_slice(2)), ftzr.clone()).featurize(&row);
+        let empty = Row {
+            name: String::new(),
+            scores: Vec::new(),
+        };
+        let feats: Vec<(&str, u32)> = ftzr.featurize(&empty);
+        assert!(feats.is_empty());
+    }
+
+    ////////////////////////////
+    //    deltas
+    ////////////////////////////
+    {
+        let tune: &[u8] = &[60, 62, 64, 60, 67];
+        test_vec_feats!(
+            deltas(n_gram::<2>()).featurize(tune),
+            [i128; 2],
+            &[[2, 2], [2, -4], [-4, 7]]
+        );
+        test_vec_feats!(
+            deltas(n_gram::<4>()).featurize(tune),
+            [i128; 4],
+            &[[2, 2, -4, 7]]
+        );
+        test_vec_feats!(
+            deltas(n_gram::<5>()).featurize(tune),
+            [i128; 5],
+            Vec::<[i128; 5]>::new()
+        );
+        test_vec_feats!(
+            deltas(n_gram::<1>()).featurize(&vec![3u32, 1, 1]),
+            [i128; 1],
+            &[[-2], [0]]
+        );
+        test_vec_feats!(
+            deltas(n_gram::<2>()).signs().featurize(&[3u64, 1, 1, 9]),
+            [i8; 2],
+            &[[-1, 0], [0, 1]]
+        );
+        test_vec_feats!(
+            deltas(n_gram::<2>())
+                .clamped(1.5)
+                .featurize(&[0.0, 0.5, -3.0, 9.0]),
+            [f64; 2],
+            &[[0.5, -1.5], [-1.5, 1.5]]
+        );
+        test_vec_feats!(
+            deltas(n_gram::<1>()).featurize(&[1.0f32, 0.25]),
+            [f32; 1],
+            &[[-0.75]]
+        );
+        test_vec_feats!(
+            ratios(n_gram::<1>()).featurize(&[2i32, 4, 1]),
+            [f64; 1],
+            &[[2.0], [0.25]]
+        );
+        test_vec_feats!(
+            deltas(n_gram::<2>()).featurize(&Vec::<i32>::new()),
+            [i128; 2],
+            Vec::<[i128; 2]>::new()
+        );
+        let _feats: HashSet<HashedAs<u64>> = deltas(n_gram::<3>()).signs().featurize(tune);
+        let _feats: Bag<HashMap<[i128; 2], usize>> = deltas(n_gram::<2>()).featurize(tune);
+        // exact at the extremes of every integer type
+        test_vec_feats!(
+            deltas(n_gram::<2>()).featurize(&[0u64, u64::MAX, 0]),
+            [i128; 2],
+            &[[u64::MAX as i128, -(u64::MAX as i128)]]
+        );
+        test_vec_feats!(
+            deltas(n_gram::<1>()).featurize(&[i64::MIN, i64::MAX]),
+            [i128; 1],
+            &[[u64::MAX as i128]]
+        );
+        test_vec_feats!(
+            deltas(n_gram::<1>()).signs().featurize(&[usize::MAX, 0]),
+            [i8; 1],
+            &[[-1]]
+        );
+        // any featurizer of slices, and the lazy path agrees with featurize
+        test_vec_feats!(
+            deltas(n_slice(2)).featurize(tune),
+            Vec<i128>,
+            &[vec![2, 2], vec![2, -4], vec![-4, 7]]
+        );
+        test_vec_feats!(
+            deltas(n_slice(5)).featurize(tune),
+            Vec<i128>,
+            Vec::<Vec<i128>>::new()
+        );
+        test_vec_feats!(
+            ratios(n_slice(1)).featurize(&vec![1.0, 2.0]),
+            Vec<f64>,
+            &[vec![2.0]]
+        );
+        test_vec_feats!(
+            deltas(strided(n_slice(2), 2))
+                .signs()
+                .featurize(&[1, 2, 3, 2, 1]),
+            Vec<i8>,
+            &[vec![1, 1], vec![-1, -1]]
+        );
+        let _feats: HashSet<HashedAs<u64>> = deltas(rolling_hash(2)).featurize(tune);
+        let _feats: HashSet<Vec<i8>> = deltas(n_slice(2)).signs().featurize(tune);
+        let lazy: Vec<[i128; 3]> = deltas(n_gram::<3>()).iterate_features(tune).collect();
+        let eager: Vec<[i128; 3]> = deltas(n_gram::<3>()).featurize(tune);
+        assert_eq!(lazy, eager);
+    }
+
+    ////////////////////////////
+    //    bpe
+    ////////////////////////////
+    {
+        let encoder = bpe("abc abc abd".split_whitespace(), 100);
+        // "ab" x3, then "abc" x2, then "abd" x1
+        assert_eq!(encoder.num_merges(), 3);
+        assert_eq!(encoder.to_merges(), "97 98\n256 99\n256 100\n");
+        assert_eq!(encoder.vocab_size(), 259);
+        assert_eq!(encoder.piece(258), Some(&b"abd"[..]));
+        assert_eq!(encoder.piece(259), None);
+        test_vec_feats!(encoder.featurize("aabcd"), String, &["a", "abc", "d"]);
+        test_vec_feats!(encoder.featurize("aabcd"), &str, &["a", "abc", "d"]);
+        assert_eq!(
+            encoder.try_featurize::<&str, Vec<_>>("aabcd"),
+            Ok(vec!["a", "abc", "d"])
+        );
+        assert_eq!(
+            encoder.try_featurize::<u32, Vec<_>>("babd"),
+            Ok(vec![98, 258])
+        );
+        test_vec_feats!(encoder.featurize("babd"), u32, &[98, 258]);
+        test_vec_feats!(encoder.featurize(""), String, Vec::<String>::new());
+        test_vec_feats!(
+            encoder.featurize(&b"xab"[..]),
+            BpePiece,
+            &[
+                BpePiece {
+                    piece: &b"x"[..],
+                    id: 120
+                },
+                BpePiece {
+                    piece: &b"ab"[..],
+                    id: 256
+                }
+            ]
+        );
+
+        // a merge that splits "é" is U+FFFD as a `Lossy<String>`, and an error when checked
+        let encoder = BytePairEncoder::train_bytes(vec![&[0xa9u8, b'b'][..]], 1);
+        test_vec_feats!(
+            encoder.featurize("éb"),
+            Lossy<String>,
+            &[Lossy("\u{FFFD}".to_owned()), Lossy("\u{FFFD}b".to_owned())]
+        );
+        assert_eq!(
+            encoder.try_featurize::<&str, Vec<_>>("éb"),
+            Err(FeatureError::InvalidUtf8 { offset: 0 })
+        );
+        assert_eq!(
+            encoder.try_featurize::<String, Vec<_>>("éb"),
+            Err(FeatureError::InvalidUtf8 { offset: 0 })
+        );
+        assert_eq!(
+            encoder.try_featurize::<&[u8], Vec<_>>("éb"),
+            Ok(vec![&[0xc3][..], &[0xa9, b'b'][..]])
+        );
+
+        // ties go to the smallest pair
+        let encoder = bpe(vec!["ba", "dc"], 1);
+        assert_eq!(encoder.to_merges(), "98 97\n");
+        let encoder = BytePairEncoder::train_bytes(vec![&[1u8, 2][..], &[1, 2, 1, 2]], 10);
+        assert_eq!(encoder.to_merges(), "1 2\n256 256\n");
+        assert_eq!(bpe(Vec::new(), 5).num_merges(), 0);
+
+        let table = encoder.to_merges();
+        assert_eq!(BytePairEncoder::from_merges(&table), Ok(encoder.clone()));
+        assert_eq!(
+            BytePairEncoder::from_merges("\n1 2\n\n"),
+            Ok(BytePairEncoder::train_bytes(vec![&[1u8, 2][..]], 1))
+        );
+        assert_eq!(
+            BytePairEncoder::from_merges("1 2\n1"),
+            Err(MergeTableError::Malformed { line: 2 })
+        );
+        assert_eq!(
+            BytePairEncoder::from_merges("1 x"),
+            Err(MergeTableError::Malformed { line: 1 })
+        );
+        assert_eq!(
+            BytePairEncoder::from_merges("1 2\n256 257"),
+            Err(MergeTableError::UnknownId { line: 2, id: 257 })
+        );
+        assert_eq!(
+            BytePairEncoder::from_merges("1 2\n1 2"),
+            Err(MergeTableError::Duplicate { line: 2 })
+        );
+        assert_eq!(BytePairEncoder::default().num_merges(), 0);
+
+        let _feats: Bag<HashMap<u32, usize>> = encoder.featurize(&vec![1u8, 2, 1, 2, 1]);
+
+        // the same merges and pieces as the simple (and quadratic) way of doing it
+        fn naive_train(words: &[&[u8]], num_merges: usize) -> String {
+            let mut words: Vec<Vec<u32>> = words
+                .iter()
+                .map(|w| w.iter().map(|b| *b as u32).collect())
+                .collect();
+            let mut table = String::new();
+            for id in 256..256 + num_merges as u32 {
+                let mut counts: HashMap<[u32; 2], usize> = HashMap::new();
+                for word in words.iter() {
+                    for pair in word.windows(2) {
+                        *counts.entry([pair[0], pair[1]]).or_insert(0) += 1;
+                    }
+                }
+                let best = counts
+                    .into_iter()
+                    .max_by(|(p1, c1), (p2, c2)| c1.cmp(c2).then(p2.cmp(p1)));
+                let [left, right] = match best {
+                    Some((pair, _)) => pair,
+                    None => break,
+                };
+                table.push_str(&format!("{} {}\n", left, right));
+                for word in words.iter_mut() {
+                    let mut i = 0;
+                    while i + 1 < word.len() {
+                        if word[i] == left && word[i + 1] == right {
+                            word[i] = id;
+                            word.remove(i + 1);
+                        }
+                        i += 1;
+                    }
+                }
+            }
+            table
+        }
+        fn naive_encode(merges: &[(u32, u32)], text: &[u8]) -> Vec<u32> {
+            let mut ids: Vec<u32> = text.iter().map(|b| *b as u32).collect();
+            loop {
+                let earliest =
+                    (0..merges.len()).find(|r| ids.windows(2).any(|p| (p[0], p[1]) == merges[*r]));
+                let rank = match earliest {
+                    Some(rank) => rank,
+                    None => return ids,
+                };
+                let mut i = 0;
+                while i + 1 < ids.len() {
+                    if (ids[i], ids[i + 1]) == merges[rank] {
+                        ids[i] = 256 + rank as u32;
+                        ids.remove(i + 1);
+                    }
+                    i += 1;
+                }
+            }
+        }
+        let mut seed: u64 = 7;
+        let mut next_word = |len: usize| -> Vec<u8> {
+            (0..len)
+                .map(|_| {
+                    seed = seed
+                        .wrapping_mul(6364136223846793005)
+                        .wrapping_add(1442695040888963407);
+                    b"aaabbc"[(seed >> 33) as usize % 6]
+                })
+                .collect()
+        };
+        let corpus: Vec<Vec<u8>> = (0..300).map(|i| next_word(1 + i % 13)).collect();
+        let corpus: Vec<&[u8]> = corpus.iter().map(Vec::as_slice).collect();
+        let encoder = BytePairEncoder::train_bytes(corpus.iter().copied(), 40);
+        assert_eq!(encoder.to_merges(), naive_train(&corpus, 40));
+        let merges: Vec<(u32, u32)> = encoder
+            .to_merges()
+            .lines()
+            .map(|l| {
+                let mut ids = l.split(' ').map(|id| id.parse().unwrap());
+                (ids.next().unwrap(), ids.next().unwrap())
+            })
+            .collect();
+        for len in [0, 1, 2, 3, 7, 30, 200] {
+            let text = next_word(len);
+            let ids: Vec<u32> = encoder.featurize(&text);
+            assert_eq!(ids, naive_encode(&merges, &text));
+            let pieces: Vec<&[u8]> = encoder.featurize(&text);
+            assert_eq!(pieces.concat(), text);
+        }
+        let _feats: HashSet<HashedAs<u64>> =
+            for_each(encoder).featurize(sentence.split_whitespace());
+    }
+
+    ////////////////////////////
+    //    word piece
+    ////////////////////////////
+    {
+        let vocab = vec![
+            "[UNK]", "a", "ab", "abc", "##b", "##c", "##bc", "", "é", "##é", "a", "?",
+        ];
+        let vocab = WordPieceVocab::new(vocab);
+        assert_eq!(vocab.len(), 12);
+        assert_eq!(vocab.id("a"), Some(1));
+        assert_eq!(vocab.id(""), None);
+        assert!(WordPieceVocab::default().is_empty());
+        let tokenizer = word_piece(&vocab).unwrap();
+        test_vec_feats!(tokenizer.featurize("abc"), &str, &["abc"]);
+        test_vec_feats!(tokenizer.featurize("abbc"), &str, &["ab", "##bc"]);
+        test_vec_feats!(tokenizer.featurize("abcbcb"), u32, &[3, 6, 4]);
+        test_vec_feats!(tokenizer.featurize("éé"), &str, &["é", "##é"]);
+        test_vec_feats!(tokenizer.featurize("aéd"), &str, &["[UNK]"]);
+        test_vec_feats!(tokenizer.featurize("b"), u32, &[0]);
+        test_vec_feats!(tokenizer.featurize(""), u32, Vec::<u32>::new());
+        test_vec_feats!(
+            tokenizer.featurize(&"ac".to_owned()),
+            Subword,
+            &[
+                Subword { piece: "a", id: 1 },
+                Subword {
+                    piece: "##c",
+                    id: 5
+                }
+            ]
+        );
+        let tokenizer = tokenizer.max_chars(3).unk("?").unwrap();
+        test_vec_feats!(tokenizer.featurize("abc"), &str, &["abc"]);
+        test_vec_feats!(
+            tokenizer.featurize("abcb"),
+            Subword,
+            &[Subword { piece: "?", id: 11 }]
+        );
+        test_vec_feats!(
+            for_each(tokenizer).featurize("ab abcb é".split_whitespace()),
+            String,
+            &["ab", "?", "é"]
+        );
+
+        // a missing unknown token is an error, instead of an id outside of the vocabulary
+        assert_eq!(
+            tokenizer.unk("<?>"),
+            Err(MissingUnkError {
+                token: "<?>".to_string()
+            })
+        );
+        let no_unk = WordPieceVocab::new(vec!["a", "##b"]);
+        assert_eq!(
+            word_piece(&no_unk).unwrap_err().to_string(),
+            "the unknown token \"[UNK]\" isn't in the vocabulary"
+        );
+
+        let fixtures = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures");
+        let vocab = WordPieceVocab::from_file(fixtures.join("word_piece_vocab.txt")).unwrap();
+        let tokenizer = word_piece(&vocab).unwrap();
+        test_vec_feats!(tokenizer.featurize("playing"), u32, &[2, 3]);
+        test_vec_feats!(tokenizer.featurize("plays"), u32, &[1]);
+        assert!(WordPieceVocab::from_file(fixtures.join("missing.txt")).is_err());
+
+        let _feats: HashSet<HashedAs<u64>> = tokenizer.featurize("playing");
+        let _feats: Bag<HashMap<&str, usize>> =
+            for_each(tokenizer).featurize(sentence.split_whitespace());
+    }
+
+    ////////////////////////////
+    //    stemmed
+    ////////////////////////////
+    {
+        // the whole Snowball English test vocabulary (voc.txt and output.txt), as "word stem" lines
+        let mut wrong = Vec::new();
+        let mut count = 0;
+        for line in include_str!("../fixtures/porter2.txt").lines() {
+            let mut pair = line.split_whitespace();
+            let (word, stem) = (pair.next().unwrap(), pair.next().unwrap());
+            if porter2(word) != stem {
+                wrong.push((word, porter2(word), stem));
+            }
+            count += 1;
+        }
+        assert_eq!(count, 29417);
+        assert_eq!(wrong, vec![]);
+        let cases = [
+            ("caresses", "caress"),
+            ("ponies", "poni"),
+            ("ties", "tie"),
+            ("cries", "cri"),
+            ("gas", "gas"),
+            ("gaps", "gap"),
+            ("kiwis", "kiwi"),
+            ("this", "this"),
+            ("hopping", "hop"),
+            ("hoped", "hope"),
+            ("agreed", "agre"),
+            ("feed", "feed"),
+            ("succeeded", "succeed"),
+            ("proceed", "proceed"),
+            ("inning", "inning"),
+            ("skies", "sky"),
+            ("dying", "die"),
+            ("news", "news"),
+            ("generate", "generat"),
+            ("happily", "happili"),
+            ("cat's", "cat"),
+            ("'tis", "tis"),
+            ("by", "by"),
+            ("s's", "s"),
+            ("café", "café"),
+        ];
+        for (word, stem) in cases.iter() {
+            assert_eq!(porter2(word), *stem, "stem of {}", word);
+        }
+        assert!(matches!(porter2("knight"), Cow::Borrowed(_)));
+        assert!(matches!(porter2("knights"), Cow::Owned(_)));
+
+        let text = "the knights knitted knots";
+        test_vec_feats!(
+            stemmed(word_slice(1)).featurize(text),
+            String,
+            &["the", "knight", "knit", "knot"]
+        );
+        test_vec_feats!(
+            stemmed(for_each(whole())).featurize(text.split_whitespace()),
+            Cow<str>,
+            &["the", "knight", "knit", "knot"]
+        );
+        let words: Vec<String> = word_slice(1).featurize(text);
+        test_vec_feats!(
+            stemmed(for_each(whole())).featurize(words.iter()),
+            String,
+            &["the", "knight", "knit", "knot"]
+        );
+        test_vec_feats!(
+            stemmed(n_slice(5)).featurize(&b"knots"[..]),
+            Vec<u8>,
+            &[b"knot".to_vec()]
+        );
+        // bytes that aren't UTF-8 aren't stemmed, and only become text through `Lossy`
+        let bytes = &b"knots\xff"[..];
+        test_vec_feats!(
+            stemmed(n_slice(6)).featurize(bytes),
+            ByteStem,
+            &[ByteStem::Bytes(bytes)]
+        );
+        let a: HashSet<HashedAs<u64>> = stemmed(n_slice(6)).featurize(bytes);
+        let b: HashSet<HashedAs<u64>> = n_slice(6).featurize(bytes);
+        assert_eq!(a, b);
+        test_vec_feats!(
+            stemmed(n_slice(6)).featurize(bytes),
+            Lossy<String>,
+            &[Lossy("knots\u{FFFD}".to_owned())]
+        );
+        assert_eq!(
+            stemmed(n_slice(6)).try_featurize::<String, Vec<_>>(bytes),
+            Err(FeatureError::InvalidUtf8 { offset: 5 })
+        );
+        let a: HashSet<HashedAs<u64>> = stemmed(word_slice(1)).featurize("knits");
+        let b: HashSet<HashedAs<u64>> = word_slice(1).featurize("knit");
+        assert_eq!(a, b);
+    }
+
+    ////////////////////////////
+    //    normalized
+    ////////////////////////////
+    {
+        let all = Normalization::default()
+            .lowercase()
+            .ascii_fold()
+            .digits()
+            .collapse_whitespace();
+        assert_eq!(all.normalize(" \t\n "), "");
+        assert_eq!(all.normalize("A\u{a0}B\n\nC"), "a b c");
+        assert_eq!(
+            all.normalize("Straße – “Œuvre” 1999…"),
+            "strasse - \"oeuvre\" 0000..."
+        );
+        assert_eq!(all.normalize("İ"), "i\u{307}");
+        assert!(matches!(all.normalize("a b 0"), Cow::Borrowed(_)));
+        assert!(matches!(all.normalize("日本 語"), Cow::Borrowed(_)));
+        assert!(matches!(all.normalize("a  b"), Cow::Owned(_)));
+        assert!(matches!(all.normalize("a\tb"), Cow::Owned(_)));
+
+        let off = Normalization::default();
+        assert!(matches!(off.normalize(" Été  9 "), Cow::Borrowed(_)));
+        let lower = Normalization::default().lowercase();
+        assert_eq!(lower.normalize("ÉTÉ  9"), "été  9");
+        let fold = Normalization::default().ascii_fold();
+        assert_eq!(fold.normalize("ÉTÉ"), "ETE");
+        let digits = Normalization::default().digits();
+        assert_eq!(digits.normalize("A1b22"), "A0b00");
+        assert!(matches!(digits.normalize("A0b00"), Cow::Borrowed(_)));
+        let ws = Normalization::default().collapse_whitespace();
+        assert_eq!(ws.normalize(" A \r\n b "), "A b");
+
+        let opts = Normalization::default().lowercase().collapse_whitespace();
+        test_vec_feats!(
+            normalized(n_gram::<2>(), opts).featurize("A  B"),
+            [u8; 2],
+            &[*b"a ", *b" b"]
+        );
+        test_vec_feats!(
+            normalized(char_gram::<2>(), all).featurize("Éa"),
+            [char; 2],
+            &[['e', 'a']]
+        );
+        test_vec_feats!(
+            normalized(n_slice(2), opts).featurize("A  B"),
+            String,
+            &["a ", " b"]
+        );
+        let text = "ab cd";
+        test_vec_feats!(
+            normalized(n_slice(5), opts).featurize(text),
+            Cow<[u8]>,
+            &[Cow::Borrowed(text.as_bytes())]
+        );
+        let windows: Vec<NormalizedSlice> = normalized(n_slice(2), opts).featurize("AB CD");
+        assert_eq!(windows.len(), 4);
+        assert_eq!(&*windows[3], b"cd");
+        // one normalized buffer, shared by every window
+        for pair in windows.windows(2) {
+            assert_eq!(pair[0].as_ptr().wrapping_add(1), pair[1].as_ptr());
+        }
+        test_vec_feats!(
+            normalized(n_slice(2), opts).featurize("AB"),
+            Vec<u8>,
+            &[b"ab".to_vec()]
+        );
+        let cows: Vec<Cow<[u8]>> = normalized(n_slice(2), opts).featurize("AB");
+        assert!(matches!(cows[0], Cow::Owned(_)));
+        // bytes become text through `Lossy` or a checked conversion
+        let bytes: Cow<[u8]> = Cow::Borrowed(&[b'a', 0xff]);
+        let text: Result<String, _> = TryFeatureFrom::try_from(bytes.clone());
+        assert_eq!(text, Err(FeatureError::InvalidUtf8 { offset: 1 }));
+        let text: Lossy<String> = FeatureFrom::from(bytes);
+        assert_eq!(*text, "a\u{FFFD}");
+
+        let case_fold = Normalization::default().case_fold();
+        assert_eq!(
+            case_fold.normalize("Straße STRASSE ẞ"),
+            "strasse strasse ss"
+        );
+        assert_eq!(case_fold.normalize("ﬁne ΣΟΦΟΣ σοφος"), "fine σοφοσ σοφοσ");
+        assert_eq!(case_fold.normalize("ᾼ Ꮳ ꮳ"), "αι Ꮳ Ꮳ");
+        assert_eq!(lower.normalize("Straße"), "straße");
+        assert!(matches!(case_fold.normalize("strasse σ"), Cow::Borrowed(_)));
+        assert!(matches!(case_fold.normalize("straße"), Cow::Owned(_)));
+        assert!(matches!(case_fold.normalize("ς"), Cow::Owned(_)));
+        test_vec_feats!(
+            normalized(word_slice(1), case_fold).featurize("Maße MASSE"),
+            String,
+            &["masse", "masse"]
+        );
+        let owned = "Naïve,  NAIVE".to_owned();
+        test_vec_feats!(
+            normalized(word_slice(1), all).featurize(&owned),
+            Cow<str>,
+            &["naive", "naive"]
+        );
+        test_vec_feats!(normalized(whole(), opts).featurize(" X "), String, &["x"]);
+        test_vec_feats!(
+            stemmed(for_each(normalized(whole(), opts))).featurize("Knights KNIT".split(' ')),
+            String,
+            &["knight", "knit"]
+        );
+        let a: HashSet<HashedAs<u64>> = normalized(n_slice(3), opts).featurize("ABCD");
+        let b: HashSet<HashedAs<u64>> = n_slice(3).featurize("abcd");
+        assert_eq!(a, b);
+    }
+
+    ////////////////////////////
+    //    stopwords
+    ////////////////////////////
+    {
+        let english = Stopwords::english();
+        assert!(english.contains("the") && english.contains("isn't"));
+        assert!(!english.contains("The") && !english.contains("fish"));
+        assert!(Stopwords::french().contains("été"));
+        assert!(Stopwords::german().contains("für"));
+        assert!(Stopwords::spanish().contains("está"));
+        let both = english.union(&Stopwords::german());
+        assert!(both.contains("the") && both.contains("und"));
+        assert!(both.len() < english.len() + Stopwords::german().len());
+        assert!(Stopwords::default().is_empty());
+
+        test_vec_feats!(
+            without_stopwords(word_gram::<1>(), english.clone())
+                .featurize("To be or not to be, a fish"),
+            [&str; 1],
+            &[["To"], ["fish"]]
+        );
+        test_vec_feats!(
+            without_stopwords(word_gram::<2>(), english.clone())
+                .featurize("the cat in the hat sat on a mat"),
+            Joined,
+            &[
+                Joined("cat hat".to_owned()),
+                Joined("hat sat".to_owned()),
+                Joined("sat mat".to_owned())
+            ]
+        );
+        test_vec_feats!(
+            without_stopwords(n_gram::<2>(), english.clone()).featurize("the cat and a hat"),
+            [&str; 2],
+            &[["cat", "hat"]]
+        );
+        test_vec_feats!(
+            without_stopwords(word_gram::<2>(), english.clone()).featurize("the cat"),
+            [&str; 2],
+            Vec::<[&str; 2]>::new()
+        );
+        test_vec_feats!(
+            without_stopwords(word_gram::<1>(), english.clone())
+                .split_on(|c| c == ',')
+                .featurize("the,red fish,a"),
+            [&str; 1],
+            &[["red fish"]]
+        );
+        let words: Vec<&str> = sentence.split(' ').collect();
+        test_vec_feats!(
+            without_stopwords(word_gram::<1>(), english.clone()).featurize(&words),
+            [&str; 1],
+            &[
+                ["one"],
+                ["fish"],
+                ["two"],
+                ["fish"],
+                ["red"],
+                ["rish"],
+                ["blue"],
+                ["fish"]
+            ]
+        );
+        let opts = Normalization::default().lowercase();
+        let text = opts.normalize("The END").into_owned();
+        test_vec_feats!(
+            without_stopwords(word_gram::<1>(), english.clone()).featurize(&text),
+            [&str; 1],
+            &[["end"]]
+        );
+
+        let fixtures = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures");
+        let fillers = Stopwords::from_file(fixtures.join("stopwords.txt")).unwrap();
+        assert_eq!(fillers, Stopwords::from_words(vec!["uh", "um"]));
+        assert!(Stopwords::from_file(fixtures.join("missing.txt")).is_err());
+
+        test_vec_feats!(
+            stemmed(without_stopwords(word_gram::<2>(), fillers)).featurize("uh fishing uh fishes"),
+            [Cow<str>; 2],
+            &[[Cow::Borrowed("fish"), Cow::Borrowed("fish")]]
+        );
+    }
+
+    ////////////////////////////
+    //    lazy for_each
+    ////////////////////////////
+    {
+        let words = sentence.split_ascii_whitespace();
+        let pushed: Vec<&str> = for_each(n_slice(2)).featurize(words.clone());
+        let iterated: Vec<&[u8]> = for_each(n_slice(2))
+            .iterate_features(words.clone())
+            .collect();
+        assert_eq!(pushed.len(), iterated.len());
+        assert_eq!(pushed[0].as_bytes(), iterated[0]);
+        test_vec_feats!(
+            for_each(n_slice(2))
+                .iterate_features(vec!["", "a", "", "abc", ""])
+                .collect(),
+            &[u8],
+            &[b"ab", b"bc"]
+        );
+
+        let ftzr = for_each(n_gram::<2>());
+        let mut iter = ftzr.iterate_features(vec!["abc", "de"]);
+        assert_eq!(iter.next(), Some(*b"ab"));
+        assert_eq!(iter.next(), Some(*b"bc"));
+        assert_eq!(iter.next(), Some(*b"de"));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.size_hint(), (0, Some(0)));
+
+        let mut iter = for_each(whole()).iterate_features(vec!["one", "fish"]);
+        assert_eq!(iter.size_hint(), (0, None));
+        assert_eq!(iter.next(), Some(&b"one"[..]));
+        assert_eq!(iter.size_hint(), (0, None));
+        assert_eq!(iter.next(), Some(&b"fish"[..]));
+        assert_eq!(iter.size_hint(), (0, Some(0)));
+
+        let doc = vec![vec!["one", "fish"], vec![], vec!["two"]];
+        test_vec_feats!(
+            for_each(for_each(n_slice(3)))
+                .iterate_features(doc.clone())
+                .collect(),
+            &[u8],
+            &[b"one", b"fis", b"ish", b"two"]
+        );
+        let iterated: Vec<Merged<String>> = for_each(for_each(n_slice(3).and_then(whole())))
+            .iterate_features(doc)
+            .map(FeatureFrom::from)
+            .collect();
+        assert_eq!(iterated.len(), 7);
+
+        // usable where an `IterFtzr` is required
+        let rows = vec![b"ab".to_vec(), b"cde".to_vec(), b"f".to_vec()];
+        let ends: Vec<FrontBack<&[u8], &[u8]>> =
+            bookends((for_each(whole()), 1), (for_each(whole()), 1)).featurize(&rows[..]);
+        assert_eq!(
+            ends,
+            vec![FrontBack::Front(&b"ab"[..]), FrontBack::Back(&b"f"[..])]
+        );
+    }
+
+    ////////////////////////////
+    //    map & filter
+    ////////////////////////////
+    {
+        let lengths = word_slice(1).map(|w: &str| w.len());
+        test_vec_feats!(
+            lengths.featurize(sentence),
+            usize,
+            &[3, 4, 3, 4, 3, 4, 4, 4]
+        );
+        test_vec_feats!(
+            for_each(whole().map(|w: &[u8]| w.len())).featurize(sentence.split(' ')),
+            usize,
+            &[3, 4, 3, 4, 3, 4, 4, 4]
+        );
+
+        let no_spaces = n_gram::<2>().filter(|bg: &[u8; 2]| !bg.contains(&b' '));
+        test_vec_feats!(no_spaces.featurize("ab cd"), String, &["ab", "cd"]);
+        let (v, set): (Vec<String>, HashSet<[u8; 2]>) = no_spaces.featurize_x2("ab cd ab");
+        assert_eq!(v, vec!["ab", "cd", "ab"]);
+        assert_eq!(set.len(), 2);
+        let iterated: Vec<[u8; 2]> = no_spaces.iterate_features("ab cd").collect();
+        assert_eq!(iterated, vec![*b"ab", *b"cd"]);
+
+        let digits = n_slice(1).filter_map(|c: &[u8]| (c[0] as char).to_digit(10));
+        test_vec_feats!(digits.featurize("a1b2"), u32, &[1, 2]);
+        let iterated: Vec<u32> = digits.iterate_features("7x").collect();
+        assert_eq!(iterated, vec![7]);
+
+        let long_words = word_slice(1).filter(|w: &&str| w.len() > 3);
+        test_vec_feats!(
+            featurizers!(long_words, word_slice(1).map(|w: &str| w.to_uppercase()))
+                .featurize("one fish"),
+            Merged<String>,
+            &[
+                Merged("fish".to_owned()),
+                Merged("ONE".to_owned()),
+                Merged("FISH".to_owned())
+            ]
+        );
+        let firsts = n_gram::<3>().map(|t: [u8; 3]| t[0]);
+        test_vec_feats!(firsts.featurize("abcd"), u8, &[b'a', b'b']);
+        assert_eq!(firsts.chunk_size(), 3);
+        format!("{:?}", lengths);
+    }
+
+    ////////////////////////////
+    //    unique, take & skip
+    ////////////////////////////
+    {
+        let uniq = unique(for_each(whole()));
+        test_vec_feats!(
+            uniq.featurize(sentence.split(' ')),
+            &str,
+            &["one", "fish", "two", "red", "rish", "blue"]
+        );
+        // each input gets its own set
+        test_vec_feats!(uniq.featurize("fish fish".split(' ')), &str, &["fish"]);
+        test_vec_feats!(uniq.featurize("fish fish".split(' ')), &str, &["fish"]);
+        let as_fn = unique(n_slice(2)).as_fn::<String>();
+        let shared = &as_fn;
+        std::thread::scope(|s| {
+            s.spawn(move || assert_eq!(shared("abab"), vec!["ab", "ba"]));
+            s.spawn(move || assert_eq!(shared("aaa"), vec!["aa"]));
+        });
+        let ftzr = unique(n_slice(1));
+        let (v, bag): (Vec<&str>, Bag<HashMap<&str, u8>>) = ftzr.featurize_x2("abab");
+        assert_eq!(v, vec!["a", "b"]);
+        assert_eq!(bag.0.values().max(), Some(&1));
+        let iterated: Vec<&[u8]> = ftzr.iterate_features("abab").collect();
+        assert_eq!(iterated, vec![b"a", b"b"]);
+        test_vec_feats!(
+            for_each(unique(n_slice(1))).featurize(vec!["aa", "aa"]),
+            &str,
+            &["a", "a"]
+        );
+        // the set is reused by the next call, and a nested call gets its own
+        let mut nested: Vec<Vec<&str>> = Vec::new();
+        ftzr.push_tokens("aab", &mut |_| nested.push(ftzr.featurize("xyx")));
+        assert_eq!(nested, vec![vec!["x", "y"], vec!["x", "y"]]);
+        test_vec_feats!(ftzr.featurize("abab"), &str, &["a", "b"]);
+        test_vec_feats!(ftzr.clone().featurize("abab"), &str, &["a", "b"]);
+
+        let all: Vec<&str> = n_slice(2).featurize(sentence);
+        let some: Vec<&str> = sample(n_slice(2), 3).featurize(sentence);
+        assert!(!some.is_empty() && some.len() < all.len());
+        assert!(some.iter().all(|g| all.contains(g)));
+        let iterated: Vec<&[u8]> = sample(n_slice(2), 3).iterate_features(sentence).collect();
+        assert_eq!(iterated.len(), some.len());
+        // sampled by the same hash as `HashedAs<u64>`
+        let hashed: Vec<HashedAs<u64>> = sample(n_slice(2), 3).featurize(sentence);
+        assert!(hashed.iter().all(|h| h.0 <= u64::MAX / 3));
+        test_vec_feats!(
+            sample(n_slice(2), 1).featurize(ak),
+            &str,
+            n_slice(2).featurize::<&str, Vec<_>>(ak)
+        );
+        test_vec_feats!(
+            sample(n_slice(2), 0).featurize(ak),
+            &str,
+            n_slice(2).featurize::<&str, Vec<_>>(ak)
+        );
+        assert_eq!(
+            sample(n_slice(2), 3).try_featurize::<&str, Vec<_>>("a"),
+            Err(FeatureError::InputTooShort { len: 1, min: 2 })
+        );
+
+        test_vec_feats!(take(n_slice(2), 3).featurize(ak), &str, &["ab", "bc", "cd"]);
+        test_vec_feats!(take(n_slice(2), 0).featurize(ak), &str, Vec::<&str>::new());
+        test_vec_feats!(take(n_slice(2), 99).featurize("abc"), &str, &["ab", "bc"]);
+        test_vec_feats!(skip(n_slice(2), 2).featurize("abcde"), &str, &["cd", "de"]);
+        test_vec_feats!(
+            skip(n_slice(2), 9).featurize("abcde"),
+            &str,
+            Vec::<&str>::new()
+        );
+        let words = take(without_stopwords(word_gram::<1>(), Stopwords::english()), 2);
+        test_vec_feats!(
+            words.featurize("the cat and the hat sat"),
+            [&str; 1],
+            &[["cat"], ["hat"]]
+        );
+
+        // the inner featurizer stops after k token groups
+        let calls = std::cell::Cell::new(0);
+        let counted = |t: &'static [u8]| {
+            calls.set(calls.get() + 1);
+            t
+        };
+        test_vec_feats!(
+            take(n_slice(1).map(counted), 2).featurize(ak),
+            &str,
+            &["a", "b"]
+        );
+        assert_eq!(calls.replace(0), 2);
+        test_vec_feats!(
+            take(for_each(n_slice(1).map(counted)), 3).featurize(vec!["ab", "cd", "ef"]),
+            &str,
+            &["a", "b", "c"]
+        );
+        assert_eq!(calls.replace(0), 3);
+        test_vec_feats!(
+            take(skip(unique(n_slice(1).map(counted)), 1), 2).featurize("aabbcc"),
+            &str,
+            &["b", "c"]
+        );
+        assert_eq!(calls.replace(0), 5);
+        let flow = take(n_slice(1), 3).push_tokens_while(ak, &mut |t: &[u8]| {
+            if t == b"b" {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        });
+        assert!(flow.is_break());
+        let flow = take(n_slice(1), 3).push_tokens_while(ak, &mut |_| ControlFlow::Continue(()));
+        assert!(flow.is_continue());
+        // push-only featurizers visit the whole input, but only k are kept
+        test_vec_feats!(
+            take(uniform_bins(0.0, 10.0, 10), 2).featurize(&[1.0, 2.0, 3.0][..]),
+            Bin,
+            &[Bin(1), Bin(2)]
+        );
+
+        let iterated: Vec<[u8; 2]> = take(skip(n_gram::<2>(), 1), 2)
+            .iterate_features("abcde")
+            .collect();
+        assert_eq!(iterated, vec![*b"bc", *b"cd"]);
+        let mut iter = take(unique(n_gram::<1>()), 2).iterate_features("aaaabbbbcccc");
+        assert_eq!(iter.next(), Some(*b"a"));
+        assert_eq!(iter.next(), Some(*b"b"));
+        assert_eq!(iter.next(), None);
+        format!("{:?}", uniq);
+    }
+
+    ////////////////////////////
+    //    and_then & zip_with
+    ////////////////////////////
+    {
+        let ftzr = n_slice(1).and_then(whole()).and_then(n_gram::<2>());
+        test_vec_feats!(
+            ftzr.featurize("ab"),
+            EitherGroup<EitherGroup<&str, String>, [u8; 2]>,
+            &[
+                Left(Left("a")),
+                Left(Left("b")),
+                Left(Right("ab".to_owned())),
+                Right(*b"ab")
+            ]
+        );
+        test_vec_feats!(
+            ftzr.featurize("ab"),
+            Merged<HashedAs<u64>>,
+            ftzr.featurize::<Merged<HashedAs<u64>>, Vec<_>>("ab")
+        );
+        let iterated: Vec<_> = ftzr.iterate_features("ab").collect();
+        assert_eq!(iterated.len(), 4);
+        assert!(iterated[0].is_left() && iterated[3].is_right());
+        assert_eq!(iterated[3].right(), Some(*b"ab"));
+        assert_eq!(
+            iterated[2].left().and_then(EitherGroup::right),
+            Some(&b"ab"[..])
+        );
+        assert_eq!(
+            Left::<u8, char>(1)
+                .as_ref()
+                .either(|a| *a as u32, |b| *b as u32),
+            1
+        );
+
+        let merged: Vec<Merged<String>> = featurizers!(n_slice(1), whole()).featurize("ab");
+        let chained: Vec<Merged<String>> = n_slice(1).and_then(whole()).featurize("ab");
+        assert_eq!(merged, chained);
+
+        test_vec_feats!(
+            n_slice(1).zip_with(n_gram::<2>()).featurize("abc"),
+            (&str, String),
+            &[("a", "ab".to_owned()), ("b", "bc".to_owned())]
+        );
+        test_vec_feats!(
+            n_slice(2).zip_with(n_slice(1)).featurize(&[1, 2, 3][..]),
+            (&[i32], HashedAs<u64>),
+            n_slice(2)
+                .zip_with(n_slice(1))
+                .iterate_features(&[1, 2, 3][..])
+                .map(|(a, b)| (a, FeatureFrom::from(b)))
+                .collect::<Vec<_>>()
+        );
+        test_vec_feats!(
+            for_each(whole().zip_with(n_slice(1))).featurize(sentence.split(' ').take(2)),
+            (&str, &str),
+            &[("one", "o"), ("fish", "f")]
+        );
+        // push-only featurizers are zipped too, and `A` stops once `B` runs out
+        let calls = std::cell::Cell::new(0);
+        let counted = |t: &'static [u8]| {
+            calls.set(calls.get() + 1);
+            t
+        };
+        test_vec_feats!(
+            n_slice(1)
+                .map(counted)
+                .zip_with(without_stopwords(word_gram::<1>(), Stopwords::english()))
+                .featurize("a cat"),
+            (&str, [&str; 1]),
+            &[("a", ["cat"])]
+        );
+        assert_eq!(calls.get(), 2);
+    }
+
+    ////////////////////////////
+    //    short inputs
+    ////////////////////////////
+    {
+        let ends = bookends((n_slice(2), 3), (n_slice(1), 2));
+        test_vec_feats!(ends.featurize(""), Merged<&str>, Vec::<Merged<&str>>::new());
+        test_vec_feats!(
+            ends.featurize("a"),
+            FrontBack<&str, &str>,
+            &[FrontBack::Back("a")]
+        );
+        test_vec_feats!(
+            ends.featurize("ab"),
+            FrontBack<&str, &str>,
+            &[FrontBack::Front("ab"), FrontBack::Back("a"), FrontBack::Back("b")]
+        );
+        test_vec_feats!(
+            ends.featurize(&Vec::<u8>::new()),
+            Merged<Vec<u8>>,
+            Vec::<Merged<Vec<u8>>>::new()
+        );
+        test_vec_feats!(
+            with_spans(ends).featurize("a"),
+            (Range<usize>, Merged<&str>),
+            &[(0..1, Merged("a"))]
+        );
+        assert_eq!(ends.min_len(), 3);
+
+        let gapped = gap_gram(n_slice(1), 2, n_gram::<2>());
+        test_vec_feats!(
+            gapped.featurize(""),
+            (&str, String),
+            Vec::<(&str, String)>::new()
+        );
+        test_vec_feats!(
+            gapped.featurize("a"),
+            (&str, String),
+            Vec::<(&str, String)>::new()
+        );
+        test_vec_feats!(
+            gapped.featurize(&[1][..]),
+            (&[i32], Vec<i32>),
+            Vec::<(&[i32], Vec<i32>)>::new()
+        );
+        test_vec_feats!(
+            gapped.featurize("abcd"),
+            (&str, String),
+            Vec::<(&str, String)>::new()
+        );
+        test_vec_feats!(
+            gapped.featurize("abcde"),
+            (&str, String),
+            &[("a", "de".to_owned())]
+        );
+        test_vec_feats!(
+            with_spans(gapped).featurize("ab"),
+            ((Range<usize>, Range<usize>), HashedAs<u64>),
+            Vec::<((Range<usize>, Range<usize>), HashedAs<u64>)>::new()
+        );
+
+        let err = FeatureError::InputTooShort { len: 1, min: 5 };
+        assert_eq!(
+            gapped.try_featurize::<(&str, String), Vec<_>>("a"),
+            Err(err)
+        );
+        assert_eq!(
+            gapped.try_featurize::<(&str, String), Vec<_>>("abcde"),
+            Ok(vec![("a", "de".to_owned())])
+        );
+        assert_eq!(
+            bislice.try_featurize::<&str, Vec<_>>(""),
+            Err(FeatureError::InputTooShort { len: 0, min: 2 })
+        );
+        assert_eq!(
+            bislice.try_featurize::<&str, Vec<_>>(&[b'a', b'b']),
+            Ok(vec!["ab"])
+        );
+        assert_eq!(
+            ends.try_featurize::<Merged<&str>, Vec<_>>(&"ab".to_owned()),
+            Err(FeatureError::InputTooShort { len: 2, min: 3 })
+        );
+        let mut count = 0;
+        assert!(ends
+            .try_push_tokens(&[1, 2, 3], &mut |_| count += 1)
+            .is_ok());
+        assert_eq!(count, 4);
+        // featurizing stops at the first error
+        let calls = std::cell::Cell::new(0);
+        let counted = |t: &'static [u8]| {
+            calls.set(calls.get() + 1);
+            t
+        };
+        assert_eq!(
+            n_slice(1)
+                .map(counted)
+                .try_featurize::<&str, Vec<_>>(&[b'a', 0xff, b'b', b'c']),
+            Err(FeatureError::InvalidUtf8 { offset: 0 })
+        );
+        assert_eq!(calls.get(), 2);
+        assert_eq!(
+            err.to_string(),
+            "input of length 1 is shorter than the minimum of 5"
+        );
+
+        // skipping short inputs
+        let words: Vec<Vec<[u8; 3]>> = sentence
+            .split(' ')
+            .filter_map(|w| {
+                n_gram::<4>()
+                    .map(|g: [u8; 4]| [g[0], g[1], g[2]])
+                    .try_featurize(w)
+                    .ok()
+            })
+            .collect();
+        assert_eq!(words.len(), 5);
+    }
+
+    ////////////////////////////
+    //    short inputs of combinators
+    ////////////////////////////
+    {
+        let short = |len, min| FeatureError::InputTooShort { len, min };
+        let none: &[i32] = &[];
+        let one: &[i32] = &[7];
+
+        // for_each has no minimum, each item is clamped
+        let each = for_each(n_slice(3));
+        assert_eq!(each.min_len(), 0);
+        let items: &[[i32; 2]] = &[];
+        assert_eq!(each.try_featurize::<Vec<i32>, Vec<_>>(items), Ok(vec![]));
+        assert_eq!(
+            each.try_featurize::<Vec<i32>, Vec<_>>(&[[1, 2]]),
+            Ok(vec![])
+        );
+
+        let pad = padded(n_gram::<3>(), 1, 1);
+        assert_eq!(pad.min_len(), 1);
+        assert!(pad.featurize::<[Pad<i32>; 3], Vec<_>>(none).is_empty());
+        assert_eq!(
+            pad.featurize::<[Pad<i32>; 3], Vec<_>>(one),
+            vec![[Pad::Start, Pad::Token(7), Pad::End]]
+        );
+        assert_eq!(
+            pad.try_featurize::<[Pad<i32>; 3], Vec<_>>(none),
+            Err(short(0, 1))
+        );
+        assert_eq!(padded(n_gram::<5>(), 1, 1).min_len(), 3);
+        assert_eq!(padded(n_slice(0), 1, 1).min_len(), 0);
+
+        let stride = strided(n_slice(2), 2);
+        assert_eq!(stride.min_len(), 2);
+        assert_eq!(
+            stride.featurize::<&[i32], Vec<_>>(none),
+            Vec::<&[i32]>::new()
+        );
+        assert_eq!(
+            stride.featurize::<&[i32], Vec<_>>(one),
+            Vec::<&[i32]>::new()
+        );
+        assert_eq!(
+            stride.try_featurize::<&[i32], Vec<_>>(one),
+            Err(short(1, 2))
+        );
+        let ragged = n_slice(2).stride(2).tail(ShortTail);
+        assert_eq!(ragged.featurize::<&[i32], Vec<_>>(one), vec![one]);
+        assert_eq!(
+            ragged.try_featurize::<&[i32], Vec<_>>(one),
+            Err(short(1, 2))
+        );
+
+        let skips = skip_gram(1..=1, 0..=1, 1..=2);
+        assert_eq!(skips.min_len(), 4);
+        assert!(skips.featurize::<HashedAs<u64>, Vec<_>>(none).is_empty());
+        assert!(skips.featurize::<HashedAs<u64>, Vec<_>>(one).is_empty());
+        assert_eq!(
+            skips.try_featurize::<HashedAs<u64>, Vec<_>>(one),
+            Err(short(1, 4))
+        );
+
+        let range = n_range(1, 3);
+        assert_eq!(range.min_len(), 3);
+        assert_eq!(
+            range.featurize::<&[i32], Vec<_>>(none),
+            Vec::<&[i32]>::new()
+        );
+        assert_eq!(range.featurize::<&[i32], Vec<_>>(one), vec![one]);
+        assert_eq!(
+            range.try_featurize::<&[i32], Vec<_>>(none),
+            Err(short(0, 3))
+        );
+
+        let diffs = deltas(n_gram::<2>());
+        assert_eq!(diffs.min_len(), 3);
+        assert!(diffs.featurize::<[i128; 2], Vec<_>>(none).is_empty());
+        assert!(diffs.featurize::<[i128; 2], Vec<_>>(one).is_empty());
+        assert_eq!(
+            diffs.try_featurize::<[i128; 2], Vec<_>>(one),
+            Err(short(1, 3))
+        );
+        assert_eq!(
+            diffs.try_featurize::<[i128; 2], Vec<_>>(&[1, 2, 4][..]),
+            Ok(vec![[1, 2]])
+        );
+
+        let prints = winnow(n_slice(2), 3);
+        assert_eq!(prints.min_len(), 4);
+        assert!(prints.featurize::<HashedAs<u64>, Vec<_>>(none).is_empty());
+        assert!(prints.featurize::<HashedAs<u64>, Vec<_>>(one).is_empty());
+        assert_eq!(
+            prints.featurize::<HashedAs<u64>, Vec<_>>(&[1, 2][..]).len(),
+            1
+        );
+        assert_eq!(
+            prints.try_featurize::<u64, Vec<_>>(&[1, 2][..]),
+            Err(short(2, 4))
+        );
+        assert_eq!(
+            prints
+                .try_featurize::<HashedAs<u64>, Vec<_>>(&[1, 2, 3, 4][..])
+                .map(|v| v.len()),
+            Ok(1)
+        );
+
+        let mapped = n_slice(2).map(|s: &[i32]| s.len());
+        assert_eq!(mapped.min_len(), 2);
+        assert!(mapped.featurize::<usize, Vec<_>>(one).is_empty());
+        assert_eq!(mapped.try_featurize::<usize, Vec<_>>(one), Err(short(1, 2)));
+        let kept = n_slice(2).filter(|s: &&[i32]| s[0] > 0);
+        assert_eq!(kept.try_featurize::<&[i32], Vec<_>>(none), Err(short(0, 2)));
+        let firsts = n_slice(2).filter_map(|s: &[i32]| s.first().copied());
+        assert_eq!(firsts.try_featurize::<i32, Vec<_>>(one), Err(short(1, 2)));
+
+        assert_eq!(
+            unique(n_slice(2)).featurize::<&[i32], Vec<_>>(none),
+            Vec::<&[i32]>::new()
+        );
+        assert_eq!(
+            unique(n_slice(2)).try_featurize::<&[i32], Vec<_>>(one),
+            Err(short(1, 2))
+        );
+        assert_eq!(
+            take(n_slice(2), 1).featurize::<&[i32], Vec<_>>(one),
+            Vec::<&[i32]>::new()
+        );
+        assert_eq!(
+            take(n_slice(2), 1).try_featurize::<&[i32], Vec<_>>(none),
+            Err(short(0, 2))
+        );
+        assert_eq!(
+            skip(n_slice(1), 1).featurize::<&[i32], Vec<_>>(one),
+            Vec::<&[i32]>::new()
+        );
+        assert_eq!(
+            skip(n_slice(1), 1).try_featurize::<&[i32], Vec<_>>(one),
+            Ok(vec![])
+        );
+
+        let zipped = n_slice(1).zip_with(n_slice(3));
+        assert_eq!(zipped.min_len(), 3);
+        assert!(zipped
+            .featurize::<(&[i32], &[i32]), Vec<_>>(none)
+            .is_empty());
+        assert!(zipped.featurize::<(&[i32], &[i32]), Vec<_>>(one).is_empty());
+        assert_eq!(
+            zipped.try_featurize::<(&[i32], &[i32]), Vec<_>>(one),
+            Err(short(1, 3))
+        );
+        let chained = n_slice(1).and_then(n_slice(2));
+        assert_eq!(chained.min_len(), 2);
+        assert!(chained.featurize::<Merged<&[i32]>, Vec<_>>(none).is_empty());
+        assert_eq!(
+            chained.featurize::<Merged<&[i32]>, Vec<_>>(one),
+            vec![Merged(one)]
+        );
+        assert_eq!(
+            chained.try_featurize::<Merged<&[i32]>, Vec<_>>(one),
+            Err(short(1, 2))
+        );
+        assert_eq!(featurizers!(n_slice(3), n_slice(1)).min_len(), 3);
+
+        let series = sax(3, 3);
+        assert_eq!(series.min_len(), 1);
+        assert_eq!(series.featurize::<char, String>(none), "");
+        assert_eq!(series.featurize::<char, String>(one), "bbb");
+        assert_eq!(series.try_featurize::<char, String>(none), Err(short(0, 1)));
+        let counts: Vec<u64> = vec![3, 5, 40, 42, 7, 1];
+        assert_eq!(
+            series.try_featurize::<char, String>(&counts),
+            Ok("aca".to_owned())
+        );
+        let windows = sax(2, 2).over(n_gram::<4>());
+        assert_eq!(windows.min_len(), 4);
+        assert_eq!(
+            windows.featurize::<String, Vec<_>>(one),
+            Vec::<String>::new()
+        );
+        assert_eq!(
+            windows.try_featurize::<String, Vec<_>>(one),
+            Err(short(1, 4))
+        );
+
+        let binned = uniform_bins(0.0, 10.0, 10);
+        assert_eq!(binned.min_len(), 0);
+        assert_eq!(binned.try_featurize::<Bin, Vec<_>>(none), Ok(vec![]));
+        assert_eq!(binned.try_featurize::<u32, Vec<_>>(one), Ok(vec![7]));
+
+        assert_eq!(stemmed(n_slice(3)).min_len(), 3);
+        assert_eq!(rolling_hash(3).min_len(), 3);
+        assert_eq!(whole().min_len() + empty().min_len(), 0);
+
+        // text, where the length of a `&str` is in bytes
+        assert_eq!(
+            char_slice(2).try_featurize::<&str, Vec<_>>(""),
+            Err(short(0, 2))
+        );
+        assert_eq!(
+            char_slice(2).try_featurize::<&str, Vec<_>>("a"),
+            Err(short(1, 2))
+        );
+        assert_eq!(
+            char_slice(1).try_featurize::<&str, Vec<_>>("é"),
+            Ok(vec!["é"])
+        );
+        assert_eq!(
+            char_gram::<2>().try_featurize::<[char; 2], Vec<_>>(""),
+            Err(short(0, 2))
+        );
+        assert_eq!(
+            char_gram::<2>().try_featurize::<[char; 2], Vec<_>>("a"),
+            Err(short(1, 2))
+        );
+        #[cfg(feature = "graphemes")]
+        {
+            assert_eq!(
+                grapheme_slice(1).try_featurize::<&str, Vec<_>>(""),
+                Err(short(0, 1))
+            );
+            assert_eq!(
+                grapheme_slice(1).try_featurize::<&str, Vec<_>>("a"),
+                Ok(vec!["a"])
+            );
+        }
+        assert_eq!(
+            word_slice(1).try_featurize::<&str, Vec<_>>(""),
+            Err(short(0, 1))
+        );
+        assert_eq!(
+            word_slice(1).try_featurize::<&str, Vec<_>>("a"),
+            Ok(vec!["a"])
+        );
+        assert_eq!(
+            word_gram::<2>().try_featurize::<[&str; 2], Vec<_>>(""),
+            Err(short(0, 2))
+        );
+        assert_eq!(
+            word_gram::<2>().try_featurize::<[&str; 2], Vec<_>>("a"),
+            Err(short(1, 2))
+        );
+        let words: &[&str] = &["a"];
+        assert_eq!(
+            word_gram::<2>().try_featurize::<[&str; 2], Vec<_>>(words),
+            Err(short(1, 2))
+        );
+        assert_eq!(
+            word_gram::<1>().try_featurize::<[&str; 1], Vec<_>>(words),
+            Ok(vec![["a"]])
+        );
+
+        let lower = Normalization::default().lowercase();
+        let norm = normalized(n_slice(2), lower);
+        assert_eq!(norm.try_featurize::<String, Vec<_>>(""), Err(short(0, 2)));
+        assert_eq!(norm.try_featurize::<String, Vec<_>>("A"), Err(short(1, 2)));
+        let norm = normalized(word_slice(1), lower);
+        assert_eq!(norm.try_featurize::<String, Vec<_>>(""), Err(short(0, 1)));
+        assert_eq!(
+            norm.try_featurize::<String, Vec<_>>("A"),
+            Ok(vec!["a".to_owned()])
+        );
+
+        let fillers = without_stopwords(word_gram::<1>(), Stopwords::english());
+        assert_eq!(
+            fillers.try_featurize::<[&str; 1], Vec<_>>(""),
+            Err(short(0, 1))
+        );
+        assert_eq!(fillers.try_featurize::<[&str; 1], Vec<_>>("a"), Ok(vec![]));
+        assert_eq!(
+            fillers.try_featurize::<[&str; 1], Vec<_>>("b"),
+            Ok(vec![["b"]])
+        );
+
+        let vocab = WordPieceVocab::new(vec!["[UNK]", "a"]);
+        let pieces = word_piece(&vocab).unwrap();
+        assert_eq!(pieces.min_len(), 0);
+        assert_eq!(pieces.try_featurize::<u32, Vec<_>>(""), Ok(vec![]));
+        assert_eq!(pieces.try_featurize::<u32, Vec<_>>("a"), Ok(vec![1]));
+
+        let title = field("title", |t: &'static str| t, n_slice(2));
+        assert_eq!(title.min_len(), 0);
+        assert_eq!(title.try_featurize::<(&str, &str), Vec<_>>(""), Ok(vec![]));
+        assert_eq!(title.try_featurize::<(&str, &str), Vec<_>>("a"), Ok(vec![]));
+
+        let spans = with_spans(n_slice(2));
+        assert_eq!(
+            spans.try_featurize::<(Range<usize>, &str), Vec<_>>(""),
+            Err(short(0, 2))
+        );
+        assert_eq!(
+            spans.try_featurize::<(Range<usize>, &str), Vec<_>>("a"),
+            Err(short(1, 2))
+        );
+        assert_eq!(
+            spans.try_featurize::<(Range<usize>, &str), Vec<_>>("ab"),
+            Ok(vec![(0..2, "ab")])
+        );
+    }
+
+    ////////////////////////////
+    //    fallible features
+    ////////////////////////////
+    {
+        let bytes: &[u8] = &[b'a', b'b', 0xff, b'c'];
+        assert_eq!(
+            n_slice(2).try_featurize::<&str, Vec<_>>(bytes),
+            Err(FeatureError::InvalidUtf8 { offset: 1 })
+        );
+        assert_eq!(
+            n_gram::<3>().try_featurize::<String, Vec<_>>(bytes),
+            Err(FeatureError::InvalidUtf8 { offset: 2 })
+        );
+        assert_eq!(
+            n_slice(2).try_featurize::<&str, Vec<_>>("abc"),
+            Ok(vec!["ab", "bc"])
+        );
+        test_vec_feats!(
+            n_slice(2).try_featurize(bytes).unwrap(),
+            Lossy<String>,
+            &[
+                Lossy("ab".to_owned()),
+                Lossy("b\u{FFFD}".to_owned()),
+                Lossy("\u{FFFD}c".to_owned())
+            ]
+        );
+        test_vec_feats!(
+            n_gram::<2>().featurize(bytes),
+            Lossy<String>,
+            &[
+                Lossy("ab".to_owned()),
+                Lossy("b\u{FFFD}".to_owned()),
+                Lossy("\u{FFFD}c".to_owned())
+            ]
+        );
+        let cows: Vec<Lossy<Cow<str>>> = n_slice(4).featurize(bytes);
+        assert_eq!(*cows[0], "ab\u{FFFD}c");
+
+        // hashing never fails, whether or not the bytes are text
+        let hashed: Result<Vec<HashedAs<u64>>, _> = n_slice(2).try_featurize(bytes);
+        assert_eq!(hashed, Ok(n_slice(2).featurize(bytes)));
+
+        // wrapped token groups
+        let ends = bookends((n_slice(2), 2), (n_slice(2), 2));
+        assert_eq!(
+            ends.try_featurize::<Merged<String>, Vec<_>>(bytes),
+            Err(FeatureError::InvalidUtf8 { offset: 0 })
+        );
+        assert_eq!(
+            ends.try_featurize::<FrontBack<&str, HashedAs<u64>>, Vec<_>>(&b"abcd"[..]),
+            Ok(vec![
+                FrontBack::Front("ab"),
+                FrontBack::Back(FeatureFrom::from(&b"cd"[..]))
+            ])
+        );
+        let gapped = gap_gram(n_slice(1), 1, n_slice(1));
+        assert_eq!(
+            gapped.try_featurize::<(&str, &str), Vec<_>>(bytes),
+            Err(FeatureError::InvalidUtf8 { offset: 0 })
+        );
+        let either: Result<EitherGroup<&str, String>, _> =
+            TryFeatureFrom::try_from(Right::<&[u8], &[u8]>(&[0xc3, 0x28][..]));
+        assert_eq!(either, Err(FeatureError::InvalidUtf8 { offset: 0 }));
+        assert_eq!(
+            gapped.try_featurize::<(String, usize, &str), Vec<_>>(&b"abc"[..]),
+            Ok(vec![("a".to_owned(), 1, "c")])
+        );
+        let ends: Result<Vec<Result<&str, String>>, _> =
+            bookends((n_slice(1), 1), (n_slice(1), 1)).try_featurize(&b"ab"[..]);
+        assert_eq!(ends, Ok(vec![Ok("a"), Err("b".to_owned())]));
+        assert_eq!(
+            whole().try_featurize::<String, Vec<_>>(&[b'a', 0xff][..]),
+            Err(FeatureError::InvalidUtf8 { offset: 1 })
+        );
+
+        // every token group has a checked conversion
+        let piece: Result<String, _> = TryFeatureFrom::try_from(Subword {
+            piece: "##c",
+            id: 5,
+        });
+        assert_eq!(piece, Ok("##c".to_owned()));
+        let joined: Result<Joined<'_'>, _> = TryFeatureFrom::try_from(["cat", "hat"]);
+        assert_eq!(joined, Ok(Joined("cat_hat".to_owned())));
+        let id: Result<u32, _> = TryFeatureFrom::try_from(Bin(3));
+        assert_eq!(id, Ok(3));
+        let none: Result<Option<u8>, _> = TryFeatureFrom::try_from(EmptyAtom);
+        assert_eq!(none, Ok(None));
+
+        // the first error wins
+        assert_eq!(
+            n_slice(2).try_featurize::<&str, Vec<_>>(&[0xff, b'a', 0xff][..]),
+            Err(FeatureError::InvalidUtf8 { offset: 0 })
+        );
+
+        assert_eq!(
+            FeatureError::InvalidUtf8 { offset: 2 }.to_string(),
+            "invalid UTF-8 at byte 2 of a token group"
+        );
+        assert_eq!(
+            FeatureError::CapacityExceeded { capacity: 8 }.to_string(),
+            "the accumulator is full at a capacity of 8"
+        );
+    }
+
+    ////////////////////////////
+    //    words
+    ////////////////////////////
+    {
+        let text = "  one fish, two fish... red fish! ";
+        test_vec_feats!(
+            word_slice(1).featurize(text),
+            &str,
+            &["one", "fish", "two", "fish", "red", "fish"]
+        );
+        test_vec_feats!(
+            word_slice(3).featurize(text),
+            &str,
+            &[
+                "one fish, two",
+                "fish, two fish",
+                "two fish... red",
+                "fish... red fish"
+            ]
+        );
+        test_vec_feats!(
+            word_slice(2).split_on(|c: char| c == ' ').featurize(text),
+            &str,
+            &[
+                "one fish,",
+                "fish, two",
+                "two fish...",
+                "fish... red",
+                "red fish!"
+            ]
+        );
+        test_vec_feats!(word_slice(7).featurize(text), &str, Vec::<&str>::new());
+        test_vec_feats!(word_slice(0).featurize(text), &str, &[""; 7]);
+        test_vec_feats!(word_slice(1).featurize(" ,. "), &str, Vec::<&str>::new());
+        test_vec_feats!(
+            word_gram::<2>().featurize(&text.to_owned()),
+            Joined<'_'>,
+            &[
+                Joined("one_fish".to_owned()),
+                Joined("fish_two".to_owned()),
+                Joined("two_fish".to_owned()),
+                Joined("fish_red".to_owned()),
+                Joined("red_fish".to_owned()),
+            ]
+        );
+        test_vec_feats!(
+            word_gram::<7>().featurize(text),
+            [&str; 7],
+            Vec::<[&str; 7]>::new()
+        );
+        let words: Vec<&str> = Iterator::collect(text.split_ascii_whitespace());
+        test_vec_feats!(
+            n_slice(2).featurize(&words),
+            Joined,
+            &[
+                Joined("one fish,".to_owned()),
+                Joined("fish, two".to_owned()),
+                Joined("two fish...".to_owned()),
+                Joined("fish... red".to_owned()),
+                Joined("red fish!".to_owned()),
+            ]
+        );
+        let _feats: Vec<HashedAs<u64>> = word_gram::<2>().featurize(text);
+        let _feats: Vec<Vec<&str>> = word_gram::<2>().featurize(text);
+        let _feats: Bag<HashMap<&str, u8>> = word_slice(1).featurize(text);
+    }
+
+    ////////////////////////////
+    //    grapheme clusters
+    ////////////////////////////
+    #[cfg(feature = "graphemes")]
+    {
+        // ZWJ emoji sequence
+        let family = "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}";
+        let text = format!("a{}b", family);
+        test_vec_feats!(graphemes().featurize(&text), &str, &["a", family, "b"]);
+        test_vec_feats!(
+            grapheme_slice(2).featurize(&text),
+            String,
+            &[format!("a{}", family), format!("{}b", family)]
+        );
+
+        // regional indicators pair up into flags
+        let flags = "\u{1F1EB}\u{1F1F7}\u{1F1E9}\u{1F1EA}\u{1F1EF}";
+        test_vec_feats!(
+            graphemes().featurize(flags),
+            &str,
+            &["\u{1F1EB}\u{1F1F7}", "\u{1F1E9}\u{1F1EA}", "\u{1F1EF}"]
+        );
+
+        // combining marks stay attached to their base
+        let accents = "a\u{301}\u{316}e\u{300}";
+        test_vec_feats!(
+            graphemes().featurize(accents),
+            &str,
+            &["a\u{301}\u{316}", "e\u{300}"]
+        );
+        test_vec_feats!(grapheme_slice(2).featurize(accents), &str, &[accents]);
+
+        // Hangul jamo compose a single syllable
+        let jamo = "\u{1100}\u{1161}\u{11A8}\u{1100}\u{1161}";
+        test_vec_feats!(
+            graphemes().featurize(jamo),
+            &str,
+            &["\u{1100}\u{1161}\u{11A8}", "\u{1100}\u{1161}"]
+        );
+
+        test_vec_feats!(
+            grapheme_slice(3).featurize(accents),
+            &str,
+            Vec::<&str>::new()
+        );
+        test_vec_feats!(grapheme_slice(0).featurize(accents), &str, &["", "", ""]);
+        test_vec_feats!(grapheme_slice(0).featurize(""), &str, &[""]);
+        test_vec_feats!(graphemes().featurize(""), &str, Vec::<&str>::new());
+        let _feats: Vec<HashedAs<u64>> = grapheme_slice(2).featurize(&text);
+    }
+
     let _feats: (HashSet<HashedAs<u64>>, Vec<&str>) = bislice.featurize_x2(ak);
     //type Collide = Collisions<&[u8], HashMap<HashedAs<u16>, &str>>;
     //let _feats: Collisions<&[u8], HashMap<HashedAs<u16>, String>> =
@@ -1,5 +1,8 @@
+use crate::error::FeatureError;
+use crate::feature_from::{try_via_from, FeatureFrom, TryFeatureFrom};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use std::collections::{LinkedList, VecDeque};
 use std::hash::Hash;
 use std::iter::FromIterator;
@@ -19,6 +22,25 @@ use std::sync::Arc;
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Bag<T>(pub T);
 
+/// `Positions` is a wrapper for `HashMap` and `BTreeMap` that records where each feature occurred, as an index of the token groups produced by the featurizer (starting at `0`). The values can be anything that `Accumulates<u32>`, such as `Vec<u32>` or `BTreeSet<u32>`.
+///
+/// It's a positional inverted list, made in the same pass as the features themselves.
+/// ```
+/// use creature_feature::convert::Positions;
+/// use creature_feature::ftzrs::{for_each, whole};
+/// use creature_feature::traits::Ftzr;
+/// use std::collections::HashMap;
+///
+/// let sentence = "one fish two fish red fish".split_ascii_whitespace();
+///
+/// let index: Positions<HashMap<&str, Vec<u32>>> = for_each(whole()).featurize(sentence);
+/// assert_eq!(index["fish"], vec![1, 3, 5]);
+/// assert_eq!(index["red"], vec![4]);
+/// ```
+#[derive(Hash, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Positions<T>(pub T);
+
 /// `Merged` is a wrapper to mark a feature as being produced by one or more different types.
 ///
 /// For example: `FeatureFrom<Result<A,B>>` or any featurizer produced by composing two featurizers with different outputs (like `bookends` or `featurizers!`)
@@ -31,6 +53,55 @@ pub struct Bag<T>(pub T);
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Merged<T>(pub T);
 
+/// `Joined` is an owned feature built by joining several words with the separator `SEP` (a space by default).
+///
+/// It's most useful with `word_gram`, or with `n_slice` over a `Vec<&str>` of words.
+/// ```
+///use creature_feature::convert::Joined;
+///use creature_feature::ftzrs::word_gram;
+///use creature_feature::traits::Ftzr;
+///
+///let feats: Vec<Joined> = word_gram::<2>().featurize("one fish, two fish");
+///assert_eq!(*feats[0], "one fish");
+///
+///let feats: Vec<Joined<'_'>> = word_gram::<2>().featurize("one fish, two fish");
+///assert_eq!(*feats[1], "fish_two");
+/// ```
+#[derive(Hash, Clone, PartialEq, Ord, PartialOrd, Eq, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Joined<const SEP: char = ' '>(pub String);
+
+impl<const SEP: char> Deref for Joined<SEP> {
+    type Target = String;
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<'a, 'b, const SEP: char> FeatureFrom<&'a [&'b str]> for Joined<SEP> {
+    fn from(words: &'a [&'b str]) -> Self {
+        let mut s = String::new();
+        for (idx, word) in words.iter().enumerate() {
+            if idx != 0 {
+                s.push(SEP);
+            }
+            s.push_str(word);
+        }
+        Joined(s)
+    }
+}
+
+impl<'a, const SEP: char, const N: usize> FeatureFrom<[&'a str; N]> for Joined<SEP> {
+    fn from(words: [&'a str; N]) -> Self {
+        FeatureFrom::from(&words[..])
+    }
+}
+
+try_via_from! {
+    ['a, 'b, const SEP: char] &'a [&'b str] => Joined<SEP>;
+    ['a, const SEP: char, const N: usize] [&'a str; N] => Joined<SEP>;
+}
+
 #[derive(Hash, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Debug, Default)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub(crate) struct Output<T>(pub T);
@@ -62,8 +133,72 @@ macro_rules! impl_deref {
 
 impl_deref!(Output<T>);
 impl_deref!(Bag<T>);
+impl_deref!(Positions<T>);
 impl_deref!(Merged<T>);
 impl_deref!(SelfOut<T>);
+impl_deref!(Lossy<T>);
+
+/// A text feature that never fails, even on bytes that aren't UTF-8. Invalid sequences are replaced with U+FFFD, exactly like `String::from_utf8_lossy`.
+///
+/// It works as both a `FeatureFrom` and a `TryFeatureFrom`, so it's the drop-in choice for `Ftzr::try_featurize` on untrusted bytes.
+/// ```
+///use creature_feature::convert::Lossy;
+///use creature_feature::ftzrs::n_gram;
+///use creature_feature::traits::Ftzr;
+///
+///let data: &[u8] = &[b'f', 0xff, b'o'];
+///let feats: Vec<Lossy<String>> = n_gram::<2>().featurize(data);
+///assert_eq!(*feats[0], "f\u{FFFD}");
+///assert_eq!(*feats[1], "\u{FFFD}o");
+/// ```
+#[derive(Hash, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Lossy<T>(pub T);
+
+impl<'a> FeatureFrom<&'a [u8]> for Lossy<Cow<'a, str>> {
+    fn from(token_group: &'a [u8]) -> Self {
+        Lossy(String::from_utf8_lossy(token_group))
+    }
+}
+
+impl<'a> FeatureFrom<&'a [u8]> for Lossy<String> {
+    fn from(token_group: &'a [u8]) -> Self {
+        Lossy(String::from_utf8_lossy(token_group).into_owned())
+    }
+}
+
+impl<const N: usize> FeatureFrom<[u8; N]> for Lossy<String> {
+    fn from(token_group: [u8; N]) -> Self {
+        FeatureFrom::from(&token_group[..])
+    }
+}
+
+impl<'a> FeatureFrom<Cow<'a, [u8]>> for Lossy<String> {
+    fn from(token_group: Cow<'a, [u8]>) -> Self {
+        FeatureFrom::from(&*token_group)
+    }
+}
+
+impl<'a> FeatureFrom<&'a str> for Lossy<String> {
+    fn from(token_group: &'a str) -> Self {
+        Lossy(token_group.to_owned())
+    }
+}
+
+impl FeatureFrom<String> for Lossy<String> {
+    fn from(token_group: String) -> Self {
+        Lossy(token_group)
+    }
+}
+
+impl<T, X> TryFeatureFrom<T> for Lossy<X>
+where
+    Lossy<X>: FeatureFrom<T>,
+{
+    fn try_from(token_group: T) -> Result<Self, FeatureError> {
+        Ok(FeatureFrom::from(token_group))
+    }
+}
 
 const UNICODE_ERR_MSG: &str = 
     "Featurizing as a &str is only supported for ascii. Please use &[u8] or Vec<char>. (This is at the bottom of the first page of the docs.)";
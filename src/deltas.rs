@@ -0,0 +1,307 @@
+use crate::accum_ftzr::{Ftzr, IterFtzr, MinLen};
+use crate::n_gram::NGram;
+use crate::numeric::Numeric;
+use crate::rolling_hash::Rolled;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::ops::Neg;
+
+/// How `Deltas` compares two consecutive values. Implemented by [`Difference`], [`Signs`], [`Clamped`] and [`Ratio`]
+pub trait DeltaKind<T> {
+    /// The type of one delta
+    type Delta: Copy;
+
+    /// The delta from `prev` to `next`
+    fn delta(&self, prev: T, next: T) -> Self::Delta;
+}
+
+/// `next - prev`, the default for `deltas`
+#[derive(Hash, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Difference;
+
+/// Only the direction of `next - prev`: `1`, `0` or `-1` (as `i8`). Created with `.signs()`
+#[derive(Hash, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Signs;
+
+/// `next - prev`, limited to `-limit..=limit`. Created with `.clamped(limit)`
+#[derive(Hash, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Clamped<D>(pub D);
+
+/// `next / prev` as an `f64`. Created with `ratios`
+#[derive(Hash, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Ratio;
+
+impl<T: Numeric> DeltaKind<T> for Difference {
+    type Delta = T::Diff;
+    #[inline]
+    fn delta(&self, prev: T, next: T) -> Self::Delta {
+        prev.diff(next)
+    }
+}
+
+impl<T: Numeric> DeltaKind<T> for Signs {
+    type Delta = i8;
+    #[inline]
+    fn delta(&self, prev: T, next: T) -> Self::Delta {
+        match prev.diff(next).partial_cmp(&Default::default()) {
+            Some(Ordering::Greater) => 1,
+            Some(Ordering::Less) => -1,
+            _ => 0,
+        }
+    }
+}
+
+impl<T, D> DeltaKind<T> for Clamped<D>
+where
+    T: Numeric<Diff = D>,
+    D: Copy + PartialOrd + Neg<Output = D>,
+{
+    type Delta = D;
+    #[inline]
+    fn delta(&self, prev: T, next: T) -> Self::Delta {
+        let d = prev.diff(next);
+        if d > self.0 {
+            self.0
+        } else if d < -self.0 {
+            -self.0
+        } else {
+            d
+        }
+    }
+}
+
+impl<T: Numeric> DeltaKind<T> for Ratio {
+    type Delta = f64;
+    #[inline]
+    fn delta(&self, prev: T, next: T) -> Self::Delta {
+        next.to_f64() / prev.to_f64()
+    }
+}
+
+/// A featurizer combinator that runs its inner featurizer on the differences between consecutive values. Created with `deltas(ftzr)` or `ratios(ftzr)`. The inner featurizer can be anything that featurizes a slice, and its windows are handed out as [`OwnedWindow`]s.
+#[derive(Hash, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Deltas<F, K = Difference> {
+    ftzr: F,
+    kind: K,
+}
+
+/// `deltas(n_gram::<N>())` yields `[D; N]` of the differences between `N + 1` consecutive values, for transposition-invariant patterns like melodic intervals. Other featurizers work too: `deltas(n_slice(n))` yields a `Vec<D>` per window. `Ftzr::featurize` computes the differences once per input, into one buffer. `IterFtzr::iterate_features` (only for `n_gram`) allocates nothing, since each window of differences is computed from the input directly.
+///
+/// Integers have `i128` differences, which are exact even between `u64::MAX` and `0`, floats keep their own type. Use `.signs()` for only the direction of each step, or `.clamped(limit)` to cap large jumps. (Compare to `ratios`)
+/// ```
+/// use creature_feature::ftzrs::{deltas, n_slice, trigram};
+/// use creature_feature::traits::Ftzr;
+///
+/// // the same tune in two keys
+/// let tune: &[u8] = &[60, 62, 64, 60, 67];
+/// let transposed: &[u8] = &[65, 67, 69, 65, 72];
+///
+/// let a: Vec<[i128; 3]> = deltas(trigram()).featurize(tune);
+/// let b: Vec<[i128; 3]> = deltas(trigram()).featurize(transposed);
+/// assert_eq!(a, vec![[2, 2, -4], [2, -4, 7]]);
+/// assert_eq!(a, b);
+///
+/// let contour: Vec<[i8; 3]> = deltas(trigram()).signs().featurize(tune);
+/// assert_eq!(contour, vec![[1, 1, -1], [1, -1, 1]]);
+///
+/// let capped: Vec<[i128; 3]> = deltas(trigram()).clamped(3).featurize(tune);
+/// assert_eq!(capped, vec![[2, 2, -3], [2, -3, 3]]);
+///
+/// let steps: Vec<Vec<i128>> = deltas(n_slice(2)).featurize(tune);
+/// assert_eq!(steps, vec![vec![2, 2], vec![2, -4], vec![-4, 7]]);
+/// ```
+pub fn deltas<F>(ftzr: F) -> Deltas<F> {
+    Deltas {
+        ftzr,
+        kind: Difference,
+    }
+}
+
+/// `ratios(n_gram::<N>())` yields `[f64; N]` of `next / prev` for `N + 1` consecutive values, for patterns that don't depend on scale, like relative price changes. (Compare to `deltas`)
+/// ```
+/// use creature_feature::ftzrs::{bigram, ratios};
+/// use creature_feature::traits::Ftzr;
+///
+/// let prices = [10.0, 20.0, 10.0];
+/// let feats: Vec<[f64; 2]> = ratios(bigram()).featurize(&prices);
+/// assert_eq!(feats, vec![[2.0, 0.5]]);
+/// ```
+pub fn ratios<F>(ftzr: F) -> Deltas<F, Ratio> {
+    Deltas { ftzr, kind: Ratio }
+}
+
+impl<F> Deltas<F> {
+    /// Only keep the direction of each difference, as `1`, `0` or `-1`
+    pub fn signs(self) -> Deltas<F, Signs> {
+        Deltas {
+            ftzr: self.ftzr,
+            kind: Signs,
+        }
+    }
+
+    /// Limit each difference to `-limit..=limit`. `limit` is an `i128` for integer input, or the same float type as the input.
+    pub fn clamped<D>(self, limit: D) -> Deltas<F, Clamped<D>> {
+        Deltas {
+            ftzr: self.ftzr,
+            kind: Clamped(limit),
+        }
+    }
+}
+
+/// The associated iterator for `<Deltas<NGram<N>, K> as IterFtzr<T>>::Iter`
+#[derive(Hash, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Debug)]
+pub struct DeltasIter<'a, T, K, const N: usize> {
+    kind: K,
+    idx: usize,
+    data: &'a [T],
+}
+
+impl<'a, T: Copy, K: DeltaKind<T>, const N: usize> Iterator for DeltasIter<'a, T, K, N> {
+    type Item = [K::Delta; N];
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let window = self.data.get(self.idx..self.idx + N + 1)?;
+        self.idx += 1;
+        Some(std::array::from_fn(|i| {
+            self.kind.delta(window[i], window[i + 1])
+        }))
+    }
+}
+
+impl<'a, T: Copy, K: DeltaKind<T> + Clone, const N: usize> IterFtzr<&'a [T]>
+    for Deltas<NGram<N>, K>
+{
+    type TokenGroup = [K::Delta; N];
+    type Iter = DeltasIter<'a, T, K, N>;
+
+    fn iterate_features(&self, origin: &'a [T]) -> Self::Iter {
+        DeltasIter {
+            kind: self.kind.clone(),
+            idx: 0,
+            data: origin,
+        }
+    }
+}
+
+impl<'a, T: Copy, K: DeltaKind<T> + Clone, const N: usize> IterFtzr<&'a Vec<T>>
+    for Deltas<NGram<N>, K>
+{
+    type TokenGroup = [K::Delta; N];
+    type Iter = DeltasIter<'a, T, K, N>;
+
+    fn iterate_features(&self, origin: &'a Vec<T>) -> Self::Iter {
+        self.iterate_features(origin.as_slice())
+    }
+}
+
+impl<'a, T: Copy, K: DeltaKind<T> + Clone, const N: usize, const M: usize> IterFtzr<&'a [T; M]>
+    for Deltas<NGram<N>, K>
+{
+    type TokenGroup = [K::Delta; N];
+    type Iter = DeltasIter<'a, T, K, N>;
+
+    fn iterate_features(&self, origin: &'a [T; M]) -> Self::Iter {
+        self.iterate_features(&origin[..])
+    }
+}
+
+impl<'a, T, F, K> Ftzr<&'a Vec<T>> for Deltas<F, K>
+where
+    Self: Ftzr<&'a [T]>,
+{
+    type TokenGroup = <Self as Ftzr<&'a [T]>>::TokenGroup;
+    fn push_tokens<Push>(&self, origin: &'a Vec<T>, push: &mut Push)
+    where
+        Push: FnMut(Self::TokenGroup),
+    {
+        self.push_tokens(origin.as_slice(), push)
+    }
+}
+
+impl<'a, T, F, K, const M: usize> Ftzr<&'a [T; M]> for Deltas<F, K>
+where
+    Self: Ftzr<&'a [T]>,
+{
+    type TokenGroup = <Self as Ftzr<&'a [T]>>::TokenGroup;
+    fn push_tokens<Push>(&self, origin: &'a [T; M], push: &mut Push)
+    where
+        Push: FnMut(Self::TokenGroup),
+    {
+        self.push_tokens(&origin[..], push)
+    }
+}
+
+/// A token group of an inner featurizer that `Deltas` can hand out: `[D; N]` from `n_gram`, `Rolled` from `rolling_hash` and `Vec<D>` (as they are), or `&[D]` from `n_slice` and the like (copied into a `Vec<D>`, since the differences only live as long as one call)
+pub trait OwnedWindow {
+    /// The window, without any borrow of the differences
+    type Owned;
+
+    /// Copy the window out of the differences, if needed
+    fn to_owned_window(self) -> Self::Owned;
+}
+
+impl<D, const N: usize> OwnedWindow for [D; N] {
+    type Owned = [D; N];
+    #[inline]
+    fn to_owned_window(self) -> Self::Owned {
+        self
+    }
+}
+
+impl<D> OwnedWindow for Vec<D> {
+    type Owned = Vec<D>;
+    #[inline]
+    fn to_owned_window(self) -> Self::Owned {
+        self
+    }
+}
+
+impl OwnedWindow for Rolled {
+    type Owned = Rolled;
+    #[inline]
+    fn to_owned_window(self) -> Self::Owned {
+        self
+    }
+}
+
+impl<D: Clone> OwnedWindow for &[D] {
+    type Owned = Vec<D>;
+    #[inline]
+    fn to_owned_window(self) -> Self::Owned {
+        self.to_vec()
+    }
+}
+
+impl<'a, T, K, F, O> Ftzr<&'a [T]> for Deltas<F, K>
+where
+    T: Copy,
+    K: DeltaKind<T>,
+    F: for<'b> Ftzr<&'b [K::Delta]>,
+    for<'b> <F as Ftzr<&'b [K::Delta]>>::TokenGroup: OwnedWindow<Owned = O>,
+{
+    type TokenGroup = O;
+    fn push_tokens<Push>(&self, origin: &'a [T], push: &mut Push)
+    where
+        Push: FnMut(Self::TokenGroup),
+    {
+        let diffs: Vec<K::Delta> = origin
+            .windows(2)
+            .map(|w| self.kind.delta(w[0], w[1]))
+            .collect();
+        self.ftzr
+            .push_tokens(&diffs[..], &mut |t| push(t.to_owned_window()))
+    }
+}
+
+/// One more number than `ftzr` needs differences
+impl<F: MinLen, K> MinLen for Deltas<F, K> {
+    fn min_len(&self) -> usize {
+        self.ftzr.min_len() + 1
+    }
+}
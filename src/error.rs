@@ -0,0 +1,44 @@
+use std::fmt;
+
+/// Why an input couldn't be featurized by one of the checked methods, like `Ftzr::try_featurize` or `TryFeatureFrom::try_from`
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[non_exhaustive]
+pub enum FeatureError {
+    /// The input is shorter than the featurizer needs. (See `traits::MinLen`)
+    InputTooShort {
+        /// The length of the input
+        len: usize,
+        /// The shortest input the featurizer fully handles
+        min: usize,
+    },
+    /// A token group isn't valid UTF-8, so it can't be a `&str` or `String`. (See `convert::Lossy` for an alternative that never fails)
+    InvalidUtf8 {
+        /// The byte offset of the first invalid byte, within the token group
+        offset: usize,
+    },
+    /// A fixed-capacity accumulator, like `heapless::BinaryHeap`, is full
+    CapacityExceeded {
+        /// The most features the accumulator can hold
+        capacity: usize,
+    },
+}
+
+impl fmt::Display for FeatureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FeatureError::InputTooShort { len, min } => write!(
+                f,
+                "input of length {} is shorter than the minimum of {}",
+                len, min
+            ),
+            FeatureError::InvalidUtf8 { offset } => {
+                write!(f, "invalid UTF-8 at byte {} of a token group", offset)
+            }
+            FeatureError::CapacityExceeded { capacity } => {
+                write!(f, "the accumulator is full at a capacity of {}", capacity)
+            }
+        }
+    }
+}
+
+impl std::error::Error for FeatureError {}
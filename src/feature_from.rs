@@ -1,4 +1,7 @@
 use crate::convert::Output;
+use crate::error::FeatureError;
+use std::borrow::Cow;
+use std::ops::Range;
 use std::str::from_utf8;
 
 ///The trait for features that can be made from another type. It's largely self-explanatory and very similar to [`std::convert::From`], only more accommodating. It's used in `Ftzr::featurize` and `Ftzr::push_tokens_from`.
@@ -39,7 +42,19 @@ impl<T: Clone, const N: usize> FeatureFrom<[T; N]> for Vec<T> {
     }
 }
 
-const UTF_ERR_MSG: &'static str = &"Featurizing into &str is only supported for ASCII, not unicode. Please first convert your input data to a Vec<char>. This is noted on the first page of the docs, at the bottom.";
+impl<'a, T: Clone> FeatureFrom<&'a [T]> for Vec<T> {
+    fn from(token_group: &'a [T]) -> Self {
+        token_group.to_vec()
+    }
+}
+
+impl<T> FeatureFrom<Vec<T>> for Vec<T> {
+    fn from(token_group: Vec<T>) -> Self {
+        token_group
+    }
+}
+
+pub(crate) const UTF_ERR_MSG: &'static str = &"Featurizing into &str is only supported for ASCII, not unicode. Please use `char_slice`/`char_gram`, or first convert your input data to a Vec<char>. This is noted on the first page of the docs, at the bottom.";
 
 impl<const N: usize> FeatureFrom<[u8; N]> for String {
     fn from(token_group: [u8; N]) -> Self {
@@ -62,6 +77,25 @@ impl<'a> FeatureFrom<&'a [char]> for String {
     }
 }
 
+macro_rules! impl_identity {
+    ($($t:ty),*) => {
+        $(
+            impl FeatureFrom<$t> for $t {
+                fn from(token_group: $t) -> Self {
+                    token_group
+                }
+            }
+            impl TryFeatureFrom<$t> for $t {
+                fn try_from(token_group: $t) -> Result<Self, FeatureError> {
+                    Ok(token_group)
+                }
+            }
+        )*
+    };
+}
+
+impl_identity!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, char, bool);
+
 impl<'a> FeatureFrom<&'a [u8]> for &'a str {
     fn from(token_group: &'a [u8]) -> Self {
         from_utf8(token_group).expect(UTF_ERR_MSG)
@@ -85,6 +119,30 @@ impl<A: Copy, B: FeatureFrom<A>> FeatureFrom<[A; 1]> for Output<B> {
     }
 }
 
+impl<'a> FeatureFrom<Cow<'a, str>> for Cow<'a, str> {
+    fn from(token_group: Cow<'a, str>) -> Self {
+        token_group
+    }
+}
+
+impl<'a> FeatureFrom<Cow<'a, str>> for String {
+    fn from(token_group: Cow<'a, str>) -> Self {
+        token_group.into_owned()
+    }
+}
+
+impl<'a> FeatureFrom<Cow<'a, [u8]>> for Cow<'a, [u8]> {
+    fn from(token_group: Cow<'a, [u8]>) -> Self {
+        token_group
+    }
+}
+
+impl<'a> FeatureFrom<Cow<'a, [u8]>> for Vec<u8> {
+    fn from(token_group: Cow<'a, [u8]>) -> Self {
+        token_group.into_owned()
+    }
+}
+
 impl FeatureFrom<String> for String {
     fn from(token_group: String) -> Self {
         token_group
@@ -108,3 +166,130 @@ where
         })
     }
 }
+
+impl<A1, A2: FeatureFrom<A1>, B1, B2: FeatureFrom<B1>> FeatureFrom<(A1, B1)> for (A2, B2) {
+    fn from(token_group: (A1, B1)) -> Self {
+        (
+            FeatureFrom::from(token_group.0),
+            FeatureFrom::from(token_group.1),
+        )
+    }
+}
+
+impl FeatureFrom<Range<usize>> for Range<usize> {
+    fn from(span: Range<usize>) -> Self {
+        span
+    }
+}
+
+///The fallible version of [`FeatureFrom`], used by `Ftzr::try_featurize`. Where `FeatureFrom` would panic, like making a `&str` from bytes that aren't UTF-8, `TryFeatureFrom` returns a [`FeatureError`] instead.
+/// ```
+///use creature_feature::traits::TryFeatureFrom;
+///use creature_feature::FeatureError;
+///
+///let data: &[u8] = &[98, 97, 99, 111, 110];
+///let out: Result<String, _> = TryFeatureFrom::try_from(data);
+///assert_eq!(out, Ok("bacon".to_owned()));
+///
+///let data: &[u8] = &[98, 97, 0xc3, 111, 110];
+///let out: Result<&str, _> = TryFeatureFrom::try_from(data);
+///assert_eq!(out, Err(FeatureError::InvalidUtf8 { offset: 2 }));
+/// ```
+pub trait TryFeatureFrom<T>: Sized {
+    #[allow(missing_docs)]
+    fn try_from(t: T) -> Result<Self, FeatureError>;
+}
+
+pub(crate) fn try_from_utf8(bytes: &[u8]) -> Result<&str, FeatureError> {
+    from_utf8(bytes).map_err(|e| FeatureError::InvalidUtf8 {
+        offset: e.valid_up_to(),
+    })
+}
+
+/// For conversions that can't fail, `TryFeatureFrom` is just `FeatureFrom`
+macro_rules! try_via_from {
+    ($([$($g:tt)*] $from:ty => $to:ty;)*) => {
+        $(
+            impl<$($g)*> $crate::feature_from::TryFeatureFrom<$from> for $to {
+                fn try_from(token_group: $from) -> Result<Self, $crate::error::FeatureError> {
+                    Ok($crate::feature_from::FeatureFrom::from(token_group))
+                }
+            }
+        )*
+    };
+}
+
+pub(crate) use try_via_from;
+
+try_via_from! {
+    ['a, T] &'a [T] => &'a [T];
+    [T, const N: usize] [T; N] => [T; N];
+    [T: Clone, const N: usize] [T; N] => Vec<T>;
+    ['a, T: Clone] &'a [T] => Vec<T>;
+    [T] Vec<T> => Vec<T>;
+    [const N: usize] [char; N] => String;
+    ['a] &'a [char] => String;
+    ['a] &'a str => &'a str;
+    ['a] &'a str => String;
+    [] String => String;
+    ['a] Cow<'a, str> => Cow<'a, str>;
+    ['a] Cow<'a, str> => String;
+    ['a] Cow<'a, [u8]> => Cow<'a, [u8]>;
+    ['a] Cow<'a, [u8]> => Vec<u8>;
+    [] Range<usize> => Range<usize>;
+}
+
+impl<'a> TryFeatureFrom<&'a [u8]> for &'a str {
+    fn try_from(token_group: &'a [u8]) -> Result<Self, FeatureError> {
+        try_from_utf8(token_group)
+    }
+}
+
+impl<'a> TryFeatureFrom<&'a [u8]> for String {
+    fn try_from(token_group: &'a [u8]) -> Result<Self, FeatureError> {
+        try_from_utf8(token_group).map(str::to_owned)
+    }
+}
+
+impl<const N: usize> TryFeatureFrom<[u8; N]> for String {
+    fn try_from(token_group: [u8; N]) -> Result<Self, FeatureError> {
+        try_from_utf8(&token_group).map(str::to_owned)
+    }
+}
+
+impl<'a> TryFeatureFrom<Cow<'a, [u8]>> for String {
+    fn try_from(token_group: Cow<'a, [u8]>) -> Result<Self, FeatureError> {
+        try_from_utf8(&token_group).map(str::to_owned)
+    }
+}
+
+impl<A: Copy, B: TryFeatureFrom<A>> TryFeatureFrom<[A; 1]> for Output<B> {
+    fn try_from(token_group: [A; 1]) -> Result<Self, FeatureError> {
+        TryFeatureFrom::try_from(token_group[0]).map(Output)
+    }
+}
+
+impl<A, B, C> TryFeatureFrom<Result<A, B>> for Output<C>
+where
+    C: TryFeatureFrom<A> + TryFeatureFrom<B>,
+{
+    fn try_from(r: Result<A, B>) -> Result<Self, FeatureError> {
+        Ok(Output(match r {
+            Err(x) => TryFeatureFrom::try_from(x)?,
+            Ok(x) => TryFeatureFrom::try_from(x)?,
+        }))
+    }
+}
+
+impl<A1, A2, B1, B2> TryFeatureFrom<(A1, B1)> for (A2, B2)
+where
+    A2: TryFeatureFrom<A1>,
+    B2: TryFeatureFrom<B1>,
+{
+    fn try_from(token_group: (A1, B1)) -> Result<Self, FeatureError> {
+        Ok((
+            TryFeatureFrom::try_from(token_group.0)?,
+            TryFeatureFrom::try_from(token_group.1)?,
+        ))
+    }
+}
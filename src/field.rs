@@ -0,0 +1,116 @@
+use crate::accum_ftzr::{Ftzr, IterFtzr, MinLen};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A featurizer combinator that featurizes one field of a record (or tuple), and tags each token group with the name of that field. Created with `field(name, get, ftzr)`
+#[derive(Hash, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Field<G, F> {
+    name: &'static str,
+    get: G,
+    ftzr: F,
+}
+
+/// `field(name, get, ftzr)` runs `ftzr` on `get(input)`, and produces `(name, token_group)`. This lets numeric columns and text be featurized together by `featurizers!`: each column is its own `field`, and the name keeps equal token groups of different columns (like `Bin(1)` of a price and `Bin(1)` of an age) apart when hashed.
+/// ```
+/// use creature_feature::convert::Merged;
+/// use creature_feature::featurizers;
+/// use creature_feature::ftzrs::misc::Bin;
+/// use creature_feature::ftzrs::{field, log_bins, trigram, uniform_bins};
+/// use creature_feature::traits::Ftzr;
+/// use creature_feature::HashedAs;
+/// use std::collections::HashSet;
+///
+/// struct Listing {
+///     title: String,
+///     price: f64,
+///     age: u32,
+/// }
+///
+/// // a fn, since a closure can't return a borrow of its argument
+/// fn title(l: &Listing) -> &str {
+///     &l.title
+/// }
+///
+/// let ftzr = featurizers!(
+///     field("title", title, trigram()),
+///     field("price", |l: &Listing| l.price, log_bins(1.0, 10_000.0, 8)),
+///     field("age", |l: &Listing| l.age, uniform_bins(0.0, 20.0, 4)),
+/// );
+///
+/// let listing = Listing { title: "red bike".to_string(), price: 120.0, age: 6 };
+/// let feats: HashSet<Merged<HashedAs<u64>>> = ftzr.featurize(&listing);
+/// assert_eq!(feats.len(), 8);
+///
+/// // also works on tuples
+/// let price = field("price", |(p, _): (f64, &str)| p, log_bins(1.0, 10_000.0, 8));
+/// let feats: Vec<(&str, Bin)> = price.featurize((120.0, "red bike"));
+/// assert_eq!(feats, vec![("price", Bin(4))]);
+/// ```
+pub fn field<G, F>(name: &'static str, get: G, ftzr: F) -> Field<G, F> {
+    Field { name, get, ftzr }
+}
+
+impl<G, F> Field<G, F> {
+    /// The name each token group is tagged with
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+/// The associated iterator for `<Field<G, F> as IterFtzr<T>>::Iter`
+#[derive(Hash, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Debug)]
+pub struct FieldIter<I> {
+    name: &'static str,
+    iter: I,
+}
+
+impl<I: Iterator> Iterator for FieldIter<I> {
+    type Item = (&'static str, I::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|t| (self.name, t))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<Origin, X, G, F> Ftzr<Origin> for Field<G, F>
+where
+    G: Fn(Origin) -> X,
+    F: Ftzr<X>,
+{
+    type TokenGroup = (&'static str, F::TokenGroup);
+    fn push_tokens<Push>(&self, origin: Origin, push: &mut Push)
+    where
+        Push: FnMut(Self::TokenGroup),
+    {
+        self.ftzr
+            .push_tokens((self.get)(origin), &mut |t| push((self.name, t)))
+    }
+}
+
+/// The length of a record says nothing about the length of its field, so the field is clamped on its own, like each item of `for_each`
+impl<G, F> MinLen for Field<G, F> {
+    fn min_len(&self) -> usize {
+        0
+    }
+}
+
+impl<Origin, X, G, F> IterFtzr<Origin> for Field<G, F>
+where
+    G: Fn(Origin) -> X,
+    F: IterFtzr<X>,
+{
+    type TokenGroup = (&'static str, F::TokenGroup);
+    type Iter = FieldIter<F::Iter>;
+
+    fn iterate_features(&self, origin: Origin) -> Self::Iter {
+        FieldIter {
+            name: self.name,
+            iter: self.ftzr.iterate_features((self.get)(origin)),
+        }
+    }
+}
@@ -1,13 +1,15 @@
-use crate::accum_ftzr::{Ftzr, IterFtzr, LinearFixed};
+use crate::accum_ftzr::{Ftzr, IterFtzr, LinearFixed, MinLen, SpanFtzr};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 use std::marker::PhantomData;
+use std::ops::ControlFlow;
 
 /// A featurizer combinator that will featurize each item of an input iterator. Created with `for_each(ftzr)`.
 #[derive(Hash, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Debug)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ForEach<F, Meta>(F, PhantomData<Meta>);
 
+/// The associated iterator for `ForEach`, which lazily featurizes one item of the input at a time
 #[derive(Hash, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Debug)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ForEachIter<F, Sentence, Word, SentIter, FWordIter> {
@@ -25,15 +27,30 @@ where
 {
     type Item = F::TokenGroup;
     fn next(&mut self) -> Option<Self::Item> {
-        /*
-        TODO
-        let mut ret: Option<Self::Item> = None;
-        match self.current {
-            Some(letters) => match letters.next() {
-                None =>
+        loop {
+            if let Some(token_group) = self.current.as_mut().and_then(Iterator::next) {
+                return Some(token_group);
             }
-        } */
-        unimplemented!("ForEach does not yet impl IterFtzr... Feel free to file a PR")
+            match self.sentence.next() {
+                Some(word) => self.current = Some(self.ftzr.iterate_features(word)),
+                None => {
+                    self.current = None;
+                    return None;
+                }
+            }
+        }
+    }
+
+    /// Only the current item is known for sure, unless it's the last one
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self
+            .current
+            .as_ref()
+            .map_or((0, Some(0)), Iterator::size_hint);
+        match self.sentence.size_hint() {
+            (_, Some(0)) => (lower, upper),
+            _ => (lower, None),
+        }
     }
 }
 
@@ -43,15 +60,27 @@ impl<F: LinearFixed, T> LinearFixed for ForEach<F, T> {
     }
 }
 
+/// The input is a sequence of items, which can't be too short, and each item is clamped on its own
+impl<F, T> MinLen for ForEach<F, T> {
+    fn min_len(&self) -> usize {
+        0
+    }
+}
+
 impl<F, Sentence, Word> IterFtzr<Sentence> for ForEach<F, (Sentence, Word)>
 where
     Sentence: IntoIterator<Item = Word>,
-    F: IterFtzr<Word>,
+    F: IterFtzr<Word> + Clone,
 {
     type TokenGroup = F::TokenGroup;
     type Iter = ForEachIter<F, Sentence, Word, Sentence::IntoIter, F::Iter>;
     fn iterate_features(&self, origin: Sentence) -> Self::Iter {
-        unimplemented!("ForEach does not yet impl IterFtzr... Feel free to file a PR")
+        ForEachIter {
+            ftzr: self.0.clone(),
+            sentence: origin.into_iter(),
+            current: None,
+            tags: PhantomData,
+        }
     }
 }
 
@@ -69,17 +98,55 @@ where
             self.0.push_tokens(word, push);
         }
     }
+
+    fn push_tokens_while<Push>(&self, origin: Sentence, push: &mut Push) -> ControlFlow<()>
+    where
+        Push: FnMut(Self::TokenGroup) -> ControlFlow<()>,
+    {
+        origin
+            .into_iter()
+            .try_for_each(|word| self.0.push_tokens_while(word, push))
+    }
+}
+/// Spans are relative to the whole sentence rather than each word, so each word comes with its own offset into the sentence, as `(offset, word)`. (See `word_indices`, `str::char_indices` and `str::match_indices`)
+impl<F, Sentence, Word> SpanFtzr<Sentence> for ForEach<F, (Sentence, (usize, Word))>
+where
+    Sentence: IntoIterator<Item = (usize, Word)>,
+    F: SpanFtzr<Word>,
+{
+    type Span = F::Span;
+    type TokenGroup = F::TokenGroup;
+    fn push_spans<Push>(&self, origin: Sentence, offset: usize, push: &mut Push)
+    where
+        Push: FnMut(Self::Span, Self::TokenGroup),
+    {
+        for (start, word) in origin.into_iter() {
+            self.0.push_spans(word, offset + start, push);
+        }
+    }
 }
+
 /// A featurizer combinator that will featurize each item of an input iterator. Note that it, logically, is nestable: `for_each(for_each(ftzr))` will featurize an iterable of iterables.
+///
+/// When the inner featurizer is an `IterFtzr`, so is `for_each(ftzr)`, and each item is only featurized once the previous one is used up.
 /// ```        
 /// use creature_feature::convert::Bag;
-/// use creature_feature::ftzrs::{whole, for_each};
+/// use creature_feature::ftzrs::{bislice, whole, for_each};
+/// use creature_feature::traits::{Ftzr, IterFtzr};
 /// use std::collections::BTreeMap;
 ///
 /// let sentence = "one fish two fish red fish blue fish"
 ///                 .split_ascii_whitespace();
 ///
-/// let bag_of_words: Bag<BTreeMap<String, i32>> = for_each(whole()).featurize(sentence);
+/// let bag_of_words: Bag<BTreeMap<String, i32>> = for_each(whole()).featurize(sentence.clone());
+///
+/// let mut lazy = for_each(bislice()).iterate_features(sentence);
+/// assert_eq!(lazy.next(), Some(&b"on"[..]));
+/// assert_eq!(lazy.nth(1), Some(&b"fi"[..]));
+///
+/// let doc = vec![vec!["one", "fish"], vec!["two", "fish"]];
+/// let words: Vec<&[u8]> = for_each(for_each(whole())).iterate_features(doc).collect();
+/// assert_eq!(words, vec!["one".as_bytes(), b"fish", b"two", b"fish"]);
 /// ```
 pub fn for_each<F, Sentence, Word>(f: F) -> ForEach<F, (Sentence, Word)> {
     ForEach(f, PhantomData::default())
@@ -1,5 +1,6 @@
-use crate::accum_ftzr::{Ftzr, IterFtzr, LinearFixed};
-use crate::feature_from::FeatureFrom;
+use crate::accum_ftzr::{Ftzr, IterFtzr, LinearFixed, MinLen, SpanFtzr};
+use crate::error::FeatureError;
+use crate::feature_from::{FeatureFrom, TryFeatureFrom};
 use crate::internal::impl_ftrzs_2;
 use crate::multiftzr::EitherGroup;
 use crate::skip_schema::SkipSchema;
@@ -7,6 +8,7 @@ use crate::skip_schema::SkipSchema;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 use std::marker::PhantomData;
+use std::ops::{ControlFlow, Range};
 
 ///The associated iterator of `<GapGram<A,B> as IterFtzr<T>>::Iter`
 #[derive(Hash, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Debug)]
@@ -32,7 +34,8 @@ impl<'a, A, B, T, U1: 'a, U2: 'a> GapGramIter<A, B, &'a [T], U1, U2> {
         bf: &BF,
     ) -> Self {
         let a = af.iterate_features(origin);
-        let b = bf.iterate_features(&origin[af.chunk_size() + gap..]);
+        // clamped, so that a short input produces no pairs
+        let b = bf.iterate_features(origin.get(af.chunk_size() + gap..).unwrap_or(&[]));
         GapGramIter {
             a,
             b,
@@ -60,7 +63,7 @@ where
             match (a, b) {
                 (Some(aa), Some(bb)) => {
                     self.idx += 1;
-                    return Some(GapPair(aa, bb, self.gap as u16));
+                    return Some(GapPair(aa, bb, self.gap));
                 }
                 _ => return None,
             }
@@ -81,7 +84,24 @@ pub struct GapGram<A, B> {
 /// The product of two types. Used as `<GapGram<A,B> as Ftzr<T>>::TokenGroup`
 #[derive(Hash, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Debug)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-pub struct GapPair<A, B>(pub(crate) A, pub(crate) B, pub(crate) u16); //TODO make names fields
+pub struct GapPair<A, B>(pub(crate) A, pub(crate) B, pub(crate) usize); //TODO make names fields
+
+impl<A, B> GapPair<A, B> {
+    /// The group of tokens before the gap
+    pub fn first(&self) -> &A {
+        &self.0
+    }
+
+    /// The group of tokens after the gap
+    pub fn second(&self) -> &B {
+        &self.1
+    }
+
+    /// The number of tokens skipped between the two groups
+    pub fn gap(&self) -> usize {
+        self.2
+    }
+}
 
 impl<A1, A2: From<A1>, B1, B2: From<B1>> From<GapPair<A1, B1>> for (A2, B2) {
     fn from(sp: GapPair<A1, B1>) -> Self {
@@ -124,12 +144,82 @@ impl<A1, A2: FeatureFrom<A1>, B1, B2: FeatureFrom<B1>> FeatureFrom<GapPair<A1, B
     }
 }
 
+impl<A1, A2, B1, B2> TryFeatureFrom<GapPair<A1, B1>> for (A2, B2)
+where
+    A2: TryFeatureFrom<A1>,
+    B2: TryFeatureFrom<B1>,
+{
+    fn try_from(sp: GapPair<A1, B1>) -> Result<Self, FeatureError> {
+        Ok((
+            TryFeatureFrom::try_from(sp.0)?,
+            TryFeatureFrom::try_from(sp.1)?,
+        ))
+    }
+}
+
+impl<A1, A2: FeatureFrom<A1>, B1, B2: FeatureFrom<B1>> FeatureFrom<GapPair<A1, B1>>
+    for (A2, usize, B2)
+{
+    fn from(sp: GapPair<A1, B1>) -> Self {
+        (FeatureFrom::from(sp.0), sp.2, FeatureFrom::from(sp.1))
+    }
+}
+
+impl<A1, A2, B1, B2> TryFeatureFrom<GapPair<A1, B1>> for (A2, usize, B2)
+where
+    A2: TryFeatureFrom<A1>,
+    B2: TryFeatureFrom<B1>,
+{
+    fn try_from(sp: GapPair<A1, B1>) -> Result<Self, FeatureError> {
+        Ok((
+            TryFeatureFrom::try_from(sp.0)?,
+            sp.2,
+            TryFeatureFrom::try_from(sp.1)?,
+        ))
+    }
+}
+
+impl<A1, A2, B1, B2, C1, C2, D1, D2> TryFeatureFrom<GapPair<GapPair<A1, B1>, GapPair<C1, D1>>>
+    for (A2, B2, C2, D2)
+where
+    A2: TryFeatureFrom<A1>,
+    B2: TryFeatureFrom<B1>,
+    C2: TryFeatureFrom<C1>,
+    D2: TryFeatureFrom<D1>,
+{
+    fn try_from(t: GapPair<GapPair<A1, B1>, GapPair<C1, D1>>) -> Result<Self, FeatureError> {
+        Ok((
+            TryFeatureFrom::try_from(t.0 .0)?,
+            TryFeatureFrom::try_from(t.0 .1)?,
+            TryFeatureFrom::try_from(t.1 .0)?,
+            TryFeatureFrom::try_from(t.1 .1)?,
+        ))
+    }
+}
+
+impl<A, B: TryFeatureFrom<A>> TryFeatureFrom<GapPair<GapPair<A, A>, GapPair<A, A>>> for [B; 4] {
+    fn try_from(t: GapPair<GapPair<A, A>, GapPair<A, A>>) -> Result<Self, FeatureError> {
+        Ok([
+            TryFeatureFrom::try_from(t.0 .0)?,
+            TryFeatureFrom::try_from(t.0 .1)?,
+            TryFeatureFrom::try_from(t.1 .0)?,
+            TryFeatureFrom::try_from(t.1 .1)?,
+        ])
+    }
+}
+
 impl<A: LinearFixed, B: LinearFixed> LinearFixed for GapGram<A, B> {
     fn chunk_size(&self) -> usize {
         self.a.chunk_size() + self.gap + self.b.chunk_size()
     }
 }
 
+impl<A: LinearFixed, B: LinearFixed> MinLen for GapGram<A, B> {
+    fn min_len(&self) -> usize {
+        self.chunk_size()
+    }
+}
+
 impl<'a, T: 'a, A, B, U1: 'a, U2: 'a> IterFtzr<&'a [T]> for GapGram<A, B>
 where
     A: LinearFixed + IterFtzr<&'a [T], TokenGroup = U1>,
@@ -259,6 +349,13 @@ where
             push(t)
         }
     }
+
+    fn push_tokens_while<Push>(&self, origin: Origin, push: &mut Push) -> ControlFlow<()>
+    where
+        Push: FnMut(Self::TokenGroup) -> ControlFlow<()>,
+    {
+        self.iterate_features(origin).try_for_each(push)
+    }
 }
 /*
 impl<Origin: Copy, A: Ftzr<Origin>, B: Ftzr<Origin>> Ftzr<Origin> for GapGram<A, B> {
@@ -271,3 +368,23 @@ impl<Origin: Copy, A: Ftzr<Origin>, B: Ftzr<Origin>> Ftzr<Origin> for GapGram<A,
     }
 }
 */
+
+impl<Origin, A: LinearFixed, B: LinearFixed> SpanFtzr<Origin> for GapGram<A, B>
+where
+    Self: Ftzr<Origin>,
+{
+    type Span = (Range<usize>, Range<usize>);
+    type TokenGroup = <Self as Ftzr<Origin>>::TokenGroup;
+    fn push_spans<Push>(&self, origin: Origin, offset: usize, push: &mut Push)
+    where
+        Push: FnMut(Self::Span, Self::TokenGroup),
+    {
+        let (n_a, n_b) = (self.a.chunk_size(), self.b.chunk_size());
+        let mut idx = offset;
+        self.push_tokens(origin, &mut |t| {
+            let b = idx + n_a + self.gap;
+            push((idx..idx + n_a, b..b + n_b), t);
+            idx += 1;
+        })
+    }
+}
@@ -0,0 +1,140 @@
+use crate::accum_ftzr::{Ftzr, IterFtzr, MinLen};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::ops::ControlFlow;
+use unicode_segmentation::{GraphemeIndices, UnicodeSegmentation};
+
+/// A fixed-length n-gram over the extended grapheme clusters (UAX #29) of a `&str`. Created with `grapheme_slice(n)` or `graphemes()`
+#[derive(Hash, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GraphemeSlice {
+    n: usize,
+}
+
+/// n-grams over user-perceived characters, produces borrowed `&str` windows of exactly `n` extended grapheme clusters. Emoji ZWJ sequences, flags and combining accents are never split. (Compare to `char_slice`)
+///
+/// Requires the `graphemes` feature.
+/// ```
+/// use creature_feature::ftzrs::grapheme_slice;
+/// use creature_feature::traits::Ftzr;
+///
+/// let feats: Vec<&str> = grapheme_slice(2).featurize("e\u{301}te\u{301}");
+/// assert_eq!(feats, vec!["e\u{301}t", "te\u{301}"]);
+/// ```
+///
+/// Like `char_slice(0)` and `word_slice(0)`, `grapheme_slice(0)` produces one empty window per position (before each cluster and at the end).
+/// ```
+/// use creature_feature::ftzrs::grapheme_slice;
+/// use creature_feature::traits::Ftzr;
+///
+/// let feats: Vec<&str> = grapheme_slice(0).featurize("e\u{301}t");
+/// assert_eq!(feats, vec!["", "", ""]);
+/// ```
+pub fn grapheme_slice(n: usize) -> GraphemeSlice {
+    GraphemeSlice { n }
+}
+
+/// The grapheme cluster segmenter, equivalent to `grapheme_slice(1)`.
+///
+/// Requires the `graphemes` feature.
+/// ```
+/// use creature_feature::ftzrs::graphemes;
+/// use creature_feature::traits::Ftzr;
+///
+/// let family = "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}";
+/// let feats: Vec<&str> = graphemes().featurize(family);
+/// assert_eq!(feats, vec![family]);
+/// ```
+pub fn graphemes() -> GraphemeSlice {
+    GraphemeSlice { n: 1 }
+}
+
+/// The associated iterator for `<GraphemeSlice as IterFtzr<&str>>::Iter`
+#[derive(Clone, Debug)]
+pub struct GraphemeSliceIter<'a> {
+    starts: GraphemeIndices<'a>,
+    // `None` for empty windows
+    ends: Option<GraphemeIndices<'a>>,
+    data: &'a str,
+    done: bool,
+}
+
+impl<'a> Iterator for GraphemeSliceIter<'a> {
+    type Item = &'a str;
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.ends.as_mut() {
+            Some(ends) => {
+                let (end, last) = ends.next()?;
+                let (start, _) = self.starts.next()?;
+                Some(&self.data[start..end + last.len()])
+            }
+            None => match self.starts.next() {
+                Some((start, _)) => Some(&self.data[start..start]),
+                None if !self.done => {
+                    self.done = true;
+                    Some(&self.data[self.data.len()..])
+                }
+                None => None,
+            },
+        }
+    }
+}
+
+impl<'a> IterFtzr<&'a str> for GraphemeSlice {
+    type TokenGroup = &'a str;
+    type Iter = GraphemeSliceIter<'a>;
+
+    fn iterate_features(&self, origin: &'a str) -> Self::Iter {
+        let mut ends = origin.grapheme_indices(true);
+        // `ends` runs ahead, starting on the last cluster of the first window
+        if self.n > 1 {
+            ends.nth(self.n - 2);
+        }
+        GraphemeSliceIter {
+            starts: origin.grapheme_indices(true),
+            ends: Some(ends).filter(|_| self.n > 0),
+            data: origin,
+            done: false,
+        }
+    }
+}
+
+impl<'a> IterFtzr<&'a String> for GraphemeSlice {
+    type TokenGroup = &'a str;
+    type Iter = GraphemeSliceIter<'a>;
+
+    fn iterate_features(&self, origin: &'a String) -> Self::Iter {
+        self.iterate_features(origin.as_str())
+    }
+}
+
+impl<Origin> Ftzr<Origin> for GraphemeSlice
+where
+    Self: IterFtzr<Origin>,
+{
+    type TokenGroup = <Self as IterFtzr<Origin>>::TokenGroup;
+    fn push_tokens<Push>(&self, origin: Origin, push: &mut Push)
+    where
+        Push: FnMut(Self::TokenGroup),
+    {
+        for t in self.iterate_features(origin) {
+            push(t)
+        }
+    }
+
+    fn push_tokens_while<Push>(&self, origin: Origin, push: &mut Push) -> ControlFlow<()>
+    where
+        Push: FnMut(Self::TokenGroup) -> ControlFlow<()>,
+    {
+        self.iterate_features(origin).try_for_each(push)
+    }
+}
+
+/// `n` graphemes, which take at least `n` bytes of a `&str`
+impl MinLen for GraphemeSlice {
+    fn min_len(&self) -> usize {
+        self.n
+    }
+}
@@ -1,5 +1,6 @@
 use crate::convert::{Output, SelfOut};
-use crate::feature_from::FeatureFrom;
+use crate::error::FeatureError;
+use crate::feature_from::{FeatureFrom, TryFeatureFrom};
 use crate::gap_gram::GapPair;
 use fxhash::FxHasher64;
 use nohash_hasher::IsEnabled;
@@ -65,6 +66,11 @@ macro_rules! impl_hashed {
                 HashedAs(h.finish() as $u_type)
             }
         }
+        impl<T: Hash> TryFeatureFrom<T> for HashedAs<$u_type> {
+            fn try_from(token_group: T) -> Result<Self, FeatureError> {
+                Ok(FeatureFrom::from(token_group))
+            }
+        }
         impl<T: Hash, V: Hash> From<GapPair<T, V>> for HashedAs<$u_type> {
             fn from(x: GapPair<T, V>) -> Self {
                 let mut h = TheHasher::default();
@@ -91,6 +97,14 @@ where
         Reverse(FeatureFrom::from(token_group))
     }
 }
+impl<A, B> TryFeatureFrom<A> for Reverse<HashedAs<B>>
+where
+    HashedAs<B>: TryFeatureFrom<A>,
+{
+    fn try_from(token_group: A) -> Result<Self, FeatureError> {
+        TryFeatureFrom::try_from(token_group).map(Reverse)
+    }
+}
 impl_hashed!(u8);
 impl_hashed!(u16);
 impl_hashed!(u32);
@@ -63,11 +63,163 @@ macro_rules! impl_ftrzs {
                     push(t)
                 }
             }
+
+            fn push_tokens_while<Push>(
+                &self,
+                origin: Origin,
+                push: &mut Push,
+            ) -> std::ops::ControlFlow<()>
+            where
+                Push: FnMut(Self::TokenGroup) -> std::ops::ControlFlow<()>,
+            {
+                self.iterate_features(origin).try_for_each(push)
+            }
         }
     };
 }
 pub(crate) use impl_ftrzs;
 
+/// Like `impl_ftrzs`, but for featurizers of numbers, which shouldn't accept `&str`/`&String` as bytes.
+macro_rules! impl_slice_ftrzs {
+    ($self:ty) => {
+        impl<'a, T> IterFtzr<&'a Vec<T>> for $self
+        where
+            Self: IterFtzr<&'a [T]>,
+        {
+            type TokenGroup = <Self as IterFtzr<&'a [T]>>::TokenGroup;
+            type Iter = <Self as IterFtzr<&'a [T]>>::Iter;
+
+            fn iterate_features(&self, origin: &'a Vec<T>) -> Self::Iter {
+                self.iterate_features(origin.as_slice())
+            }
+        }
+        impl<'a, T, const N: usize> IterFtzr<&'a [T; N]> for $self
+        where
+            Self: IterFtzr<&'a [T]>,
+        {
+            type TokenGroup = <Self as IterFtzr<&'a [T]>>::TokenGroup;
+            type Iter = <Self as IterFtzr<&'a [T]>>::Iter;
+
+            fn iterate_features(&self, origin: &'a [T; N]) -> Self::Iter {
+                self.iterate_features(&origin[..])
+            }
+        }
+
+        impl<Origin> Ftzr<Origin> for $self
+        where
+            Self: IterFtzr<Origin>,
+        {
+            type TokenGroup = <Self as IterFtzr<Origin>>::TokenGroup;
+            fn push_tokens<Push>(&self, origin: Origin, push: &mut Push)
+            where
+                Push: FnMut(Self::TokenGroup),
+            {
+                for t in self.iterate_features(origin) {
+                    push(t)
+                }
+            }
+
+            fn push_tokens_while<Push>(
+                &self,
+                origin: Origin,
+                push: &mut Push,
+            ) -> std::ops::ControlFlow<()>
+            where
+                Push: FnMut(Self::TokenGroup) -> std::ops::ControlFlow<()>,
+            {
+                self.iterate_features(origin).try_for_each(push)
+            }
+        }
+    };
+}
+pub(crate) use impl_slice_ftrzs;
+
+/// Like `impl_push_ftrzs`, but for featurizers of numbers, which shouldn't accept `&str`/`&String` as bytes.
+macro_rules! impl_push_slice_ftrzs {
+    ($self:ty) => {
+        impl<'a, T> Ftzr<&'a Vec<T>> for $self
+        where
+            Self: Ftzr<&'a [T]>,
+        {
+            type TokenGroup = <Self as Ftzr<&'a [T]>>::TokenGroup;
+            fn push_tokens<Push>(&self, origin: &'a Vec<T>, push: &mut Push)
+            where
+                Push: FnMut(Self::TokenGroup),
+            {
+                self.push_tokens(origin.as_slice(), push)
+            }
+        }
+        impl<'a, T, const N: usize> Ftzr<&'a [T; N]> for $self
+        where
+            Self: Ftzr<&'a [T]>,
+        {
+            type TokenGroup = <Self as Ftzr<&'a [T]>>::TokenGroup;
+            fn push_tokens<Push>(&self, origin: &'a [T; N], push: &mut Push)
+            where
+                Push: FnMut(Self::TokenGroup),
+            {
+                self.push_tokens(&origin[..], push)
+            }
+        }
+    };
+}
+pub(crate) use impl_push_slice_ftrzs;
+
+/// Like `impl_ftrzs`, but for featurizers that only impl `Ftzr<&[T]>` (by visitation) and not `IterFtzr`.
+macro_rules! impl_push_ftrzs {
+    ($self:ty) => {
+        impl<'a, T> Ftzr<&'a Vec<T>> for $self
+        where
+            Self: Ftzr<&'a [T]>,
+        {
+            type TokenGroup = <Self as Ftzr<&'a [T]>>::TokenGroup;
+            fn push_tokens<Push>(&self, origin: &'a Vec<T>, push: &mut Push)
+            where
+                Push: FnMut(Self::TokenGroup),
+            {
+                self.push_tokens(origin.as_slice(), push)
+            }
+        }
+        impl<'a, T, const N: usize> Ftzr<&'a [T; N]> for $self
+        where
+            Self: Ftzr<&'a [T]>,
+        {
+            type TokenGroup = <Self as Ftzr<&'a [T]>>::TokenGroup;
+            fn push_tokens<Push>(&self, origin: &'a [T; N], push: &mut Push)
+            where
+                Push: FnMut(Self::TokenGroup),
+            {
+                self.push_tokens(&origin[..], push)
+            }
+        }
+        impl<'a> Ftzr<&'a str> for $self
+        where
+            Self: Ftzr<&'a [u8]>,
+        {
+            type TokenGroup = <Self as Ftzr<&'a [u8]>>::TokenGroup;
+            fn push_tokens<Push>(&self, origin: &'a str, push: &mut Push)
+            where
+                Push: FnMut(Self::TokenGroup),
+            {
+                self.push_tokens(origin.as_bytes(), push)
+            }
+        }
+        impl<'a> Ftzr<&'a String> for $self
+        where
+            Self: Ftzr<&'a [u8]>,
+        {
+            type TokenGroup = <Self as Ftzr<&'a [u8]>>::TokenGroup;
+            fn push_tokens<Push>(&self, origin: &'a String, push: &mut Push)
+            where
+                Push: FnMut(Self::TokenGroup),
+            {
+                self.push_tokens(origin.as_bytes(), push)
+            }
+        }
+    };
+}
+pub(crate) use impl_push_ftrzs;
+
 macro_rules! impl_ftrzs_2 {
     ($self:ty) => {
         impl<'a, Y, X, T> IterFtzr<&'a Vec<T>> for $self
@@ -120,7 +120,7 @@
 //!
 //! # Where does `creature_feature` fit in with other tokenizers?
 //!
-//! `creature_feature` is very flexible, and [`traits::Ftzr`]/[`traits::IterFtzr`] can be easily implemented with a newtype for whatever other tokenizer/featurizer you please. Anything could be featurized: images, documents, time-series data, etc.
+//! `creature_feature` is very flexible, and [`traits::Ftzr`]/[`traits::IterFtzr`] can be easily implemented with a newtype for whatever other tokenizer/featurizer you please. Anything could be featurized: images, documents, time-series data (see `ftzrs::sax`), etc.
 //!
 //! # Example: Featurizing books
 //! Consider a custom struct to represent a book
@@ -174,8 +174,9 @@
 //! Now we could easily implement a similarity metric for `Book` via `Vec<HashedAs<u64>>`, like cosine or jaccard.
 //!
 //! # Usage notes
-//! * No bounds checking is performed. This is the responsibility of the user.
-//! * To handle unicode, convert to `Vec<char>`
+//! * Inputs that are too short for a featurizer are clamped, so combinators like `bookends` and `gap_gram` only produce the token groups that fit. Use [`traits::Ftzr::try_featurize`] to get a [`FeatureError`] instead.
+//! * `Ftzr::try_featurize` also returns an error, rather than panicking, on token groups that aren't UTF-8. Use `convert::Lossy<String>` as the feature to substitute U+FFFD instead.
+//! * `n_slice` and `n_gram` treat a `&str` as bytes. To handle unicode, use [`ftzrs::char_slice`] or [`ftzrs::char_gram`] (or convert to `Vec<char>`). With the `graphemes` feature, `ftzrs::grapheme_slice` won't split emoji or combining accents either.
 //!
 //!
 //! # YOU CAN HELP
@@ -199,18 +200,46 @@ pub use hashedfeature::HashedAs;
 
 mod accum_ftzr;
 
+mod error;
+pub use error::FeatureError;
+
+mod adapters;
+mod bins;
 mod bookends;
+mod bpe;
+mod char_gram;
+mod char_slice;
+mod deltas;
+mod field;
 mod for_each;
 mod gap_gram;
+#[cfg(feature = "graphemes")]
+mod grapheme;
 mod multiftzr;
 mod n_gram;
+mod n_range;
 mod n_slice;
+mod normalized;
+mod numeric;
+mod padded;
+mod rolling_hash;
+mod sax;
+mod skip_gram;
+mod spans;
+mod stemmed;
+mod stopwords;
+mod strided;
 mod whole_empty;
+mod winnow;
+mod word_gram;
+mod word_piece;
 
 /// All traits contained in the crate. The most imminently useful are [`traits::Ftzr`] and [`traits::FeatureFrom`]
 pub mod traits {
-    pub use super::accum_ftzr::{Accumulates, Ftzr, IterFtzr, LinearFixed};
-    pub use super::feature_from::FeatureFrom;
+    pub use super::accum_ftzr::{
+        Accumulates, Ftzr, InputLen, IterFtzr, LinearFixed, MinLen, SpanFtzr,
+    };
+    pub use super::feature_from::{FeatureFrom, TryFeatureFrom};
 }
 
 /// newtype wrappers to supplement [`traits::FeatureFrom`]
@@ -219,45 +248,114 @@ pub mod convert;
 /// featurizers & combinators
 pub mod ftzrs {
 
-    /// A macro for general composition of featurizers. `featurizers!(ftzr_a, ftzr_b, ... ftzr_z)` will featurize input data exactly `z` times. Must use `Merged` in the output.
+    /// A macro for general composition of featurizers. `featurizers!(ftzr_a, ftzr_b, ... ftzr_z)` will featurize input data exactly `z` times. The output can use `Merged<T>` when every featurizer produces a `T` (like `HashedAs<u64>` for more than two featurizers), or nested `EitherGroup`s for any mix of types. (See also `Ftzr::and_then`)
     ///
     /// ```
-    /// let my_ftzr = featurizers!(ftzr1,
-    ///                            ftzr2,
-    ///                            ftzr3,
-    ///                            ftzr_n);
-    /// let feats: HashSet<Merged<&str>> = my_ftzr.featurize(your_data);
+    /// use creature_feature::convert::Merged;
+    /// use creature_feature::featurizers;
+    /// use creature_feature::ftzrs::misc::EitherGroup::{self, Left, Right};
+    /// use creature_feature::ftzrs::{bigram, bislice, whole};
+    /// use creature_feature::traits::Ftzr;
+    /// use std::collections::HashSet;
+    ///
+    /// let feats: HashSet<Merged<String>> = featurizers!(bislice(), whole()).featurize("abc");
+    /// assert_eq!(feats.len(), 3);
+    ///
+    /// // featurizers!(a, b, c) is MultiFtzr(a, MultiFtzr(b, c))
+    /// let my_ftzr = featurizers!(bislice(), bigram(), whole());
+    /// let feats: Vec<EitherGroup<&str, EitherGroup<[u8; 2], String>>> = my_ftzr.featurize("abc");
+    /// assert_eq!(feats[0], Left("ab"));
+    /// assert_eq!(feats[2], Right(Left(*b"ab")));
+    /// assert_eq!(feats[4], Right(Right("abc".to_owned())));
     /// ```
     #[macro_export]
     macro_rules! featurizers {
-        ($a:expr) => {
+        ($a:expr $(,)?) => {
             $a
         };
-        ($a:expr $(, $tail:expr)*) => {{
-            use creature_feature::ftzrs::misc::MultiFtzr;
-            MultiFtzr($a, featurizers!($($tail), *),
-        )
-        }};
+        ($a:expr $(, $tail:expr)+ $(,)?) => {
+            $crate::ftzrs::misc::MultiFtzr($a, $crate::featurizers!($($tail),+))
+        };
     }
 
     pub use featurizers;
 
+    pub use super::adapters::{sample, skip, take, unique};
+    pub use super::bins::{bin_edges, log_bins, quantile_bins, uniform_bins};
     pub use super::bookends::bookends;
+    pub use super::bpe::bpe;
+    pub use super::char_gram::char_gram;
+    pub use super::char_slice::char_slice;
+    pub use super::deltas::{deltas, ratios};
+    pub use super::field::field;
     pub use super::gap_gram::gap_gram;
+    #[cfg(feature = "graphemes")]
+    pub use super::grapheme::{grapheme_slice, graphemes};
     //pub use super::multiftzr::featurizers;
     pub use super::for_each::for_each;
     pub use super::n_gram::{bigram, n_gram, trigram};
+    pub use super::n_range::n_range;
     pub use super::n_slice::{bislice, n_slice, trislice};
+    pub use super::normalized::normalized;
+    pub use super::padded::padded;
+    pub use super::rolling_hash::rolling_hash;
+    pub use super::sax::sax;
+    pub use super::skip_gram::skip_gram;
+    pub use super::spans::with_spans;
+    pub use super::stemmed::stemmed;
+    pub use super::stopwords::without_stopwords;
+    pub use super::strided::strided;
     pub use super::whole_empty::{empty, whole};
+    pub use super::winnow::winnow;
+    pub use super::word_gram::{word_gram, word_indices, word_slice};
+    pub use super::word_piece::word_piece;
 
     /// All associated types for the featurizers in `creature_feature::ftzrs`. Most featurizers have an an associated iterator, and possibly a specialized type for `Self::TokenGroup`.
     pub mod misc {
+        pub use super::super::adapters::{
+            Filter, FilterMap, Map, Sample, SampleIter, Skip, Take, Unique, UniqueIter,
+        };
+        pub use super::super::bins::{Bin, Bins};
         pub use super::super::bookends::{BookEnds, BookEndsIter, FrontBack};
-        pub use super::super::for_each::ForEach;
+        pub use super::super::bpe::{BpePiece, BytePairEncoder, MergeTableError};
+        pub use super::super::char_gram::{CharGram, CharGramIter};
+        pub use super::super::char_slice::{CharSlice, CharSliceIter, CharWindow};
+        pub use super::super::deltas::{
+            Clamped, DeltaKind, Deltas, DeltasIter, Difference, OwnedWindow, Ratio, Signs,
+        };
+        pub use super::super::field::{Field, FieldIter};
+        pub use super::super::for_each::{ForEach, ForEachIter};
         pub use super::super::gap_gram::{GapGram, GapGramIter, GapPair};
-        pub use super::super::multiftzr::{EitherGroup, MultiFtzr, MultiFtzrIter};
+        #[cfg(feature = "graphemes")]
+        pub use super::super::grapheme::{GraphemeSlice, GraphemeSliceIter};
+        pub use super::super::multiftzr::{AndThen, EitherGroup, MultiFtzr, MultiFtzrIter, Zip};
         pub use super::super::n_gram::{NGram, NGramIter};
+        pub use super::super::n_range::NRange;
         pub use super::super::n_slice::{SliceGram, SliceGramIter};
+        pub use super::super::normalized::{Normalization, Normalized, NormalizedSlice};
+        pub use super::super::numeric::Numeric;
+        pub use super::super::padded::{
+            Pad, PadIter, Padded, PaddedGramIter, PaddedSlice, PaddedSliceIter, END_SENTINEL,
+            START_SENTINEL,
+        };
+        pub use super::super::rolling_hash::{Rolled, RollingHash, RollingHashIter, ROLLING_BASE};
+        pub use super::super::sax::{
+            Sax, SaxIter, SaxOver, SaxOverIter, SaxSymbol, SaxWindow, SaxWord,
+        };
+        pub use super::super::skip_gram::SkipGram;
+        pub use super::super::spans::WithSpans;
+        pub use super::super::stemmed::{porter2, ByteStem, Stemmable, Stemmed};
+        pub use super::super::stopwords::{Stopwords, WithoutStopwords};
+        pub use super::super::strided::{
+            DropTail, PadTail, ShortTail, Strided, StridedGramIter, StridedPaddedGramIter,
+            StridedPaddedSliceIter, StridedSliceIter,
+        };
         pub use super::super::whole_empty::{Empty, EmptyAtom, Whole};
+        pub use super::super::winnow::{Fingerprint, Winnow};
+        pub use super::super::word_gram::{
+            is_word_delimiter, WordGram, WordGramIter, WordIndices, WordSlice, WordSliceIter,
+            WordSpans,
+        };
+        pub use super::super::word_piece::{MissingUnkError, Subword, WordPiece, WordPieceVocab};
     }
 }
@@ -3,12 +3,14 @@
 #![allow(unused)]
 //#[allow(warnings, unused)]
 mod for_each;
+mod skip_gram;
 mod skip_schema;
 #[macro_use]
 mod internal;
 mod convert;
 mod feature_from;
 mod whole_empty;
+mod word_gram;
 
 mod bench;
 
@@ -18,12 +20,36 @@ use tokengroup::*;
 mod bookends;
 use bookends::*;
 
+mod char_gram;
+mod char_slice;
+#[cfg(feature = "graphemes")]
+mod grapheme;
+
 mod accum_ftzr;
 use accum_ftzr::*;
 
 mod n_slice;
 use n_slice::*;
 
+mod adapters;
+mod bins;
+mod bpe;
+mod deltas;
+mod error;
+mod field;
+mod n_range;
+mod normalized;
+mod numeric;
+mod padded;
+mod rolling_hash;
+mod sax;
+mod spans;
+mod stemmed;
+mod stopwords;
+mod strided;
+mod winnow;
+mod word_piece;
+
 mod multiftzr;
 use multiftzr::*;
 
@@ -1,11 +1,15 @@
-use crate::accum_ftzr::{Ftzr, IterFtzr};
+use crate::accum_ftzr::{Ftzr, IterFtzr, MinLen};
 use crate::convert::Merged;
-use crate::feature_from::FeatureFrom;
+use crate::error::FeatureError;
+use crate::feature_from::{FeatureFrom, TryFeatureFrom};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 use std::hash::{Hash, Hasher};
+use std::iter;
+use std::marker::PhantomData;
+use std::ops::ControlFlow;
 
-/// The composition of two featurizers. Created with `featurizers!`
+/// The composition of two featurizers. Created with `featurizers!` (or `Ftzr::and_then`, see [`AndThen`])
 #[derive(Hash, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Debug)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct MultiFtzr<A, B>(pub A, pub B);
@@ -66,6 +70,54 @@ pub enum EitherGroup<A, B> {
     Right(B),
 }
 
+impl<A, B> EitherGroup<A, B> {
+    /// The token group from `A`, if it is one
+    pub fn left(self) -> Option<A> {
+        match self {
+            EitherGroup::Left(a) => Some(a),
+            EitherGroup::Right(_) => None,
+        }
+    }
+
+    /// The token group from `B`, if it is one
+    pub fn right(self) -> Option<B> {
+        match self {
+            EitherGroup::Left(_) => None,
+            EitherGroup::Right(b) => Some(b),
+        }
+    }
+
+    /// Whether this came from `A`
+    pub fn is_left(&self) -> bool {
+        matches!(self, EitherGroup::Left(_))
+    }
+
+    /// Whether this came from `B`
+    pub fn is_right(&self) -> bool {
+        matches!(self, EitherGroup::Right(_))
+    }
+
+    /// Borrows the token group, like `Option::as_ref`
+    pub fn as_ref(&self) -> EitherGroup<&A, &B> {
+        match self {
+            EitherGroup::Left(a) => EitherGroup::Left(a),
+            EitherGroup::Right(b) => EitherGroup::Right(b),
+        }
+    }
+
+    /// Converts the token group with whichever function matches its side
+    pub fn either<X, FA, FB>(self, on_left: FA, on_right: FB) -> X
+    where
+        FA: FnOnce(A) -> X,
+        FB: FnOnce(B) -> X,
+    {
+        match self {
+            EitherGroup::Left(a) => on_left(a),
+            EitherGroup::Right(b) => on_right(b),
+        }
+    }
+}
+
 /// This is needed so that identical features will
 /// have the same hash in the case of MultiFtzr<A,A>
 /// Notice that this is not desired for `bookends::FrontBack`
@@ -108,6 +160,20 @@ where
     }
 }
 
+/// Lets each side become a different feature, like `EitherGroup<String, HashedAs<u64>>`, when there's no common type for `Merged`
+impl<A1, A2, B1, B2> FeatureFrom<EitherGroup<A1, B1>> for EitherGroup<A2, B2>
+where
+    A2: FeatureFrom<A1>,
+    B2: FeatureFrom<B1>,
+{
+    fn from(x: EitherGroup<A1, B1>) -> Self {
+        match x {
+            EitherGroup::Left(a) => EitherGroup::Left(FeatureFrom::from(a)),
+            EitherGroup::Right(b) => EitherGroup::Right(FeatureFrom::from(b)),
+        }
+    }
+}
+
 impl<A, B, X> FeatureFrom<EitherGroup<A, B>> for Merged<X>
 where
     X: FeatureFrom<A> + FeatureFrom<B>,
@@ -121,6 +187,32 @@ where
         })
     }
 }
+
+impl<A1, A2, B1, B2> TryFeatureFrom<EitherGroup<A1, B1>> for EitherGroup<A2, B2>
+where
+    A2: TryFeatureFrom<A1>,
+    B2: TryFeatureFrom<B1>,
+{
+    fn try_from(x: EitherGroup<A1, B1>) -> Result<Self, FeatureError> {
+        Ok(match x {
+            EitherGroup::Left(a) => EitherGroup::Left(TryFeatureFrom::try_from(a)?),
+            EitherGroup::Right(b) => EitherGroup::Right(TryFeatureFrom::try_from(b)?),
+        })
+    }
+}
+
+impl<A, B, X> TryFeatureFrom<EitherGroup<A, B>> for Merged<X>
+where
+    X: TryFeatureFrom<A> + TryFeatureFrom<B>,
+{
+    fn try_from(x: EitherGroup<A, B>) -> Result<Self, FeatureError> {
+        Ok(Merged(match x {
+            EitherGroup::Left(a) => TryFeatureFrom::try_from(a)?,
+            EitherGroup::Right(a) => TryFeatureFrom::try_from(a)?,
+        }))
+    }
+}
+
 impl<Origin: Copy, A, B> Ftzr<Origin> for MultiFtzr<A, B>
 where
     A: Ftzr<Origin>,
@@ -139,3 +231,135 @@ where
         self.1.push_tokens(origin, &mut p);
     }
 }
+
+/// The composition of two featurizers, which is `featurizers!(a, b)` tied to one input type. Created with `Ftzr::and_then`
+#[derive(Hash, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AndThen<A, B, Meta> {
+    ftzrs: MultiFtzr<A, B>,
+    tags: PhantomData<Meta>,
+}
+
+impl<A, B, Meta> AndThen<A, B, Meta> {
+    pub(crate) fn new(a: A, b: B) -> Self {
+        AndThen {
+            ftzrs: MultiFtzr(a, b),
+            tags: PhantomData,
+        }
+    }
+}
+
+impl<Origin, A, B> Ftzr<Origin> for AndThen<A, B, Origin>
+where
+    MultiFtzr<A, B>: Ftzr<Origin>,
+{
+    type TokenGroup = <MultiFtzr<A, B> as Ftzr<Origin>>::TokenGroup;
+    fn push_tokens<Push>(&self, origin: Origin, push: &mut Push)
+    where
+        Push: FnMut(Self::TokenGroup),
+    {
+        self.ftzrs.push_tokens(origin, push)
+    }
+}
+
+impl<Origin, A, B> IterFtzr<Origin> for AndThen<A, B, Origin>
+where
+    MultiFtzr<A, B>: IterFtzr<Origin>,
+{
+    type TokenGroup = <MultiFtzr<A, B> as IterFtzr<Origin>>::TokenGroup;
+    type Iter = <MultiFtzr<A, B> as IterFtzr<Origin>>::Iter;
+
+    fn iterate_features(&self, origin: Origin) -> Self::Iter {
+        self.ftzrs.iterate_features(origin)
+    }
+}
+
+/// Pairs the token groups of two featurizers in lockstep. Created with `Ftzr::zip_with`
+#[derive(Hash, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Zip<A, B, Meta> {
+    a: A,
+    b: B,
+    tags: PhantomData<Meta>,
+}
+
+impl<A, B, Meta> Zip<A, B, Meta> {
+    pub(crate) fn new(a: A, b: B) -> Self {
+        Zip {
+            a,
+            b,
+            tags: PhantomData,
+        }
+    }
+}
+
+/// The token groups of `B` are buffered in a `Vec` first, then paired with those of `A` as they're visited. `iterate_features` zips lazily instead, when both are `IterFtzr`.
+impl<Origin: Copy, A, B> Ftzr<Origin> for Zip<A, B, Origin>
+where
+    A: Ftzr<Origin>,
+    B: Ftzr<Origin>,
+{
+    type TokenGroup = (A::TokenGroup, B::TokenGroup);
+    fn push_tokens<Push>(&self, origin: Origin, push: &mut Push)
+    where
+        Push: FnMut(Self::TokenGroup),
+    {
+        self.push_tokens_while(origin, &mut |pair| {
+            push(pair);
+            ControlFlow::Continue(())
+        });
+    }
+
+    fn push_tokens_while<Push>(&self, origin: Origin, push: &mut Push) -> ControlFlow<()>
+    where
+        Push: FnMut(Self::TokenGroup) -> ControlFlow<()>,
+    {
+        let mut rights = Vec::new();
+        self.b.push_tokens(origin, &mut |b| rights.push(b));
+        let mut rights = rights.into_iter();
+        // `A` is stopped once `B` runs out, but only `push` stopping is passed on
+        let mut flow = ControlFlow::Continue(());
+        self.a
+            .push_tokens_while(origin, &mut |a| match rights.next() {
+                Some(b) => {
+                    flow = push((a, b));
+                    flow
+                }
+                None => ControlFlow::Break(()),
+            });
+        flow
+    }
+}
+
+impl<Origin: Copy, A, B> IterFtzr<Origin> for Zip<A, B, Origin>
+where
+    A: IterFtzr<Origin>,
+    B: IterFtzr<Origin>,
+{
+    type TokenGroup = (A::TokenGroup, B::TokenGroup);
+    type Iter = iter::Zip<A::Iter, B::Iter>;
+
+    fn iterate_features(&self, origin: Origin) -> Self::Iter {
+        self.a
+            .iterate_features(origin)
+            .zip(self.b.iterate_features(origin))
+    }
+}
+
+impl<A: MinLen, B: MinLen> MinLen for MultiFtzr<A, B> {
+    fn min_len(&self) -> usize {
+        self.0.min_len().max(self.1.min_len())
+    }
+}
+
+impl<A: MinLen, B: MinLen, Meta> MinLen for AndThen<A, B, Meta> {
+    fn min_len(&self) -> usize {
+        self.ftzrs.min_len()
+    }
+}
+
+impl<A: MinLen, B: MinLen, Meta> MinLen for Zip<A, B, Meta> {
+    fn min_len(&self) -> usize {
+        self.a.min_len().max(self.b.min_len())
+    }
+}
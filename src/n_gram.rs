@@ -1,7 +1,10 @@
-use crate::accum_ftzr::{Ftzr, IterFtzr, LinearFixed};
+use crate::accum_ftzr::{Ftzr, IterFtzr, LinearFixed, MinLen, SpanFtzr};
+use crate::spans::push_linear_spans;
+use crate::strided::{strided, Strided};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 use std::convert::{TryFrom, TryInto};
+use std::ops::{ControlFlow, Range};
 
 /// The type of a fixed-length n-gram over copied data. Created by `n_gram::<N>()`
 #[derive(Hash, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Debug, Default)]
@@ -25,11 +28,8 @@ where
     fn next(&mut self) -> Option<Self::Item> {
         let jdx = self.idx + N;
         if jdx <= self.data.len() {
-            let ret = Some(
-                TryInto::try_into(&self.data[self.idx..jdx])
-                    .map_err(|_| ())
-                    .expect("Error converting from slice to [T;N]"),
-            );
+            // the window is always N long, so this can't fail, but ends the iteration rather than panicking
+            let ret = TryInto::try_into(&self.data[self.idx..jdx]).ok();
             self.idx += 1;
             ret
         } else {
@@ -59,6 +59,12 @@ impl<const N: usize> LinearFixed for NGram<N> {
     }
 }
 
+impl<const N: usize> MinLen for NGram<N> {
+    fn min_len(&self) -> usize {
+        N
+    }
+}
+
 impl<'a, T, const N: usize> IterFtzr<&'a Vec<T>> for NGram<N>
 where
     [T; N]: TryFrom<&'a [T]>,
@@ -129,6 +135,13 @@ pub fn n_gram<const N: usize>() -> NGram<N> {
     NGram::<N>()
 }
 
+impl<const N: usize> NGram<N> {
+    /// Only produce the windows starting every `step` tokens. (See `strided`)
+    pub fn stride(self, step: usize) -> Strided<Self> {
+        strided(self, step)
+    }
+}
+
 /// bigrams over copied data, produces owned data (like String) or multiple `[T; 2]`. (Compare to `bislice`)
 pub fn bigram() -> NGram<2> {
     NGram::<2>()
@@ -152,4 +165,25 @@ where
             push(t)
         }
     }
+
+    fn push_tokens_while<Push>(&self, origin: Origin, push: &mut Push) -> ControlFlow<()>
+    where
+        Push: FnMut(Self::TokenGroup) -> ControlFlow<()>,
+    {
+        self.iterate_features(origin).try_for_each(push)
+    }
+}
+
+impl<Origin, const N: usize> SpanFtzr<Origin> for NGram<N>
+where
+    Self: Ftzr<Origin>,
+{
+    type Span = Range<usize>;
+    type TokenGroup = <Self as Ftzr<Origin>>::TokenGroup;
+    fn push_spans<Push>(&self, origin: Origin, offset: usize, push: &mut Push)
+    where
+        Push: FnMut(Self::Span, Self::TokenGroup),
+    {
+        push_linear_spans(self, origin, offset, push)
+    }
 }
@@ -0,0 +1,61 @@
+use crate::accum_ftzr::{Ftzr, MinLen};
+use crate::internal::impl_push_ftrzs;
+use crate::skip_schema::SkipSchema;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Every n-gram over referenced data with `min <= n <= max`. Created with `n_range(min, max)`
+#[derive(Hash, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct NRange {
+    min: usize,
+    max: usize,
+}
+
+/// All n-grams over referenced data with a length from `min` to `max` (inclusive), in a single pass over the input. Produces the same outputs as `n_slice`, such as `&[T]`, `&str`, `Vec<T>` or `HashedAs`.
+///
+/// Grams are visited in order of where they start, and then by length. Empty grams are never produced, so `n_range(0, k)` is the same as `n_range(1, k)`.
+/// ```
+/// use creature_feature::ftzrs::n_range;
+/// use creature_feature::traits::Ftzr;
+///
+/// let feats: Vec<&str> = n_range(1, 3).featurize("abcd");
+/// assert_eq!(feats, vec!["a", "ab", "abc", "b", "bc", "bcd", "c", "cd", "d"]);
+///
+/// // instead of featurizers!(n_slice(2), n_slice(3)), which featurizes the input twice
+/// let feats: Vec<Vec<usize>> = n_range(2, 3).featurize(&[1, 2, 3]);
+/// assert_eq!(feats, vec![vec![1, 2], vec![1, 2, 3], vec![2, 3]]);
+/// ```
+pub fn n_range(min: usize, max: usize) -> NRange {
+    NRange { min, max }
+}
+
+impl NRange {
+    fn schema(&self) -> SkipSchema {
+        SkipSchema {
+            group_a: (self.min, self.max),
+            gap: (0, 0),
+            group_b: (0, 0),
+        }
+    }
+}
+
+impl<'a, T> Ftzr<&'a [T]> for NRange {
+    type TokenGroup = &'a [T];
+    #[inline]
+    fn push_tokens<Push>(&self, origin: &'a [T], push: &mut Push)
+    where
+        Push: FnMut(Self::TokenGroup),
+    {
+        self.schema().push_windows(origin, push)
+    }
+}
+
+impl_push_ftrzs!(NRange);
+
+impl MinLen for NRange {
+    fn min_len(&self) -> usize {
+        self.max
+    }
+}
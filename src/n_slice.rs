@@ -1,6 +1,9 @@
-use crate::accum_ftzr::{Ftzr, IterFtzr, LinearFixed};
+use crate::accum_ftzr::{Ftzr, IterFtzr, LinearFixed, MinLen, SpanFtzr};
 
 use crate::internal::impl_ftrzs;
+use crate::spans::push_linear_spans;
+use crate::strided::{strided, Strided};
+use std::ops::Range;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -9,7 +12,7 @@ use serde::{Deserialize, Serialize};
 #[derive(Hash, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Debug)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SliceGram {
-    n: usize,
+    pub(crate) n: usize,
 }
 
 /// general n-grams over referenced data, produces borrowed data (like &str) or multiple `&[T]` of a fixed length. (Compare to `n_gram`)
@@ -35,6 +38,13 @@ pub fn trislice() -> SliceGram {
     SliceGram { n: 3 }
 }
 
+impl SliceGram {
+    /// Only produce the windows starting every `step` tokens. (See `strided`)
+    pub fn stride(self, step: usize) -> Strided<Self> {
+        strided(self, step)
+    }
+}
+
 /// The associated iterator for SliceGram as IterFtzr<T>>::Iter
 #[derive(Hash, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Debug)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -101,6 +111,12 @@ impl LinearFixed for SliceGram {
         self.n
     }
 }
+
+impl MinLen for SliceGram {
+    fn min_len(&self) -> usize {
+        self.chunk_size()
+    }
+}
 impl<'a, T> IterFtzr<&'a [T]> for SliceGram {
     type TokenGroup = &'a [T];
     type Iter = SliceGramIter<&'a [T]>;
@@ -161,3 +177,17 @@ impl<'a, T, const N: usize> IterFtzr<&'a [T; N]> for SliceGram {
         }
     }
 }*/
+
+impl<Origin> SpanFtzr<Origin> for SliceGram
+where
+    Self: Ftzr<Origin>,
+{
+    type Span = Range<usize>;
+    type TokenGroup = <Self as Ftzr<Origin>>::TokenGroup;
+    fn push_spans<Push>(&self, origin: Origin, offset: usize, push: &mut Push)
+    where
+        Push: FnMut(Self::Span, Self::TokenGroup),
+    {
+        push_linear_spans(self, origin, offset, push)
+    }
+}
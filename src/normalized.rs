@@ -0,0 +1,558 @@
+use crate::accum_ftzr::{Ftzr, MinLen, SpanFtzr};
+use crate::char_gram::CharGram;
+use crate::convert::Lossy;
+use crate::error::FeatureError;
+use crate::feature_from::{try_via_from, FeatureFrom, TryFeatureFrom};
+use crate::n_gram::NGram;
+use crate::n_slice::SliceGram;
+use crate::whole_empty::Whole;
+use crate::word_gram::WordSlice;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::{Deref, Range};
+use std::sync::Arc;
+
+/// Which normalizations `normalized` applies. All are off by default, and they're turned on with the builder methods.
+/// ```
+/// use creature_feature::ftzrs::misc::Normalization;
+///
+/// let opts = Normalization::default().lowercase().ascii_fold().digits().collapse_whitespace();
+/// assert_eq!(opts.normalize("  Crème  Brûlée\t(2 for £10) "), "creme brulee (0 for £00)");
+///
+/// // nothing is allocated if the text is already normalized
+/// assert!(matches!(opts.normalize("creme brulee"), std::borrow::Cow::Borrowed(_)));
+/// ```
+#[derive(Hash, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Normalization {
+    lowercase: bool,
+    case_fold: bool,
+    ascii_fold: bool,
+    digits: bool,
+    collapse_whitespace: bool,
+}
+
+impl Normalization {
+    /// Unicode lowercasing, so `"ÉTÉ"` becomes `"été"`. This isn't enough to ignore case: `"ß"` stays `"ß"` while `"SS"` becomes `"ss"`, so use `case_fold` for that.
+    pub fn lowercase(self) -> Self {
+        Normalization {
+            lowercase: true,
+            ..self
+        }
+    }
+
+    /// Full Unicode case folding, so that text that only differs by case is the same: `"Straße"`, `"STRASSE"` and `"strasse"` all become `"strasse"`, and `"ﬁ"` becomes `"fi"`. This is mostly lowercasing, and it makes `lowercase` redundant.
+    pub fn case_fold(self) -> Self {
+        Normalization {
+            case_fold: true,
+            ..self
+        }
+    }
+
+    /// Replace accented Latin letters (Latin-1 and Latin Extended-A) and typographic punctuation with their closest ASCII, so `"Æsir’s café"` becomes `"AEsir's cafe"`. Other chars are kept.
+    pub fn ascii_fold(self) -> Self {
+        Normalization {
+            ascii_fold: true,
+            ..self
+        }
+    }
+
+    /// Replace every ASCII digit with `'0'`, so numbers with the same number of digits share features
+    pub fn digits(self) -> Self {
+        Normalization {
+            digits: true,
+            ..self
+        }
+    }
+
+    /// Replace each run of whitespace with a single space, and trim whitespace from both ends
+    pub fn collapse_whitespace(self) -> Self {
+        Normalization {
+            collapse_whitespace: true,
+            ..self
+        }
+    }
+
+    /// The normalized text, borrowing `text` if it's already normalized
+    pub fn normalize<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        if self.is_normalized(text) {
+            Cow::Borrowed(text)
+        } else {
+            let mut out = String::with_capacity(text.len());
+            self.normalize_into(text, &mut out);
+            Cow::Owned(out)
+        }
+    }
+
+    fn is_normalized(&self, text: &str) -> bool {
+        if self.collapse_whitespace
+            && (text.starts_with(char::is_whitespace)
+                || text.ends_with(char::is_whitespace)
+                || text.contains(|c: char| c.is_whitespace() && c != ' ')
+                || text.contains("  "))
+        {
+            return false;
+        }
+        text.chars().all(|c| {
+            !(self.case_fold && !is_case_fold_fixed(c)
+                || self.lowercase && !is_lowercase_fixed(c)
+                || self.ascii_fold && ascii_fold(c).is_some()
+                || self.digits && c.is_ascii_digit() && c != '0')
+        })
+    }
+
+    fn normalize_into(&self, text: &str, out: &mut String) {
+        let mut space = false;
+        for c in text.chars() {
+            if self.collapse_whitespace && c.is_whitespace() {
+                space = true;
+                continue;
+            }
+            if space && !out.is_empty() {
+                out.push(' ');
+            }
+            space = false;
+            if self.case_fold {
+                case_fold(c, |c| self.push_folded(c, out));
+            } else if self.lowercase {
+                for c in c.to_lowercase() {
+                    self.push_folded(c, out);
+                }
+            } else {
+                self.push_folded(c, out);
+            }
+        }
+    }
+
+    fn push_folded(&self, c: char, out: &mut String) {
+        match ascii_fold(c).filter(|_| self.ascii_fold) {
+            Some(folded) => folded.chars().for_each(|c| self.push_digit(c, out)),
+            None => self.push_digit(c, out),
+        }
+    }
+
+    fn push_digit(&self, c: char, out: &mut String) {
+        if self.digits && c.is_ascii_digit() {
+            out.push('0')
+        } else {
+            out.push(c)
+        }
+    }
+}
+
+fn is_lowercase_fixed(c: char) -> bool {
+    let mut lower = c.to_lowercase();
+    lower.next() == Some(c) && lower.next().is_none()
+}
+
+fn is_case_fold_fixed(c: char) -> bool {
+    let mut fixed = 0;
+    case_fold(c, |folded| fixed += if folded == c { 1 } else { 2 });
+    fixed == 1
+}
+
+/// Visits the full case folding of a char (the `C` and `F` mappings of Unicode's CaseFolding.txt)
+fn case_fold<F: FnMut(char)>(c: char, mut push: F) {
+    match c {
+        // Cherokee folds to uppercase, since its lowercase letters were added to Unicode later
+        'Ꭰ'..='Ᏽ' | 'ᏸ'..='ᏽ' | 'ꭰ'..='ꮿ' => c.to_uppercase().for_each(push),
+        _ => match special_case_fold(c) {
+            Some(folded) => folded.chars().for_each(push),
+            None => c.to_lowercase().for_each(push),
+        },
+    }
+}
+
+/// The case folding of the chars where it isn't the same as `char::to_lowercase` (besides Cherokee)
+fn special_case_fold(c: char) -> Option<&'static str> {
+    Some(match c {
+        'µ' => "μ",
+        'ß' | 'ẞ' => "ss",
+        'ŉ' => "ʼn",
+        'ſ' => "s",
+        'ǰ' => "j\u{30c}",
+        '\u{345}' | '\u{1fbe}' => "ι",
+        'ΐ' | '\u{1fd3}' => "ι\u{308}\u{301}",
+        'ΰ' | '\u{1fe3}' => "υ\u{308}\u{301}",
+        'ς' => "σ",
+        'ϐ' => "β",
+        'ϑ' => "θ",
+        'ϕ' => "φ",
+        'ϖ' => "π",
+        'ϰ' => "κ",
+        'ϱ' => "ρ",
+        'ϵ' => "ε",
+        'և' => "եւ",
+        'ᲀ' => "в",
+        'ᲁ' => "д",
+        'ᲂ' => "о",
+        'ᲃ' => "с",
+        'ᲄ' | 'ᲅ' => "т",
+        'ᲆ' => "ъ",
+        'ᲇ' => "ѣ",
+        'ᲈ' => "ꙋ",
+        'ẖ' => "h\u{331}",
+        'ẗ' => "t\u{308}",
+        'ẘ' => "w\u{30a}",
+        'ẙ' => "y\u{30a}",
+        'ẚ' => "aʾ",
+        'ẛ' => "ṡ",
+        'ὐ' => "υ\u{313}",
+        'ὒ' => "υ\u{313}\u{300}",
+        'ὔ' => "υ\u{313}\u{301}",
+        'ὖ' => "υ\u{313}\u{342}",
+        'ᾀ' | 'ᾈ' => "ἀι",
+        'ᾁ' | 'ᾉ' => "ἁι",
+        'ᾂ' | 'ᾊ' => "ἂι",
+        'ᾃ' | 'ᾋ' => "ἃι",
+        'ᾄ' | 'ᾌ' => "ἄι",
+        'ᾅ' | 'ᾍ' => "ἅι",
+        'ᾆ' | 'ᾎ' => "ἆι",
+        'ᾇ' | 'ᾏ' => "ἇι",
+        'ᾐ' | 'ᾘ' => "ἠι",
+        'ᾑ' | 'ᾙ' => "ἡι",
+        'ᾒ' | 'ᾚ' => "ἢι",
+        'ᾓ' | 'ᾛ' => "ἣι",
+        'ᾔ' | 'ᾜ' => "ἤι",
+        'ᾕ' | 'ᾝ' => "ἥι",
+        'ᾖ' | 'ᾞ' => "ἦι",
+        'ᾗ' | 'ᾟ' => "ἧι",
+        'ᾠ' | 'ᾨ' => "ὠι",
+        'ᾡ' | 'ᾩ' => "ὡι",
+        'ᾢ' | 'ᾪ' => "ὢι",
+        'ᾣ' | 'ᾫ' => "ὣι",
+        'ᾤ' | 'ᾬ' => "ὤι",
+        'ᾥ' | 'ᾭ' => "ὥι",
+        'ᾦ' | 'ᾮ' => "ὦι",
+        'ᾧ' | 'ᾯ' => "ὧι",
+        'ᾲ' => "ὰι",
+        'ᾳ' | 'ᾼ' => "αι",
+        'ᾴ' => "άι",
+        'ᾶ' => "α\u{342}",
+        'ᾷ' => "α\u{342}ι",
+        'ῂ' => "ὴι",
+        'ῃ' | 'ῌ' => "ηι",
+        'ῄ' => "ήι",
+        'ῆ' => "η\u{342}",
+        'ῇ' => "η\u{342}ι",
+        'ῒ' => "ι\u{308}\u{300}",
+        'ῖ' => "ι\u{342}",
+        'ῗ' => "ι\u{308}\u{342}",
+        'ῢ' => "υ\u{308}\u{300}",
+        'ῤ' => "ρ\u{313}",
+        'ῦ' => "υ\u{342}",
+        'ῧ' => "υ\u{308}\u{342}",
+        'ῲ' => "ὼι",
+        'ῳ' | 'ῼ' => "ωι",
+        'ῴ' => "ώι",
+        'ῶ' => "ω\u{342}",
+        'ῷ' => "ω\u{342}ι",
+        'ﬀ' => "ff",
+        'ﬁ' => "fi",
+        'ﬂ' => "fl",
+        'ﬃ' => "ffi",
+        'ﬄ' => "ffl",
+        'ﬅ' | 'ﬆ' => "st",
+        'ﬓ' => "մն",
+        'ﬔ' => "մե",
+        'ﬕ' => "մի",
+        'ﬖ' => "վն",
+        'ﬗ' => "մխ",
+        _ => return None,
+    })
+}
+
+/// The ASCII replacement of a char, if it has one
+fn ascii_fold(c: char) -> Option<&'static str> {
+    Some(match c {
+        'À' | 'Á' | 'Â' | 'Ã' | 'Ä' | 'Å' | 'Ā' | 'Ă' | 'Ą' => "A",
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' | 'ă' | 'ą' => "a",
+        'Æ' => "AE",
+        'æ' => "ae",
+        'Ç' | 'Ć' | 'Ĉ' | 'Ċ' | 'Č' => "C",
+        'ç' | 'ć' | 'ĉ' | 'ċ' | 'č' => "c",
+        'Ð' | 'Ď' | 'Đ' => "D",
+        'ð' | 'ď' | 'đ' => "d",
+        'È' | 'É' | 'Ê' | 'Ë' | 'Ē' | 'Ĕ' | 'Ė' | 'Ę' | 'Ě' => "E",
+        'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ĕ' | 'ė' | 'ę' | 'ě' => "e",
+        'Ĝ' | 'Ğ' | 'Ġ' | 'Ģ' => "G",
+        'ĝ' | 'ğ' | 'ġ' | 'ģ' => "g",
+        'Ĥ' | 'Ħ' => "H",
+        'ĥ' | 'ħ' => "h",
+        'Ì' | 'Í' | 'Î' | 'Ï' | 'Ĩ' | 'Ī' | 'Ĭ' | 'Į' | 'İ' => "I",
+        'ì' | 'í' | 'î' | 'ï' | 'ĩ' | 'ī' | 'ĭ' | 'į' | 'ı' => "i",
+        'Ĳ' => "IJ",
+        'ĳ' => "ij",
+        'Ĵ' => "J",
+        'ĵ' => "j",
+        'Ķ' => "K",
+        'ķ' | 'ĸ' => "k",
+        'Ĺ' | 'Ļ' | 'Ľ' | 'Ŀ' | 'Ł' => "L",
+        'ĺ' | 'ļ' | 'ľ' | 'ŀ' | 'ł' => "l",
+        'Ñ' | 'Ń' | 'Ņ' | 'Ň' | 'Ŋ' => "N",
+        'ñ' | 'ń' | 'ņ' | 'ň' | 'ŉ' | 'ŋ' => "n",
+        'Ò' | 'Ó' | 'Ô' | 'Õ' | 'Ö' | 'Ø' | 'Ō' | 'Ŏ' | 'Ő' => "O",
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' | 'ŏ' | 'ő' => "o",
+        'Œ' => "OE",
+        'œ' => "oe",
+        'Ŕ' | 'Ŗ' | 'Ř' => "R",
+        'ŕ' | 'ŗ' | 'ř' => "r",
+        'Ś' | 'Ŝ' | 'Ş' | 'Š' => "S",
+        'ś' | 'ŝ' | 'ş' | 'š' | 'ſ' => "s",
+        'ß' => "ss",
+        'Ţ' | 'Ť' | 'Ŧ' => "T",
+        'ţ' | 'ť' | 'ŧ' => "t",
+        'Þ' => "TH",
+        'þ' => "th",
+        'Ù' | 'Ú' | 'Û' | 'Ü' | 'Ũ' | 'Ū' | 'Ŭ' | 'Ů' | 'Ű' | 'Ų' => "U",
+        'ù' | 'ú' | 'û' | 'ü' | 'ũ' | 'ū' | 'ŭ' | 'ů' | 'ű' | 'ų' => "u",
+        'Ŵ' => "W",
+        'ŵ' => "w",
+        'Ý' | 'Ŷ' | 'Ÿ' => "Y",
+        'ý' | 'ÿ' | 'ŷ' => "y",
+        'Ź' | 'Ż' | 'Ž' => "Z",
+        'ź' | 'ż' | 'ž' => "z",
+        '\u{a0}' => " ",
+        '‘' | '’' | '‚' | '′' => "'",
+        '“' | '”' | '„' | '″' | '«' | '»' => "\"",
+        '‐' | '‑' | '‒' | '–' | '—' | '―' => "-",
+        '…' => "...",
+        _ => return None,
+    })
+}
+
+/// A featurizer combinator that normalizes text before featurizing it. Created with `normalized(ftzr, opts)`
+#[derive(Hash, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Normalized<F> {
+    ftzr: F,
+    opts: Normalization,
+}
+
+/// Normalizes `&str` input with `opts` (see [`Normalization`]), then featurizes it with `ftzr`, which can be `n_gram`, `char_gram`, `n_slice`, `word_slice` or `whole`. Nothing is allocated when the input is already normalized.
+///
+/// `n_gram` and `char_gram` produce the same owned tokens as usual. Tokens that would borrow from the input are `Cow<str>` for `word_slice` and `whole`, which borrow when the input didn't change, and can be featurized into `String`, `HashedAs<_>` or the `Cow` itself. `n_slice` produces [`NormalizedSlice`] windows instead, which all share one normalized copy of the input. For a sentence that's already split, use `for_each(normalized(whole(), opts))`.
+/// ```
+/// use creature_feature::convert::Bag;
+/// use creature_feature::ftzrs::misc::Normalization;
+/// use creature_feature::ftzrs::{for_each, normalized, trigram, whole, word_slice};
+/// use creature_feature::traits::Ftzr;
+/// use std::collections::HashMap;
+///
+/// let opts = Normalization::default().lowercase().ascii_fold();
+///
+/// let a: Vec<String> = normalized(trigram(), opts).featurize("Café");
+/// let b: Vec<String> = trigram().featurize("cafe");
+/// assert_eq!(a, b);
+///
+/// let words: Bag<HashMap<String, u8>> = normalized(word_slice(1), opts).featurize("Éte, ETE, été");
+/// assert_eq!(words["ete"], 3);
+///
+/// let sentence = "The the THE".split_whitespace();
+/// let words: Bag<HashMap<String, u8>> = for_each(normalized(whole(), opts)).featurize(sentence);
+/// assert_eq!(words["the"], 3);
+/// ```
+pub fn normalized<F>(ftzr: F, opts: Normalization) -> Normalized<F> {
+    Normalized { ftzr, opts }
+}
+
+impl<'a, const N: usize> Ftzr<&'a str> for Normalized<NGram<N>> {
+    type TokenGroup = [u8; N];
+    fn push_tokens<Push>(&self, origin: &'a str, push: &mut Push)
+    where
+        Push: FnMut(Self::TokenGroup),
+    {
+        self.ftzr
+            .push_tokens(self.opts.normalize(origin).as_ref(), push)
+    }
+}
+
+impl<'a, const N: usize> Ftzr<&'a str> for Normalized<CharGram<N>> {
+    type TokenGroup = [char; N];
+    fn push_tokens<Push>(&self, origin: &'a str, push: &mut Push)
+    where
+        Push: FnMut(Self::TokenGroup),
+    {
+        self.ftzr
+            .push_tokens(self.opts.normalize(origin).as_ref(), push)
+    }
+}
+
+impl<'a> Ftzr<&'a str> for Normalized<SliceGram> {
+    type TokenGroup = NormalizedSlice<'a>;
+    fn push_tokens<Push>(&self, origin: &'a str, push: &mut Push)
+    where
+        Push: FnMut(Self::TokenGroup),
+    {
+        match self.opts.normalize(origin) {
+            Cow::Borrowed(text) => self.ftzr.push_tokens(text, &mut |t: &'a [u8]| {
+                push(NormalizedSlice(SliceBuf::Borrowed(t)))
+            }),
+            Cow::Owned(text) => {
+                let text: Arc<[u8]> = Arc::from(text.into_bytes());
+                let bytes: &[u8] = &text;
+                self.ftzr.push_spans(bytes, 0, &mut |span, _| {
+                    push(NormalizedSlice(SliceBuf::Shared(text.clone(), span)))
+                })
+            }
+        }
+    }
+}
+
+/// The TokenGroup for `normalized(n_slice(n), opts)`, a window of the normalized text. It derefs to `[u8]` and is hashed like `&[u8]`, so `HashedAs` features agree with a plain `n_slice` of the normalized text.
+///
+/// It borrows from the input when normalizing didn't change it. Otherwise the text is normalized once, and every window shares that buffer.
+#[derive(Clone)]
+pub struct NormalizedSlice<'a>(SliceBuf<'a>);
+
+#[derive(Clone)]
+enum SliceBuf<'a> {
+    Borrowed(&'a [u8]),
+    Shared(Arc<[u8]>, Range<usize>),
+}
+
+impl<'a> Deref for NormalizedSlice<'a> {
+    type Target = [u8];
+    fn deref(&self) -> &Self::Target {
+        match &self.0 {
+            SliceBuf::Borrowed(bytes) => bytes,
+            SliceBuf::Shared(text, span) => &text[span.clone()],
+        }
+    }
+}
+
+/// Identical to `Hash for [u8]`
+impl<'a> Hash for NormalizedSlice<'a> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (**self).hash(state)
+    }
+}
+
+impl<'a> PartialEq for NormalizedSlice<'a> {
+    fn eq(&self, other: &Self) -> bool {
+        **self == **other
+    }
+}
+
+impl<'a> Eq for NormalizedSlice<'a> {}
+
+impl<'a> PartialOrd for NormalizedSlice<'a> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'a> Ord for NormalizedSlice<'a> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (**self).cmp(&**other)
+    }
+}
+
+impl<'a> fmt::Debug for NormalizedSlice<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("NormalizedSlice").field(&&**self).finish()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'a> Serialize for NormalizedSlice<'a> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(self)
+    }
+}
+
+impl<'a> FeatureFrom<NormalizedSlice<'a>> for NormalizedSlice<'a> {
+    fn from(token_group: NormalizedSlice<'a>) -> Self {
+        token_group
+    }
+}
+
+impl<'a> FeatureFrom<NormalizedSlice<'a>> for Cow<'a, [u8]> {
+    fn from(token_group: NormalizedSlice<'a>) -> Self {
+        match token_group.0 {
+            SliceBuf::Borrowed(bytes) => Cow::Borrowed(bytes),
+            SliceBuf::Shared(text, span) => Cow::Owned(text[span].to_vec()),
+        }
+    }
+}
+
+impl<'a> FeatureFrom<NormalizedSlice<'a>> for Vec<u8> {
+    fn from(token_group: NormalizedSlice<'a>) -> Self {
+        token_group.to_vec()
+    }
+}
+
+impl<'a> FeatureFrom<NormalizedSlice<'a>> for String {
+    fn from(token_group: NormalizedSlice<'a>) -> Self {
+        FeatureFrom::from(&*token_group)
+    }
+}
+
+impl<'a> FeatureFrom<NormalizedSlice<'a>> for Lossy<String> {
+    fn from(token_group: NormalizedSlice<'a>) -> Self {
+        FeatureFrom::from(&*token_group)
+    }
+}
+
+impl<'a> TryFeatureFrom<NormalizedSlice<'a>> for String {
+    fn try_from(token_group: NormalizedSlice<'a>) -> Result<Self, FeatureError> {
+        TryFeatureFrom::try_from(&*token_group)
+    }
+}
+
+try_via_from! {
+    ['a] NormalizedSlice<'a> => NormalizedSlice<'a>;
+    ['a] NormalizedSlice<'a> => Cow<'a, [u8]>;
+    ['a] NormalizedSlice<'a> => Vec<u8>;
+}
+
+impl<'a, P: Fn(char) -> bool + Clone> Ftzr<&'a str> for Normalized<WordSlice<P>> {
+    type TokenGroup = Cow<'a, str>;
+    fn push_tokens<Push>(&self, origin: &'a str, push: &mut Push)
+    where
+        Push: FnMut(Self::TokenGroup),
+    {
+        match self.opts.normalize(origin) {
+            Cow::Borrowed(text) => self
+                .ftzr
+                .push_tokens(text, &mut |t: &'a str| push(Cow::Borrowed(t))),
+            Cow::Owned(text) => self
+                .ftzr
+                .push_tokens(text.as_str(), &mut |t: &str| push(Cow::Owned(t.to_owned()))),
+        }
+    }
+}
+
+impl<'a> Ftzr<&'a str> for Normalized<Whole> {
+    type TokenGroup = Cow<'a, str>;
+    fn push_tokens<Push>(&self, origin: &'a str, push: &mut Push)
+    where
+        Push: FnMut(Self::TokenGroup),
+    {
+        push(self.opts.normalize(origin))
+    }
+}
+
+impl<'a, F> Ftzr<&'a String> for Normalized<F>
+where
+    Self: Ftzr<&'a str>,
+{
+    type TokenGroup = <Self as Ftzr<&'a str>>::TokenGroup;
+    fn push_tokens<Push>(&self, origin: &'a String, push: &mut Push)
+    where
+        Push: FnMut(Self::TokenGroup),
+    {
+        self.push_tokens(origin.as_str(), push)
+    }
+}
+
+/// The minimum of `ftzr`, measured before normalizing
+impl<F: MinLen> MinLen for Normalized<F> {
+    fn min_len(&self) -> usize {
+        self.ftzr.min_len()
+    }
+}
@@ -0,0 +1,53 @@
+/// The numbers that `sax`, `deltas` and `ratios` work with. The difference of two integers is an `i128`, which holds the difference of any two `u64`s or `i64`s exactly (and lets unsigned types go down), and the difference of two floats is the same float type.
+pub trait Numeric: Copy {
+    /// The type of `next - prev`
+    type Diff: Copy + PartialOrd + Default;
+
+    /// `next - self`
+    fn diff(self, next: Self) -> Self::Diff;
+
+    /// The value as a float, used for ratios and SAX
+    fn to_f64(self) -> f64;
+}
+
+macro_rules! impl_numeric_int {
+    ($($t:ty),*) => {$(
+        impl Numeric for $t {
+            type Diff = i128;
+            #[inline]
+            fn diff(self, next: Self) -> i128 {
+                next as i128 - self as i128
+            }
+            #[inline]
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+        }
+    )*};
+}
+
+impl_numeric_int!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+impl Numeric for f32 {
+    type Diff = f32;
+    #[inline]
+    fn diff(self, next: Self) -> f32 {
+        next - self
+    }
+    #[inline]
+    fn to_f64(self) -> f64 {
+        f64::from(self)
+    }
+}
+
+impl Numeric for f64 {
+    type Diff = f64;
+    #[inline]
+    fn diff(self, next: Self) -> f64 {
+        next - self
+    }
+    #[inline]
+    fn to_f64(self) -> f64 {
+        self
+    }
+}
@@ -0,0 +1,469 @@
+use crate::accum_ftzr::{Ftzr, IterFtzr, MinLen};
+use crate::char_gram::CharGram;
+use crate::convert::Lossy;
+use crate::error::FeatureError;
+use crate::feature_from::{try_via_from, FeatureFrom, TryFeatureFrom, UTF_ERR_MSG};
+use crate::internal::impl_ftrzs;
+use crate::n_gram::NGram;
+use crate::n_slice::SliceGram;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::iter::Copied;
+use std::ops::ControlFlow;
+use std::slice;
+use std::str::{from_utf8, Chars};
+
+/// The start sentinel when a padded feature is rendered as text, like `String`. It's the Unicode noncharacter U+FDD0, which is never part of interchanged text, so a padded `"^a"` can't collide with a real `^a` in the input.
+pub const START_SENTINEL: char = '\u{FDD0}';
+
+/// The end sentinel when a padded feature is rendered as text, like `String`. It's the Unicode noncharacter U+FDD1. (See [`START_SENTINEL`])
+pub const END_SENTINEL: char = '\u{FDD1}';
+
+/// A featurizer combinator that surrounds its input with start and end sentinels, without copying it. Created with `padded(ftzr, pad_left, pad_right)`
+#[derive(Hash, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Padded<F> {
+    ftzr: F,
+    left: usize,
+    right: usize,
+}
+
+/// `padded(ftzr, pad_left, pad_right)` runs `ftzr` as if the input started with `pad_left` start sentinels and ended with `pad_right` end sentinels, so prefixes and suffixes get grams of their own. Nothing is allocated, the padding is virtual. Works with `n_gram`, `n_slice` and `char_gram`. Grams made only of padding are skipped.
+///
+/// `n_gram` and `char_gram` yield `[Pad<T>; N]` and `n_slice` yields a `PaddedSlice<T>`. When converted to `String`, the sentinels are rendered as [`START_SENTINEL`] and [`END_SENTINEL`], two Unicode noncharacters that can't be confused with the text itself.
+/// ```
+/// use creature_feature::ftzrs::misc::Pad;
+/// use creature_feature::ftzrs::{bigram, bislice, char_gram, padded};
+/// use creature_feature::traits::Ftzr;
+///
+/// // for readability, '^' and '$' stand in for the sentinels
+/// let show = |feats: Vec<String>| -> Vec<String> {
+///     feats.iter().map(|f| f.replace('\u{FDD0}', "^").replace('\u{FDD1}', "$")).collect()
+/// };
+///
+/// let feats: Vec<String> = padded(bigram(), 1, 1).featurize("ab");
+/// assert_eq!(show(feats), vec!["^a", "ab", "b$"]);
+///
+/// let feats: Vec<String> = padded(char_gram::<3>(), 2, 2).featurize("été");
+/// assert_eq!(show(feats), vec!["^^é", "^ét", "été", "té$", "é$$"]);
+///
+/// let feats: Vec<[Pad<i32>; 2]> = padded(bigram(), 1, 0).featurize(&[7, 8]);
+/// assert_eq!(feats, vec![[Pad::Start, Pad::Token(7)], [Pad::Token(7), Pad::Token(8)]]);
+///
+/// // a real '^' in the text is a different feature
+/// let feats: Vec<String> = padded(bislice(), 1, 1).featurize("^a");
+/// assert_eq!(feats, vec!["\u{FDD0}^", "^a", "a\u{FDD1}"]);
+/// ```
+pub fn padded<F>(ftzr: F, pad_left: usize, pad_right: usize) -> Padded<F> {
+    Padded {
+        ftzr,
+        left: pad_left,
+        right: pad_right,
+    }
+}
+
+/// A token of padded input: either one of the sentinels or a token of the original input. It's similar to `Option<T>`, but with a different `None` for each end.
+#[derive(Hash, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Pad<T> {
+    /// The start sentinel, rendered as `START_SENTINEL`
+    Start,
+    /// A token of the original input
+    Token(T),
+    /// The end sentinel, rendered as `END_SENTINEL`
+    End,
+}
+
+impl<T> Pad<T> {
+    /// `true` unless it's a sentinel
+    pub fn is_token(&self) -> bool {
+        matches!(self, Pad::Token(_))
+    }
+
+    /// The original token, or `None` for either sentinel
+    pub fn token(self) -> Option<T> {
+        match self {
+            Pad::Token(t) => Some(t),
+            _ => None,
+        }
+    }
+}
+
+/// An iterator that wraps the tokens of `I` in start and end sentinels
+#[derive(Hash, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Debug)]
+pub struct PadIter<I> {
+    left: usize,
+    inner: I,
+    right: usize,
+}
+
+impl<I: Iterator> Iterator for PadIter<I> {
+    type Item = Pad<I::Item>;
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.left > 0 {
+            self.left -= 1;
+            return Some(Pad::Start);
+        }
+        match self.inner.next() {
+            Some(t) => Some(Pad::Token(t)),
+            None if self.right > 0 => {
+                self.right -= 1;
+                Some(Pad::End)
+            }
+            None => None,
+        }
+    }
+}
+
+/// The associated iterator for `<Padded<NGram<N>> as IterFtzr<T>>::Iter` and `<Padded<CharGram<N>> as IterFtzr<T>>::Iter`
+#[derive(Hash, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Debug)]
+pub struct PaddedGramIter<I, T, const N: usize> {
+    primed: bool,
+    window: [Pad<T>; N],
+    tokens: PadIter<I>,
+}
+
+impl<I: Iterator<Item = T>, T: Copy, const N: usize> Iterator for PaddedGramIter<I, T, N> {
+    type Item = [Pad<T>; N];
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if !self.primed {
+                for slot in self.window.iter_mut() {
+                    *slot = self.tokens.next()?;
+                }
+                self.primed = true;
+            } else {
+                let token = self.tokens.next()?;
+                if N > 0 {
+                    self.window.rotate_left(1);
+                    self.window[N - 1] = token;
+                }
+            }
+            if self.window.iter().any(Pad::is_token) {
+                return Some(self.window);
+            }
+        }
+    }
+}
+
+impl<F> Padded<F> {
+    fn pad<I: Iterator<Item = T>, T, const N: usize>(&self, inner: I) -> PaddedGramIter<I, T, N> {
+        PaddedGramIter {
+            primed: false,
+            window: [(); N].map(|_| Pad::Start),
+            tokens: PadIter {
+                left: self.left,
+                inner,
+                right: self.right,
+            },
+        }
+    }
+}
+
+/// Padding stands in for missing tokens, so one real token is enough when the padding covers the rest
+impl<F: MinLen> MinLen for Padded<F> {
+    fn min_len(&self) -> usize {
+        match self.ftzr.min_len() {
+            0 => 0,
+            n => n.saturating_sub(self.left + self.right).max(1),
+        }
+    }
+}
+
+impl<'a, T: Copy, const N: usize> IterFtzr<&'a [T]> for Padded<NGram<N>> {
+    type TokenGroup = [Pad<T>; N];
+    type Iter = PaddedGramIter<Copied<slice::Iter<'a, T>>, T, N>;
+
+    fn iterate_features(&self, origin: &'a [T]) -> Self::Iter {
+        self.pad(origin.iter().copied())
+    }
+}
+
+impl<'a, T: Copy, const N: usize> IterFtzr<&'a Vec<T>> for Padded<NGram<N>> {
+    type TokenGroup = [Pad<T>; N];
+    type Iter = PaddedGramIter<Copied<slice::Iter<'a, T>>, T, N>;
+
+    fn iterate_features(&self, origin: &'a Vec<T>) -> Self::Iter {
+        self.iterate_features(origin.as_slice())
+    }
+}
+
+impl<'a, T: Copy, const N: usize, const M: usize> IterFtzr<&'a [T; M]> for Padded<NGram<N>> {
+    type TokenGroup = [Pad<T>; N];
+    type Iter = PaddedGramIter<Copied<slice::Iter<'a, T>>, T, N>;
+
+    fn iterate_features(&self, origin: &'a [T; M]) -> Self::Iter {
+        self.iterate_features(&origin[..])
+    }
+}
+
+impl<'a, const N: usize> IterFtzr<&'a str> for Padded<NGram<N>> {
+    type TokenGroup = [Pad<u8>; N];
+    type Iter = PaddedGramIter<Copied<slice::Iter<'a, u8>>, u8, N>;
+
+    fn iterate_features(&self, origin: &'a str) -> Self::Iter {
+        self.iterate_features(origin.as_bytes())
+    }
+}
+
+impl<'a, const N: usize> IterFtzr<&'a String> for Padded<NGram<N>> {
+    type TokenGroup = [Pad<u8>; N];
+    type Iter = PaddedGramIter<Copied<slice::Iter<'a, u8>>, u8, N>;
+
+    fn iterate_features(&self, origin: &'a String) -> Self::Iter {
+        self.iterate_features(origin.as_bytes())
+    }
+}
+
+impl<Origin, const N: usize> Ftzr<Origin> for Padded<NGram<N>>
+where
+    Self: IterFtzr<Origin>,
+{
+    type TokenGroup = <Self as IterFtzr<Origin>>::TokenGroup;
+    fn push_tokens<Push>(&self, origin: Origin, push: &mut Push)
+    where
+        Push: FnMut(Self::TokenGroup),
+    {
+        for t in self.iterate_features(origin) {
+            push(t)
+        }
+    }
+
+    fn push_tokens_while<Push>(&self, origin: Origin, push: &mut Push) -> ControlFlow<()>
+    where
+        Push: FnMut(Self::TokenGroup) -> ControlFlow<()>,
+    {
+        self.iterate_features(origin).try_for_each(push)
+    }
+}
+
+impl<'a, const N: usize> IterFtzr<&'a str> for Padded<CharGram<N>> {
+    type TokenGroup = [Pad<char>; N];
+    type Iter = PaddedGramIter<Chars<'a>, char, N>;
+
+    fn iterate_features(&self, origin: &'a str) -> Self::Iter {
+        self.pad(origin.chars())
+    }
+}
+
+impl<'a, const N: usize> IterFtzr<&'a String> for Padded<CharGram<N>> {
+    type TokenGroup = [Pad<char>; N];
+    type Iter = PaddedGramIter<Chars<'a>, char, N>;
+
+    fn iterate_features(&self, origin: &'a String) -> Self::Iter {
+        self.iterate_features(origin.as_str())
+    }
+}
+
+impl<Origin, const N: usize> Ftzr<Origin> for Padded<CharGram<N>>
+where
+    Self: IterFtzr<Origin>,
+{
+    type TokenGroup = <Self as IterFtzr<Origin>>::TokenGroup;
+    fn push_tokens<Push>(&self, origin: Origin, push: &mut Push)
+    where
+        Push: FnMut(Self::TokenGroup),
+    {
+        for t in self.iterate_features(origin) {
+            push(t)
+        }
+    }
+
+    fn push_tokens_while<Push>(&self, origin: Origin, push: &mut Push) -> ControlFlow<()>
+    where
+        Push: FnMut(Self::TokenGroup) -> ControlFlow<()>,
+    {
+        self.iterate_features(origin).try_for_each(push)
+    }
+}
+
+/// The TokenGroup for `Padded<SliceGram>`: a window of padded input, made of `front` start sentinels, a borrowed `body` of the original input, then `back` end sentinels.
+#[derive(Hash, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Debug)]
+pub struct PaddedSlice<'a, T> {
+    /// The number of start sentinels
+    pub front: usize,
+    /// The part of the original input in this window, never empty
+    pub body: &'a [T],
+    /// The number of end sentinels
+    pub back: usize,
+}
+
+/// The associated iterator for `<Padded<SliceGram> as IterFtzr<T>>::Iter`
+#[derive(Hash, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Debug)]
+pub struct PaddedSliceIter<'a, T> {
+    n: usize,
+    left: usize,
+    right: usize,
+    idx: usize,
+    data: &'a [T],
+}
+
+impl<'a, T> Iterator for PaddedSliceIter<'a, T> {
+    type Item = PaddedSlice<'a, T>;
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        // `idx`, `start` and `end` are positions in the padded input
+        let body_end = self.left + self.data.len();
+        loop {
+            let idx = self.idx;
+            if idx + self.n > body_end + self.right {
+                return None;
+            }
+            self.idx += 1;
+            let start = idx.max(self.left);
+            let end = (idx + self.n).min(body_end);
+            if start < end {
+                return Some(PaddedSlice {
+                    front: start - idx,
+                    body: &self.data[start - self.left..end - self.left],
+                    back: idx + self.n - end,
+                });
+            }
+        }
+    }
+}
+
+impl<'a, T> IterFtzr<&'a [T]> for Padded<SliceGram> {
+    type TokenGroup = PaddedSlice<'a, T>;
+    type Iter = PaddedSliceIter<'a, T>;
+
+    fn iterate_features(&self, origin: &'a [T]) -> Self::Iter {
+        PaddedSliceIter {
+            n: self.ftzr.n,
+            left: self.left,
+            right: self.right,
+            idx: 0,
+            data: origin,
+        }
+    }
+}
+
+impl_ftrzs!(Padded<SliceGram>);
+
+fn render_char(token: Pad<char>) -> char {
+    match token {
+        Pad::Start => START_SENTINEL,
+        Pad::Token(c) => c,
+        Pad::End => END_SENTINEL,
+    }
+}
+
+fn push_n(s: &mut String, chr: char, n: usize) {
+    for _ in 0..n {
+        s.push(chr);
+    }
+}
+
+fn render(front: usize, body: &str, back: usize) -> String {
+    let mut s = String::with_capacity(front * 3 + body.len() + back * 3);
+    push_n(&mut s, START_SENTINEL, front);
+    s.push_str(body);
+    push_n(&mut s, END_SENTINEL, back);
+    s
+}
+
+/// The number of start sentinels, the bytes of the original input, and the number of end sentinels
+fn split_bytes(token_group: &[Pad<u8>]) -> (usize, Vec<u8>, usize) {
+    let front = token_group.iter().take_while(|t| **t == Pad::Start).count();
+    let back = token_group
+        .iter()
+        .rev()
+        .take_while(|t| **t == Pad::End)
+        .count();
+    let body = token_group.iter().filter_map(|t| t.token()).collect();
+    (front, body, back)
+}
+
+fn try_render(front: usize, body: &[u8], back: usize) -> Result<String, FeatureError> {
+    match from_utf8(body) {
+        Ok(body) => Ok(render(front, body, back)),
+        Err(e) => Err(FeatureError::InvalidUtf8 {
+            offset: front + e.valid_up_to(),
+        }),
+    }
+}
+
+/// Panics on a window that splits a multi-byte character, like `FeatureFrom<&[u8]> for String`. (Use `Lossy<String>` to get U+FFFD in its place, or `TryFeatureFrom` to get an error)
+impl<const N: usize> FeatureFrom<[Pad<u8>; N]> for String {
+    fn from(token_group: [Pad<u8>; N]) -> Self {
+        let (front, body, back) = split_bytes(&token_group);
+        render(front, from_utf8(&body).expect(UTF_ERR_MSG), back)
+    }
+}
+
+impl<const N: usize> FeatureFrom<[Pad<u8>; N]> for Lossy<String> {
+    fn from(token_group: [Pad<u8>; N]) -> Self {
+        let (front, body, back) = split_bytes(&token_group);
+        Lossy(render(front, &String::from_utf8_lossy(&body), back))
+    }
+}
+
+impl<const N: usize> TryFeatureFrom<[Pad<u8>; N]> for String {
+    fn try_from(token_group: [Pad<u8>; N]) -> Result<Self, FeatureError> {
+        let (front, body, back) = split_bytes(&token_group);
+        try_render(front, &body, back)
+    }
+}
+
+impl<const N: usize> FeatureFrom<[Pad<char>; N]> for String {
+    fn from(token_group: [Pad<char>; N]) -> Self {
+        token_group.iter().copied().map(render_char).collect()
+    }
+}
+
+/// Also panics on a window that splits a multi-byte character, like the `[Pad<u8>; N]` version
+impl<'a> FeatureFrom<PaddedSlice<'a, u8>> for String {
+    fn from(token_group: PaddedSlice<'a, u8>) -> Self {
+        let body = from_utf8(token_group.body).expect(UTF_ERR_MSG);
+        render(token_group.front, body, token_group.back)
+    }
+}
+
+impl<'a> FeatureFrom<PaddedSlice<'a, u8>> for Lossy<String> {
+    fn from(token_group: PaddedSlice<'a, u8>) -> Self {
+        let body = String::from_utf8_lossy(token_group.body);
+        Lossy(render(token_group.front, &body, token_group.back))
+    }
+}
+
+impl<'a> TryFeatureFrom<PaddedSlice<'a, u8>> for String {
+    fn try_from(token_group: PaddedSlice<'a, u8>) -> Result<Self, FeatureError> {
+        try_render(token_group.front, token_group.body, token_group.back)
+    }
+}
+
+impl<'a> FeatureFrom<PaddedSlice<'a, char>> for String {
+    fn from(token_group: PaddedSlice<'a, char>) -> Self {
+        let mut s = String::new();
+        push_n(&mut s, START_SENTINEL, token_group.front);
+        s.extend(token_group.body);
+        push_n(&mut s, END_SENTINEL, token_group.back);
+        s
+    }
+}
+
+impl<'a, T> FeatureFrom<PaddedSlice<'a, T>> for PaddedSlice<'a, T> {
+    fn from(token_group: PaddedSlice<'a, T>) -> Self {
+        token_group
+    }
+}
+
+impl<'a, T: Clone> FeatureFrom<PaddedSlice<'a, T>> for Vec<Pad<T>> {
+    fn from(token_group: PaddedSlice<'a, T>) -> Self {
+        let mut v =
+            Vec::with_capacity(token_group.front + token_group.body.len() + token_group.back);
+        v.extend((0..token_group.front).map(|_| Pad::Start));
+        v.extend(token_group.body.iter().cloned().map(Pad::Token));
+        v.extend((0..token_group.back).map(|_| Pad::End));
+        v
+    }
+}
+
+try_via_from! {
+    [const N: usize] [Pad<char>; N] => String;
+    ['a] PaddedSlice<'a, char> => String;
+    ['a, T] PaddedSlice<'a, T> => PaddedSlice<'a, T>;
+    ['a, T: Clone] PaddedSlice<'a, T> => Vec<Pad<T>>;
+}
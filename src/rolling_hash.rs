@@ -0,0 +1,126 @@
+use crate::accum_ftzr::{Ftzr, IterFtzr, LinearFixed, MinLen};
+use crate::feature_from::{try_via_from, FeatureFrom};
+use crate::hashedfeature::HashedAs;
+use crate::internal::impl_ftrzs;
+use fxhash::hash64;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::hash::Hash;
+
+/// The base of the polynomial used by `rolling_hash` (the 64 bit FNV prime)
+pub const ROLLING_BASE: u64 = 0x100000001b3;
+
+/// A fixed-length n-gram that only produces the hash of each window, updated in O(1) per step. Created with `rolling_hash(n)`
+#[derive(Hash, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RollingHash {
+    n: usize,
+}
+
+/// Rabin-Karp n-grams: the same windows as `n_slice(n)`, but hashed as they go, so long grams cost the same as short ones. Produces `HashedAs<_>` or `u64`.
+///
+/// The hash of a window `w` is `h(w[0])·B^(n-1) + h(w[1])·B^(n-2) + ... + h(w[n-1])`, with wrapping arithmetic (mod 2^64), where `h` is the 64 bit FxHash of a single token and `B` is [`ROLLING_BASE`]. `u64` is that raw value, which is reproducible across versions. `HashedAs<_>` hashes it once more like any other token group, so it's not the same as the `HashedAs` values of `n_slice(n)`. A `n` of `0` produces nothing.
+/// ```
+/// use creature_feature::ftzrs::{n_slice, rolling_hash};
+/// use creature_feature::traits::Ftzr;
+/// use creature_feature::HashedAs;
+///
+/// let text = "the cat sat on the mat";
+///
+/// let hashes: Vec<HashedAs<u64>> = rolling_hash(4).featurize(text);
+/// let grams: Vec<&str> = n_slice(4).featurize(text);
+/// assert_eq!(hashes.len(), grams.len());
+///
+/// // "the " occurs twice
+/// assert_eq!(hashes[0], hashes[15]);
+/// assert_eq!(grams[0], grams[15]);
+/// ```
+pub fn rolling_hash(n: usize) -> RollingHash {
+    RollingHash { n }
+}
+
+/// The token group of `rolling_hash`: the polynomial hash of one window. (See [`rolling_hash`])
+#[derive(Hash, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Rolled(pub u64);
+
+/// The associated iterator for `<RollingHash as IterFtzr<T>>::Iter`
+#[derive(Hash, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Debug)]
+pub struct RollingHashIter<'a, T> {
+    n: usize,
+    idx: usize,
+    hash: u64,
+    // ROLLING_BASE^(n-1), the weight of the token leaving the window
+    top: u64,
+    data: &'a [T],
+}
+
+impl<'a, T: Hash> Iterator for RollingHashIter<'a, T> {
+    type Item = Rolled;
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let jdx = self.idx + self.n;
+        if self.n == 0 || jdx > self.data.len() {
+            return None;
+        }
+        self.hash = if self.idx == 0 {
+            self.data[..jdx].iter().fold(0, |h, t| {
+                h.wrapping_mul(ROLLING_BASE).wrapping_add(hash64(t))
+            })
+        } else {
+            let out = hash64(&self.data[self.idx - 1]).wrapping_mul(self.top);
+            self.hash
+                .wrapping_sub(out)
+                .wrapping_mul(ROLLING_BASE)
+                .wrapping_add(hash64(&self.data[jdx - 1]))
+        };
+        self.idx += 1;
+        Some(Rolled(self.hash))
+    }
+}
+
+impl<'a, T: Hash> IterFtzr<&'a [T]> for RollingHash {
+    type TokenGroup = Rolled;
+    type Iter = RollingHashIter<'a, T>;
+
+    fn iterate_features(&self, origin: &'a [T]) -> Self::Iter {
+        RollingHashIter {
+            n: self.n,
+            idx: 0,
+            hash: 0,
+            top: (1..self.n).fold(1, |b, _| b.wrapping_mul(ROLLING_BASE)),
+            data: origin,
+        }
+    }
+}
+
+impl_ftrzs!(RollingHash);
+
+impl LinearFixed for RollingHash {
+    fn chunk_size(&self) -> usize {
+        self.n
+    }
+}
+
+impl MinLen for RollingHash {
+    fn min_len(&self) -> usize {
+        self.chunk_size()
+    }
+}
+
+impl FeatureFrom<Rolled> for Rolled {
+    fn from(token_group: Rolled) -> Self {
+        token_group
+    }
+}
+
+impl FeatureFrom<Rolled> for u64 {
+    fn from(token_group: Rolled) -> Self {
+        token_group.0
+    }
+}
+
+try_via_from! {
+    [] Rolled => u64;
+    [] Rolled => Rolled;
+}
@@ -0,0 +1,372 @@
+use crate::accum_ftzr::{Accumulates, Ftzr, IterFtzr, MinLen};
+use crate::feature_from::{try_via_from, FeatureFrom};
+use crate::internal::impl_slice_ftrzs;
+use crate::numeric::Numeric;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::ops::Deref;
+
+/// Below this standard deviation, a series is treated as flat and isn't scaled when z-normalized
+const FLAT_STD: f64 = 0.01;
+
+/// Symbolic Aggregate approXimation of a numeric series. Created with `sax(segments, alphabet)`
+#[derive(Hash, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Sax {
+    segments: usize,
+    alphabet: u8,
+}
+
+/// One letter of a SAX word, always a lowercase ASCII letter like `b'a'`. This is the TokenGroup of [`Sax`]: it converts to `u8` or `char`, and a `String` can accumulate it directly.
+#[derive(Hash, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SaxSymbol(pub u8);
+
+/// SAX (Lin, Keogh et al., 2003) turns a time series of any numeric type (`f32`, `f64` or any primitive integer, see [`Numeric`](crate::ftzrs::misc::Numeric)) into a word of `segments` letters.
+///
+/// The series is z-normalized (unless its standard deviation is below `0.01`, in which case it's only centered), reduced to `segments` means by piecewise aggregate approximation (series that don't divide evenly are split fractionally) and each mean is mapped to one of the first `alphabet` lowercase ASCII letters, using breakpoints that cut the standard normal distribution into equally likely regions. `alphabet` is clamped to `1..=26`. Each symbol is a [`SaxSymbol`], which can be collected into a `String` or `Vec<u8>` and fed straight into `n_gram`/`n_slice`, `Bag` or `HashedAs`. Empty input produces nothing, and NaN isn't supported.
+///
+/// To get one word per sliding window instead of one per series, use [`Sax::over`].
+/// ```
+/// use creature_feature::convert::Bag;
+/// use creature_feature::ftzrs::{bigram, sax};
+/// use creature_feature::traits::Ftzr;
+/// use std::collections::HashMap;
+///
+/// let series: Vec<f64> = (0..64).map(|x| (x as f64 / 8.0).sin()).collect();
+///
+/// let word: Vec<u8> = sax(8, 4).featurize(&series);
+/// assert_eq!(word, b"cddbaacd");
+///
+/// let motifs: Bag<HashMap<String, u8>> = bigram().featurize(&word);
+/// assert_eq!(motifs["cd"], 2);
+///
+/// let word = sax(8, 4).featurize::<char, String>(&series);
+/// assert_eq!(word, "cddbaacd");
+///
+/// let counts: Vec<u64> = vec![3, 5, 40, 42, 7, 1];
+/// let word = sax(3, 3).featurize::<char, String>(&counts);
+/// assert_eq!(word, "aca");
+/// ```
+pub fn sax(segments: usize, alphabet: usize) -> Sax {
+    Sax {
+        segments,
+        alphabet: alphabet.clamp(1, 26) as u8,
+    }
+}
+
+impl Sax {
+    /// Run `ftzr` and turn each of its windows (`[T; N]` from `n_gram` or `&[T]` from `n_slice`) into a [`SaxWord`], each window being z-normalized on its own. This is the usual way to find motifs in a long series.
+    /// ```
+    /// use creature_feature::ftzrs::misc::SaxWord;
+    /// use creature_feature::ftzrs::{n_gram, n_slice, sax};
+    /// use creature_feature::traits::Ftzr;
+    ///
+    /// let series = [1, 2, 3, 4, 3, 2, 1, 2, 3, 4];
+    ///
+    /// let words: Vec<String> = sax(2, 2).over(n_gram::<4>()).featurize(&series);
+    /// assert_eq!(words, vec!["ab", "ab", "ba", "ba", "ba", "ab", "ab"]);
+    ///
+    /// let words: Vec<SaxWord> = sax(2, 2).over(n_slice(4)).featurize(&series);
+    /// assert_eq!(&*words[0], "ab");
+    /// ```
+    pub fn over<F>(self, ftzr: F) -> SaxOver<F> {
+        SaxOver { sax: self, ftzr }
+    }
+
+    fn word<W: SaxWindow>(&self, window: W) -> SaxWord {
+        let symbols: SaxIter<'_, W::Value> = self.iterate_features(window.values());
+        SaxWord(symbols.map(|s| s.0 as char).collect())
+    }
+}
+
+/// The associated iterator for `<Sax as IterFtzr<T>>::Iter`
+#[derive(Clone, PartialEq, PartialOrd, Debug)]
+pub struct SaxIter<'a, F> {
+    origin: &'a [F],
+    mean: f64,
+    std: f64,
+    breakpoints: Vec<f64>,
+    width: f64,
+    segment: usize,
+    segments: usize,
+}
+
+impl<'a, F: Numeric> Iterator for SaxIter<'a, F> {
+    type Item = SaxSymbol;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.segment >= self.segments {
+            return None;
+        }
+        // each point covers [i, i + 1), each segment covers [start, end)
+        let j = self.segment;
+        self.segment += 1;
+        let (start, end) = (j as f64 * self.width, (j + 1) as f64 * self.width);
+        let mut sum = 0.0;
+        let mut i = start.floor() as usize;
+        while (i as f64) < end && i < self.origin.len() {
+            let overlap = end.min(i as f64 + 1.0) - start.max(i as f64);
+            sum += overlap * (self.origin[i].to_f64() - self.mean) / self.std;
+            i += 1;
+        }
+        let paa = sum / self.width;
+        let symbol = self.breakpoints.partition_point(|b| *b <= paa);
+        Some(SaxSymbol(b'a' + symbol as u8))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.segments - self.segment;
+        (n, Some(n))
+    }
+}
+
+impl<'a, F: Numeric> IterFtzr<&'a [F]> for Sax {
+    type TokenGroup = SaxSymbol;
+    type Iter = SaxIter<'a, F>;
+
+    fn iterate_features(&self, origin: &'a [F]) -> Self::Iter {
+        let len = origin.len() as f64;
+        let mean = origin.iter().map(|x| x.to_f64()).sum::<f64>() / len;
+        let var = origin
+            .iter()
+            .map(|x| (x.to_f64() - mean).powi(2))
+            .sum::<f64>()
+            / len;
+        let std = if var.sqrt() < FLAT_STD {
+            1.0
+        } else {
+            var.sqrt()
+        };
+        SaxIter {
+            origin,
+            mean,
+            std,
+            breakpoints: (1..self.alphabet)
+                .map(|i| inverse_normal_cdf(i as f64 / self.alphabet as f64))
+                .collect(),
+            width: len / self.segments as f64,
+            segment: 0,
+            segments: if origin.is_empty() { 0 } else { self.segments },
+        }
+    }
+}
+
+impl_slice_ftrzs!(Sax);
+
+/// Any values can be split into `segments`, but empty input produces nothing
+impl MinLen for Sax {
+    fn min_len(&self) -> usize {
+        1
+    }
+}
+
+impl FeatureFrom<SaxSymbol> for SaxSymbol {
+    fn from(token_group: SaxSymbol) -> Self {
+        token_group
+    }
+}
+
+impl FeatureFrom<SaxSymbol> for u8 {
+    fn from(token_group: SaxSymbol) -> Self {
+        token_group.0
+    }
+}
+
+impl FeatureFrom<SaxSymbol> for char {
+    fn from(token_group: SaxSymbol) -> Self {
+        token_group.0 as char
+    }
+}
+
+impl Accumulates<SaxSymbol> for String {
+    type State = Self;
+
+    #[inline]
+    fn accum_token(state: &mut Self::State, token: SaxSymbol) {
+        state.push(token.0 as char)
+    }
+    #[inline]
+    fn finish(state: Self::State) -> Self {
+        state
+    }
+}
+
+/// The SAX word of one window. This is the TokenGroup of [`SaxOver`], and it derefs to `str`
+#[derive(Hash, Clone, PartialEq, Ord, PartialOrd, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SaxWord(pub String);
+
+impl Deref for SaxWord {
+    type Target = str;
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl FeatureFrom<SaxWord> for SaxWord {
+    fn from(token_group: SaxWord) -> Self {
+        token_group
+    }
+}
+
+impl FeatureFrom<SaxWord> for String {
+    fn from(token_group: SaxWord) -> Self {
+        token_group.0
+    }
+}
+
+impl FeatureFrom<SaxWord> for Vec<u8> {
+    fn from(token_group: SaxWord) -> Self {
+        token_group.0.into_bytes()
+    }
+}
+
+try_via_from! {
+    [] SaxSymbol => SaxSymbol;
+    [] SaxSymbol => u8;
+    [] SaxSymbol => char;
+    [] SaxWord => SaxWord;
+    [] SaxWord => String;
+    [] SaxWord => Vec<u8>;
+}
+
+/// A window of numbers that [`Sax::over`] can turn into a word: `[T; N]` (from `n_gram`) or `&[T]` (from `n_slice`)
+pub trait SaxWindow {
+    /// The type of number in the window
+    type Value: Numeric;
+
+    /// The window as a slice
+    fn values(&self) -> &[Self::Value];
+}
+
+impl<T: Numeric, const N: usize> SaxWindow for [T; N] {
+    type Value = T;
+    fn values(&self) -> &[T] {
+        self
+    }
+}
+
+impl<T: Numeric> SaxWindow for &[T] {
+    type Value = T;
+    fn values(&self) -> &[T] {
+        self
+    }
+}
+
+/// A featurizer combinator that turns each window of its inner featurizer into a [`SaxWord`]. Created with [`Sax::over`]
+#[derive(Hash, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SaxOver<F> {
+    sax: Sax,
+    ftzr: F,
+}
+
+impl<Origin, F> Ftzr<Origin> for SaxOver<F>
+where
+    F: Ftzr<Origin>,
+    F::TokenGroup: SaxWindow,
+{
+    type TokenGroup = SaxWord;
+    fn push_tokens<Push>(&self, origin: Origin, push: &mut Push)
+    where
+        Push: FnMut(Self::TokenGroup),
+    {
+        self.ftzr
+            .push_tokens(origin, &mut |window| push(self.sax.word(window)))
+    }
+}
+
+/// The associated iterator for `<SaxOver<F> as IterFtzr<T>>::Iter`
+#[derive(Clone, Debug)]
+pub struct SaxOverIter<I> {
+    sax: Sax,
+    iter: I,
+}
+
+impl<I> Iterator for SaxOverIter<I>
+where
+    I: Iterator,
+    I::Item: SaxWindow,
+{
+    type Item = SaxWord;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|window| self.sax.word(window))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<Origin, F> IterFtzr<Origin> for SaxOver<F>
+where
+    F: IterFtzr<Origin>,
+    F::TokenGroup: SaxWindow,
+{
+    type TokenGroup = SaxWord;
+    type Iter = SaxOverIter<F::Iter>;
+
+    fn iterate_features(&self, origin: Origin) -> Self::Iter {
+        SaxOverIter {
+            sax: self.sax,
+            iter: self.ftzr.iterate_features(origin),
+        }
+    }
+}
+
+/// The quantile function of the standard normal distribution, by Peter Acklam's rational approximation (relative error below 1.15e-9)
+fn inverse_normal_cdf(p: f64) -> f64 {
+    const A: [f64; 6] = [
+        -3.969_683_028_665_376e1,
+        2.209_460_984_245_205e2,
+        -2.759_285_104_469_687e2,
+        1.383_577_518_672_69e2,
+        -3.066_479_806_614_716e1,
+        2.506_628_277_459_239,
+    ];
+    const B: [f64; 5] = [
+        -5.447_609_879_822_406e1,
+        1.615_858_368_580_409e2,
+        -1.556_989_798_598_866e2,
+        6.680_131_188_771_972e1,
+        -1.328_068_155_288_572e1,
+    ];
+    const C: [f64; 6] = [
+        -7.784_894_002_430_293e-3,
+        -3.223_964_580_411_365e-1,
+        -2.400_758_277_161_838,
+        -2.549_732_539_343_734,
+        4.374_664_141_464_968,
+        2.938_163_982_698_783,
+    ];
+    const D: [f64; 4] = [
+        7.784_695_709_041_462e-3,
+        3.224_671_290_700_398e-1,
+        2.445_134_137_142_996,
+        3.754_408_661_907_416,
+    ];
+    const P_LOW: f64 = 0.02425;
+
+    let tail = |q: f64| {
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    };
+    if p < P_LOW {
+        tail((-2.0 * p.ln()).sqrt())
+    } else if p <= 1.0 - P_LOW {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    } else {
+        -tail((-2.0 * (1.0 - p).ln()).sqrt())
+    }
+}
+
+impl<F: MinLen> MinLen for SaxOver<F> {
+    fn min_len(&self) -> usize {
+        self.ftzr.min_len()
+    }
+}
@@ -0,0 +1,75 @@
+use crate::accum_ftzr::{Ftzr, MinLen};
+use crate::gap_gram::GapPair;
+use crate::internal::impl_push_ftrzs;
+use crate::skip_schema::SkipSchema;
+use std::ops::RangeInclusive;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A k-skip-n-gram over referenced data. Created with `skip_gram(group_a, gap, group_b)`
+#[derive(Hash, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SkipGram {
+    schema: SkipSchema,
+}
+
+/// `skip_gram(group_a, gap, group_b)` produces a `GapPair` for every pair of groups separated by a gap, for every length of the first group in `group_a`, every gap in `gap` and every length of the second group in `group_b`. The gap is kept in the `GapPair` (see `GapPair::gap`), so the same two groups at different distances are different features.
+///
+/// Unlike `gap_gram`, the input is only featurized once no matter how many gaps are allowed. A gap of `0` means the two groups are adjacent.
+///
+/// Pairs are visited in order of where the first group starts, then by the length of the first group, then by the gap, then by the length of the second group.
+/// ```
+/// use creature_feature::ftzrs::skip_gram;
+/// use creature_feature::traits::Ftzr;
+///
+/// // classic 2-skip-bigrams
+/// let ftzr = skip_gram(1..=1, 0..=2, 1..=1);
+///
+/// let feats: Vec<(&str, &str)> = ftzr.featurize("abcd");
+/// assert_eq!(
+///     feats,
+///     vec![("a", "b"), ("a", "c"), ("a", "d"), ("b", "c"), ("b", "d"), ("c", "d")]
+/// );
+///
+/// let feats: Vec<(&str, usize, &str)> = ftzr.featurize("abcd");
+/// assert_eq!(feats[..3], [("a", 0, "b"), ("a", 1, "c"), ("a", 2, "d")]);
+/// ```
+pub fn skip_gram(
+    group_a: RangeInclusive<usize>,
+    gap: RangeInclusive<usize>,
+    group_b: RangeInclusive<usize>,
+) -> SkipGram {
+    SkipGram {
+        schema: SkipSchema {
+            group_a: group_a.into_inner(),
+            gap: gap.into_inner(),
+            group_b: group_b.into_inner(),
+        },
+    }
+}
+
+impl<'a, T> Ftzr<&'a [T]> for SkipGram {
+    type TokenGroup = GapPair<&'a [T], &'a [T]>;
+    #[inline]
+    fn push_tokens<Push>(&self, origin: &'a [T], push: &mut Push)
+    where
+        Push: FnMut(Self::TokenGroup),
+    {
+        self.schema.push_pairs(origin, self.schema.gap.0, push)
+    }
+}
+
+impl_push_ftrzs!(SkipGram);
+
+/// The widest pair: the longest groups with the longest gap
+impl MinLen for SkipGram {
+    fn min_len(&self) -> usize {
+        let SkipSchema {
+            group_a,
+            gap,
+            group_b,
+        } = self.schema;
+        group_a.1 + gap.1 + group_b.1
+    }
+}
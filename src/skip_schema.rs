@@ -14,69 +14,86 @@ pub(crate) struct SkipSchema {
     pub(crate) group_b: (usize, usize),
 }
 
-impl<'a, T> Ftzr<&'a [T]> for SkipSchema {
-    type TokenGroup = EitherGroup<&'a [T], GapPair<&'a [T], &'a [T]>>;
+impl SkipSchema {
+    /// visits every `GapPair` whose group lengths and gap lie in their ranges. A gap of zero is only included if `min_gap` is zero.
     #[inline]
-    fn push_tokens<Push>(&self, origin: &'a [T], push: &mut Push)
+    pub(crate) fn push_pairs<'a, T, Push>(&self, s: &'a [T], min_gap: usize, push: &mut Push)
     where
-        Push: FnMut(Self::TokenGroup) -> (),
+        Push: FnMut(GapPair<&'a [T], &'a [T]>),
     {
-        let s = origin;
-        let min = self.group_a.0 + self.gap.0 + self.group_b.0;
-        //println!("{:?}", &s[grp_a_1..grp_a_2]);
+        let min = self.group_a.0 + min_gap + self.group_b.0;
         if s.len() < min {
-            return ();
+            return;
         };
-        if self.gap.1 > 0 {
-            let min_gap = cmp::max(1, self.gap.0);
-            for x in 0..(s.len() - min + 1) {
-                for grp_a_idx in (x + self.group_a.0)..(x + self.group_a.1 + 1) {
-                    if grp_a_idx > s.len() {
-                        break;
-                    }
-                    //if x != grp_a_idx {
-                    //    println!("ga: {:?}", ((x, grp_a_idx), &s[x..grp_a_idx]));
-                    //};
-                    let group_a = &s[x..grp_a_idx];
-                    for space_idx in (grp_a_idx + min_gap)..(grp_a_idx + self.gap.1 + 1) {
-                        for grp_b_idx in
-                            (space_idx + self.group_b.0)..(space_idx + self.group_b.1 + 1)
-                        {
-                            if grp_b_idx > s.len() {
-                                break;
-                            }
+        for x in 0..(s.len() - min + 1) {
+            for grp_a_idx in (x + self.group_a.0)..(x + self.group_a.1 + 1) {
+                if grp_a_idx > s.len() {
+                    break;
+                }
+                let group_a = &s[x..grp_a_idx];
+                for space_idx in (grp_a_idx + min_gap)..(grp_a_idx + self.gap.1 + 1) {
+                    for grp_b_idx in (space_idx + self.group_b.0)..(space_idx + self.group_b.1 + 1)
+                    {
+                        if grp_b_idx > s.len() {
+                            break;
+                        }
 
-                            let group_b = &s[space_idx..grp_b_idx];
-                            //let mut hasher: FxHasher64 = Default::default();
+                        let group_b = &s[space_idx..grp_b_idx];
 
-                            if group_a.len() != 0 && group_b.len() != 0 {
-                                push(EitherGroup::Right(GapPair(
-                                    group_a,
-                                    group_b,
-                                    (space_idx - grp_a_idx) as u16,
-                                )))
-                            }
+                        if !group_a.is_empty() && !group_b.is_empty() {
+                            push(GapPair(group_a, group_b, space_idx - grp_a_idx))
                         }
                     }
                 }
             }
         }
+    }
 
-        if self.gap.0 == 0 {
-            let a = self.group_a.0 + self.group_b.0;
-            let b = self.group_a.1 + self.group_b.1;
-            for x in 0..(s.len() - a + 1) {
-                for _y in a..(b + 1) {
-                    let y = x + _y;
+    /// visits every contiguous window whose length lies in `group_a.0 + group_b.0 ..= group_a.1 + group_b.1`
+    #[inline]
+    pub(crate) fn push_windows<'a, T, Push>(&self, s: &'a [T], push: &mut Push)
+    where
+        Push: FnMut(&'a [T]),
+    {
+        let a = self.group_a.0 + self.group_b.0;
+        let b = self.group_a.1 + self.group_b.1;
+        if s.len() < a {
+            return;
+        };
+        for x in 0..(s.len() - a + 1) {
+            for _y in a..(b + 1) {
+                let y = x + _y;
 
-                    if y > s.len() {
-                        break;
-                    }
-                    if x != y {
-                        push(EitherGroup::Left(&s[x..y]));
-                    };
+                if y > s.len() {
+                    break;
                 }
+                if x != y {
+                    push(&s[x..y]);
+                };
             }
         }
     }
 }
+
+impl<'a, T> Ftzr<&'a [T]> for SkipSchema {
+    type TokenGroup = EitherGroup<&'a [T], GapPair<&'a [T], &'a [T]>>;
+    #[inline]
+    fn push_tokens<Push>(&self, origin: &'a [T], push: &mut Push)
+    where
+        Push: FnMut(Self::TokenGroup),
+    {
+        let s = origin;
+        let min = self.group_a.0 + self.gap.0 + self.group_b.0;
+        if s.len() < min {
+            return;
+        };
+        if self.gap.1 > 0 {
+            let min_gap = cmp::max(1, self.gap.0);
+            self.push_pairs(s, min_gap, &mut |pair| push(EitherGroup::Right(pair)));
+        }
+
+        if self.gap.0 == 0 {
+            self.push_windows(s, &mut |window| push(EitherGroup::Left(window)));
+        }
+    }
+}
@@ -0,0 +1,70 @@
+use crate::accum_ftzr::{Ftzr, LinearFixed, MinLen, SpanFtzr};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::ops::Range;
+
+/// A featurizer combinator that pairs every group of tokens with where it came from. Created with `with_spans(ftzr)`
+#[derive(Hash, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct WithSpans<F>(F);
+
+/// `with_spans(ftzr)` yields `(span, token_group)` for each token group of `ftzr`, where the span is a `Range<usize>` of indices into the input (byte offsets for `&str`). For `gap_gram` it's a pair of ranges, one per group. Works with `n_slice`, `n_gram`, `gap_gram`, `bookends` and `for_each`. (See [`crate::traits::SpanFtzr`])
+///
+/// With `for_each`, spans are relative to the whole sentence, not each word. Each word is paired with its offset into the sentence, as with `word_indices(text)`.
+/// ```
+/// use creature_feature::ftzrs::{bislice, bookends, for_each, trigram, with_spans, word_indices};
+/// use creature_feature::ftzrs::misc::FrontBack;
+/// use creature_feature::traits::Ftzr;
+/// use std::ops::Range;
+///
+/// let text = "one fish";
+///
+/// let feats: Vec<(Range<usize>, &str)> = with_spans(bislice()).featurize(text);
+/// assert_eq!(feats[..2], [(0..2, "on"), (1..3, "ne")]);
+///
+/// let suffixes: Vec<(Range<usize>, FrontBack<String, String>)> =
+///     with_spans(bookends((trigram(), 3), (trigram(), 4))).featurize(text);
+/// assert_eq!(suffixes[2], (5..8, FrontBack::Back("ish".to_string())));
+///
+/// let feats: Vec<(Range<usize>, &str)> =
+///     with_spans(for_each(bislice())).featurize(word_indices(text));
+/// assert_eq!(feats[2], (4..6, "fi"));
+/// assert_eq!(&text[feats[2].0.clone()], "fi");
+/// ```
+pub fn with_spans<F>(ftzr: F) -> WithSpans<F> {
+    WithSpans(ftzr)
+}
+
+impl<Origin, F: SpanFtzr<Origin>> Ftzr<Origin> for WithSpans<F> {
+    type TokenGroup = (F::Span, F::TokenGroup);
+    fn push_tokens<Push>(&self, origin: Origin, push: &mut Push)
+    where
+        Push: FnMut(Self::TokenGroup),
+    {
+        self.0.push_spans(origin, 0, &mut |span, t| push((span, t)))
+    }
+}
+
+impl<F: MinLen> MinLen for WithSpans<F> {
+    fn min_len(&self) -> usize {
+        self.0.min_len()
+    }
+}
+
+/// `push_spans` for featurizers that advance one token at a time, like `NGram<N>` and `SliceGram`
+pub(crate) fn push_linear_spans<Origin, F, Push>(
+    ftzr: &F,
+    origin: Origin,
+    offset: usize,
+    push: &mut Push,
+) where
+    F: Ftzr<Origin> + LinearFixed,
+    Push: FnMut(Range<usize>, F::TokenGroup),
+{
+    let n = ftzr.chunk_size();
+    let mut idx = offset;
+    ftzr.push_tokens(origin, &mut |t| {
+        push(idx..idx + n, t);
+        idx += 1;
+    })
+}
@@ -0,0 +1,506 @@
+use crate::accum_ftzr::{Ftzr, MinLen};
+use crate::convert::Lossy;
+use crate::error::FeatureError;
+use crate::feature_from::{try_from_utf8, try_via_from, FeatureFrom, TryFeatureFrom};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::hash::{Hash, Hasher};
+use std::str::from_utf8;
+
+/// A featurizer combinator that stems every word produced by another featurizer. Created with `stemmed(ftzr)`
+#[derive(Hash, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Stemmed<F>(F);
+
+/// Maps each word token of `ftzr` to its English stem with the Porter2 (Snowball English) stemmer, so that "fish", "fishing" and "fished" are all counted as "fish". Works with any featurizer whose tokens are whole words as `&str`, `&[u8]`, `Cow<str>` or `String`, like `word_slice(1)` or `for_each(whole())`, and each word of a `[&str; N]` from `word_gram`.
+///
+/// Stems of `&str` tokens are `Cow<str>`, which only allocates when the word changes. They can be featurized into `String`, `Cow<str>` or `HashedAs<_>` (with the same hash as the `&str`). Stems of `&[u8]` tokens are a [`ByteStem`], which is featurized like the bytes it came from. Words are expected to be lowercase, and words that aren't ASCII (or aren't UTF-8) are left as they are.
+/// ```
+/// use creature_feature::convert::Bag;
+/// use creature_feature::ftzrs::{stemmed, word_slice};
+/// use creature_feature::traits::Ftzr;
+/// use std::borrow::Cow;
+/// use std::collections::HashMap;
+///
+/// let text = "the fish fished, fishing for fishes";
+///
+/// let bag: Bag<HashMap<String, u8>> = stemmed(word_slice(1)).featurize(text);
+/// assert_eq!(bag["fish"], 4);
+///
+/// let stems: Vec<Cow<str>> = stemmed(word_slice(1)).featurize("generously consolidated");
+/// assert_eq!(stems, vec!["generous", "consolid"]);
+/// ```
+pub fn stemmed<F>(ftzr: F) -> Stemmed<F> {
+    Stemmed(ftzr)
+}
+
+/// Word tokens that can be stemmed by `stemmed`
+pub trait Stemmable {
+    /// The type of the stem
+    type Stem;
+
+    /// The Porter2 stem of the word
+    fn stem(self) -> Self::Stem;
+}
+
+impl<'a> Stemmable for &'a str {
+    type Stem = Cow<'a, str>;
+    fn stem(self) -> Self::Stem {
+        porter2(self)
+    }
+}
+
+impl<'a> Stemmable for &'a [u8] {
+    type Stem = ByteStem<'a>;
+    fn stem(self) -> Self::Stem {
+        match from_utf8(self) {
+            Ok(word) => ByteStem::Text(porter2(word)),
+            Err(_) => ByteStem::Bytes(self),
+        }
+    }
+}
+
+impl<'a> Stemmable for Cow<'a, str> {
+    type Stem = Cow<'a, str>;
+    fn stem(self) -> Self::Stem {
+        match self {
+            Cow::Borrowed(word) => porter2(word),
+            Cow::Owned(word) => Cow::Owned(word.stem()),
+        }
+    }
+}
+
+impl Stemmable for String {
+    type Stem = String;
+    fn stem(self) -> Self::Stem {
+        match porter2(&self) {
+            Cow::Borrowed(_) => self,
+            Cow::Owned(stem) => stem,
+        }
+    }
+}
+
+impl<'a, const N: usize> Stemmable for [&'a str; N] {
+    type Stem = [Cow<'a, str>; N];
+    fn stem(self) -> Self::Stem {
+        self.map(porter2)
+    }
+}
+
+/// The stem of a `&[u8]` word. Words that aren't UTF-8 can't be stemmed, and are kept as they are.
+///
+/// It's featurized like the `&[u8]` it came from: into `Vec<u8>` or `HashedAs<_>` (with the same hash as the bytes), or into `String` and `Cow<str>`, which panic on bytes that aren't UTF-8. Use `Lossy<String>` to get U+FFFD in their place, or `Ftzr::try_featurize` to get an error.
+#[derive(Clone, PartialEq, Ord, PartialOrd, Eq, Debug)]
+pub enum ByteStem<'a> {
+    /// The stem of a UTF-8 word
+    Text(Cow<'a, str>),
+    /// A word that isn't UTF-8
+    Bytes(&'a [u8]),
+}
+
+impl<'a> ByteStem<'a> {
+    /// The bytes of the stem
+    pub fn as_bytes(&self) -> &[u8] {
+        match self {
+            ByteStem::Text(stem) => stem.as_bytes(),
+            ByteStem::Bytes(word) => word,
+        }
+    }
+}
+
+/// Identical to `Hash for [u8]`
+impl<'a> Hash for ByteStem<'a> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_bytes().hash(state)
+    }
+}
+
+impl<'a> FeatureFrom<ByteStem<'a>> for ByteStem<'a> {
+    fn from(token_group: ByteStem<'a>) -> Self {
+        token_group
+    }
+}
+
+impl<'a> FeatureFrom<ByteStem<'a>> for Vec<u8> {
+    fn from(token_group: ByteStem<'a>) -> Self {
+        token_group.as_bytes().to_vec()
+    }
+}
+
+impl<'a> FeatureFrom<ByteStem<'a>> for Cow<'a, str> {
+    fn from(token_group: ByteStem<'a>) -> Self {
+        match token_group {
+            ByteStem::Text(stem) => stem,
+            ByteStem::Bytes(word) => Cow::Borrowed(FeatureFrom::from(word)),
+        }
+    }
+}
+
+impl<'a> FeatureFrom<ByteStem<'a>> for String {
+    fn from(token_group: ByteStem<'a>) -> Self {
+        <Cow<str> as FeatureFrom<_>>::from(token_group).into_owned()
+    }
+}
+
+impl<'a> FeatureFrom<ByteStem<'a>> for Lossy<String> {
+    fn from(token_group: ByteStem<'a>) -> Self {
+        FeatureFrom::from(token_group.as_bytes())
+    }
+}
+
+impl<'a> TryFeatureFrom<ByteStem<'a>> for Cow<'a, str> {
+    fn try_from(token_group: ByteStem<'a>) -> Result<Self, FeatureError> {
+        match token_group {
+            ByteStem::Text(stem) => Ok(stem),
+            ByteStem::Bytes(word) => try_from_utf8(word).map(Cow::Borrowed),
+        }
+    }
+}
+
+impl<'a> TryFeatureFrom<ByteStem<'a>> for String {
+    fn try_from(token_group: ByteStem<'a>) -> Result<Self, FeatureError> {
+        <Cow<str> as TryFeatureFrom<_>>::try_from(token_group).map(Cow::into_owned)
+    }
+}
+
+try_via_from! {
+    ['a] ByteStem<'a> => ByteStem<'a>;
+    ['a] ByteStem<'a> => Vec<u8>;
+}
+
+impl<Origin, F> Ftzr<Origin> for Stemmed<F>
+where
+    F: Ftzr<Origin>,
+    F::TokenGroup: Stemmable,
+{
+    type TokenGroup = <F::TokenGroup as Stemmable>::Stem;
+    fn push_tokens<Push>(&self, origin: Origin, push: &mut Push)
+    where
+        Push: FnMut(Self::TokenGroup),
+    {
+        self.0.push_tokens(origin, &mut |word| push(word.stem()))
+    }
+}
+
+impl<F: MinLen> MinLen for Stemmed<F> {
+    fn min_len(&self) -> usize {
+        self.0.min_len()
+    }
+}
+
+/// Words with a fixed stem
+const EXCEPTIONS: [(&str, &str); 18] = [
+    ("skis", "ski"),
+    ("skies", "sky"),
+    ("dying", "die"),
+    ("lying", "lie"),
+    ("tying", "tie"),
+    ("idly", "idl"),
+    ("gently", "gentl"),
+    ("ugly", "ugli"),
+    ("early", "earli"),
+    ("only", "onli"),
+    ("singly", "singl"),
+    ("sky", "sky"),
+    ("news", "news"),
+    ("howe", "howe"),
+    ("atlas", "atlas"),
+    ("cosmos", "cosmos"),
+    ("bias", "bias"),
+    ("andes", "andes"),
+];
+
+/// Words that are left alone once step 1a is done
+const INVARIANT_AFTER_1A: [&str; 8] = [
+    "inning", "outing", "canning", "herring", "earring", "proceed", "exceed", "succeed",
+];
+
+/// Prefixes that are R1 by themselves
+const R1_PREFIXES: [&str; 3] = ["gener", "commun", "arsen"];
+
+const STEP_2: [(&str, &str); 24] = [
+    ("ization", "ize"),
+    ("ational", "ate"),
+    ("fulness", "ful"),
+    ("ousness", "ous"),
+    ("iveness", "ive"),
+    ("tional", "tion"),
+    ("biliti", "ble"),
+    ("lessli", "less"),
+    ("entli", "ent"),
+    ("ation", "ate"),
+    ("alism", "al"),
+    ("aliti", "al"),
+    ("ousli", "ous"),
+    ("iviti", "ive"),
+    ("fulli", "ful"),
+    ("enci", "ence"),
+    ("anci", "ance"),
+    ("abli", "able"),
+    ("izer", "ize"),
+    ("ator", "ate"),
+    ("alli", "al"),
+    ("bli", "ble"),
+    ("ogi", "og"),
+    ("li", ""),
+];
+
+const STEP_3: [(&str, &str); 9] = [
+    ("ational", "ate"),
+    ("tional", "tion"),
+    ("alize", "al"),
+    ("icate", "ic"),
+    ("iciti", "ic"),
+    ("ative", ""),
+    ("ical", "ic"),
+    ("ness", ""),
+    ("ful", ""),
+];
+
+const STEP_4: [&str; 18] = [
+    "ement", "ance", "ence", "able", "ible", "ment", "ant", "ent", "ism", "ate", "iti", "ous",
+    "ive", "ize", "ion", "al", "er", "ic",
+];
+
+/// The Porter2 stem of a lowercase English word, borrowing `word` if it's unchanged. (See [`stemmed`])
+pub fn porter2(word: &str) -> Cow<'_, str> {
+    if word.len() <= 2 || !word.is_ascii() {
+        return Cow::Borrowed(word);
+    }
+    if let Some((_, stem)) = EXCEPTIONS.iter().find(|(w, _)| *w == word) {
+        return if *stem == word {
+            Cow::Borrowed(word)
+        } else {
+            Cow::Owned((*stem).to_owned())
+        };
+    }
+    let mut stemmer = Porter2::new(word.strip_prefix('\'').unwrap_or(word));
+    stemmer.run();
+    let stem = stemmer.word;
+    if stem == word.as_bytes() {
+        Cow::Borrowed(word)
+    } else {
+        // `word` is ASCII, and the stemmer only ever writes ASCII
+        Cow::Owned(
+            String::from_utf8(stem)
+                .unwrap_or_else(|_| unreachable!("the Porter2 stem of an ASCII word is ASCII")),
+        )
+    }
+}
+
+fn is_vowel(c: u8) -> bool {
+    matches!(c, b'a' | b'e' | b'i' | b'o' | b'u' | b'y')
+}
+
+fn is_double(w: &[u8]) -> bool {
+    match w {
+        [.., a, b] => {
+            a == b
+                && matches!(
+                    a,
+                    b'b' | b'd' | b'f' | b'g' | b'm' | b'n' | b'p' | b'r' | b't'
+                )
+        }
+        _ => false,
+    }
+}
+
+fn is_li_ending(c: u8) -> bool {
+    matches!(
+        c,
+        b'c' | b'd' | b'e' | b'g' | b'h' | b'k' | b'm' | b'n' | b'r' | b't'
+    )
+}
+
+fn has_vowel(w: &[u8]) -> bool {
+    w.iter().any(|c| is_vowel(*c))
+}
+
+/// Whether `w` ends with a non-vowel, vowel, non-vowel (other than `w`, `x` or `Y`), or is only a vowel and a non-vowel
+fn ends_short_syllable(w: &[u8]) -> bool {
+    match w {
+        [a, b] => is_vowel(*a) && !is_vowel(*b),
+        [.., a, b, c] => {
+            !is_vowel(*a) && is_vowel(*b) && !is_vowel(*c) && !matches!(c, b'w' | b'x' | b'Y')
+        }
+        _ => false,
+    }
+}
+
+/// The start of the region after the first non-vowel that follows a vowel, looking from `start`
+fn region_after(w: &[u8], start: usize) -> usize {
+    (start + 1..w.len())
+        .find(|i| is_vowel(w[i - 1]) && !is_vowel(w[*i]))
+        .map_or(w.len(), |i| i + 1)
+}
+
+struct Porter2 {
+    // 'y' that acts as a consonant is marked as 'Y'
+    word: Vec<u8>,
+    r1: usize,
+    r2: usize,
+}
+
+impl Porter2 {
+    fn new(word: &str) -> Self {
+        let mut w = word.as_bytes().to_vec();
+        for i in 0..w.len() {
+            if w[i] == b'y' && (i == 0 || is_vowel(w[i - 1])) {
+                w[i] = b'Y';
+            }
+        }
+        let r1 = R1_PREFIXES
+            .iter()
+            .find(|p| w.starts_with(p.as_bytes()))
+            .map_or_else(|| region_after(&w, 0), |p| p.len());
+        let r2 = region_after(&w, r1);
+        Porter2 { word: w, r1, r2 }
+    }
+
+    fn ends(&self, suffix: &str) -> bool {
+        self.word.ends_with(suffix.as_bytes())
+    }
+
+    /// Where `suffix` would start
+    fn start_of(&self, suffix: &str) -> usize {
+        self.word.len() - suffix.len()
+    }
+
+    fn replace(&mut self, suffix: &str, with: &str) {
+        let start = self.start_of(suffix);
+        self.word.truncate(start);
+        self.word.extend_from_slice(with.as_bytes());
+    }
+
+    fn is_short(&self) -> bool {
+        self.r1 >= self.word.len() && ends_short_syllable(&self.word)
+    }
+
+    fn run(&mut self) {
+        self.step_0();
+        self.step_1a();
+        if INVARIANT_AFTER_1A
+            .iter()
+            .any(|w| w.as_bytes() == self.word.as_slice())
+        {
+            return;
+        }
+        self.step_1b();
+        self.step_1c();
+        self.step_2();
+        self.step_3();
+        self.step_4();
+        self.step_5();
+        for c in self.word.iter_mut() {
+            if *c == b'Y' {
+                *c = b'y';
+            }
+        }
+    }
+
+    fn step_0(&mut self) {
+        if let Some(suffix) = ["'s'", "'s", "'"].iter().find(|s| self.ends(s)) {
+            self.replace(suffix, "");
+        }
+    }
+
+    fn step_1a(&mut self) {
+        if self.ends("sses") {
+            self.replace("sses", "ss");
+        } else if self.ends("ied") || self.ends("ies") {
+            // "ties" -> "tie", but "cries" -> "cri"
+            let with = if self.word.len() > 4 { "i" } else { "ie" };
+            self.replace("ies", with);
+        } else if self.ends("s")
+            && !self.ends("us")
+            && !self.ends("ss")
+            && has_vowel(&self.word[..self.word.len().saturating_sub(2)])
+        {
+            self.replace("s", "");
+        }
+    }
+
+    fn step_1b(&mut self) {
+        let suffix = match ["eedly", "ingly", "edly", "eed", "ing", "ed"]
+            .iter()
+            .find(|s| self.ends(s))
+        {
+            Some(suffix) => *suffix,
+            None => return,
+        };
+        if suffix.starts_with("eed") {
+            if self.start_of(suffix) >= self.r1 {
+                self.replace(suffix, "ee");
+            }
+        } else if has_vowel(&self.word[..self.start_of(suffix)]) {
+            self.replace(suffix, "");
+            if self.ends("at") || self.ends("bl") || self.ends("iz") {
+                self.word.push(b'e');
+            } else if is_double(&self.word) {
+                self.word.pop();
+            } else if self.is_short() {
+                self.word.push(b'e');
+            }
+        }
+    }
+
+    fn step_1c(&mut self) {
+        let len = self.word.len();
+        if len > 2 && matches!(self.word[len - 1], b'y' | b'Y') && !is_vowel(self.word[len - 2]) {
+            self.word[len - 1] = b'i';
+        }
+    }
+
+    fn step_2(&mut self) {
+        if let Some((suffix, with)) = STEP_2.iter().find(|(s, _)| self.ends(s)) {
+            let start = self.start_of(suffix);
+            let before = start.checked_sub(1).map(|i| self.word[i]);
+            let allowed = match *suffix {
+                "ogi" => before == Some(b'l'),
+                "li" => before.is_some_and(is_li_ending),
+                _ => true,
+            };
+            if start >= self.r1 && allowed {
+                self.replace(suffix, with);
+            }
+        }
+    }
+
+    fn step_3(&mut self) {
+        if let Some((suffix, with)) = STEP_3.iter().find(|(s, _)| self.ends(s)) {
+            let start = self.start_of(suffix);
+            let region = if *suffix == "ative" { self.r2 } else { self.r1 };
+            if start >= region {
+                self.replace(suffix, with);
+            }
+        }
+    }
+
+    fn step_4(&mut self) {
+        if let Some(suffix) = STEP_4.iter().find(|s| self.ends(s)) {
+            let start = self.start_of(suffix);
+            let allowed = *suffix != "ion"
+                || start
+                    .checked_sub(1)
+                    .is_some_and(|i| matches!(self.word[i], b's' | b't'));
+            if start >= self.r2 && allowed {
+                self.replace(suffix, "");
+            }
+        }
+    }
+
+    fn step_5(&mut self) {
+        let start = match self.word.len().checked_sub(1) {
+            Some(start) => start,
+            None => return,
+        };
+        if self.ends("e") {
+            if start >= self.r2 || (start >= self.r1 && !ends_short_syllable(&self.word[..start])) {
+                self.word.pop();
+            }
+        } else if self.ends("ll") && start >= self.r2 {
+            self.word.pop();
+        }
+    }
+}
@@ -0,0 +1,261 @@
+use crate::accum_ftzr::{Ftzr, MinLen};
+use crate::word_gram::is_word_delimiter;
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+
+/// A set of stopwords, which is cheap to clone. Created with `Stopwords::english()` (or another built-in list), `Stopwords::from_words` or `Stopwords::from_file`
+///
+/// The built-in lists are lowercase and based on the Snowball stopword lists. Words are compared exactly, so it's best to lowercase first (see `normalized`).
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct Stopwords(Arc<HashSet<String>>);
+
+impl Stopwords {
+    /// Common English function words, like "the", "and" and "isn't"
+    pub fn english() -> Self {
+        Stopwords::from_words(ENGLISH.split_whitespace())
+    }
+
+    /// Common French function words, like "le", "et" and "été"
+    pub fn french() -> Self {
+        Stopwords::from_words(FRENCH.split_whitespace())
+    }
+
+    /// Common German function words, like "der", "und" and "für"
+    pub fn german() -> Self {
+        Stopwords::from_words(GERMAN.split_whitespace())
+    }
+
+    /// Common Spanish function words, like "el", "y" and "está"
+    pub fn spanish() -> Self {
+        Stopwords::from_words(SPANISH.split_whitespace())
+    }
+
+    /// Any words, like a `HashSet<&str>` or `Vec<String>`
+    pub fn from_words<I, S>(words: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Stopwords(Arc::new(
+            words.into_iter().map(|w| w.as_ref().to_owned()).collect(),
+        ))
+    }
+
+    /// Reads a file with one word per line. Blank lines, and lines starting with `#`, are skipped.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let text = fs::read_to_string(path)?;
+        Ok(Stopwords::from_words(
+            text.lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with('#')),
+        ))
+    }
+
+    /// All the words of both sets, like for multilingual text
+    pub fn union(&self, other: &Stopwords) -> Self {
+        Stopwords::from_words(self.0.union(&other.0))
+    }
+
+    /// Whether `word` is a stopword
+    pub fn contains(&self, word: &str) -> bool {
+        self.0.contains(word)
+    }
+
+    /// The number of stopwords
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether there are no stopwords
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+/// A featurizer combinator that drops stopwords from text before featurizing the remaining words. Created with `without_stopwords(ftzr, stopwords)`
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct WithoutStopwords<F, P = fn(char) -> bool> {
+    ftzr: F,
+    stopwords: Stopwords,
+    is_delim: P,
+}
+
+/// Splits text into words (like `word_gram`), drops the words that are in `stopwords`, and featurizes the remaining words with `ftzr`. So the n-grams of `word_gram` and `n_gram` skip over stopwords, and `ftzr` never sees them. The words are collected into a `Vec<&str>` first, then `ftzr` gets them as a `&[&str]`.
+///
+/// Text is split on whitespace and ASCII punctuation (use `.split_on(your_fn)` to change that), and words that are already split can be given as a `&[&str]`. Stopwords are compared exactly, so normalize the text first if it isn't lowercase.
+/// ```
+/// use creature_feature::convert::{Bag, Joined};
+/// use creature_feature::ftzrs::misc::{Normalization, Stopwords};
+/// use creature_feature::ftzrs::{stemmed, without_stopwords, word_gram};
+/// use creature_feature::traits::Ftzr;
+/// use std::borrow::Cow;
+/// use std::collections::{HashMap, HashSet};
+///
+/// let english = Stopwords::english();
+///
+/// let words: Vec<[&str; 1]> = without_stopwords(word_gram::<1>(), english.clone()).featurize("the cat and the hat");
+/// assert_eq!(words, vec![["cat"], ["hat"]]);
+///
+/// let bigrams: Vec<Joined> = without_stopwords(word_gram::<2>(), english.clone()).featurize("the cat in the hat sat");
+/// assert_eq!(bigrams, vec![Joined("cat hat".to_owned()), Joined("hat sat".to_owned())]);
+///
+/// // stopwords are removed before stemming, after lowercasing
+/// let text = Normalization::default().lowercase().normalize("The fishing was only fishy");
+/// let ftzr = stemmed(without_stopwords(word_gram::<1>(), english));
+/// let bag: Bag<HashMap<[Cow<str>; 1], u8>> = ftzr.featurize(&*text);
+/// assert_eq!(bag.len(), 2);
+///
+/// let custom = Stopwords::from_words(vec!["uh", "um"].into_iter().collect::<HashSet<&str>>());
+/// let words: Vec<[&str; 1]> = without_stopwords(word_gram::<1>(), custom).featurize(&["um", "hello", "uh"][..]);
+/// assert_eq!(words, vec![["hello"]]);
+/// ```
+pub fn without_stopwords<F>(ftzr: F, stopwords: Stopwords) -> WithoutStopwords<F> {
+    WithoutStopwords {
+        ftzr,
+        stopwords,
+        is_delim: is_word_delimiter,
+    }
+}
+
+impl<F, P> WithoutStopwords<F, P> {
+    /// Use a different predicate to decide which characters separate words.
+    pub fn split_on<Q: Fn(char) -> bool>(self, is_delim: Q) -> WithoutStopwords<F, Q> {
+        WithoutStopwords {
+            ftzr: self.ftzr,
+            stopwords: self.stopwords,
+            is_delim,
+        }
+    }
+
+    fn push_words<'a, I, O, Push>(&self, words: I, push: &mut Push)
+    where
+        I: Iterator<Item = &'a str>,
+        F: for<'b> Ftzr<&'b [&'a str], TokenGroup = O>,
+        Push: FnMut(O),
+    {
+        let words: Vec<&'a str> = words.filter(|w| !self.stopwords.contains(w)).collect();
+        self.ftzr.push_tokens(&words[..], push)
+    }
+}
+
+impl<'a, F, P, O> Ftzr<&'a str> for WithoutStopwords<F, P>
+where
+    F: for<'b> Ftzr<&'b [&'a str], TokenGroup = O>,
+    P: Fn(char) -> bool,
+{
+    type TokenGroup = O;
+    fn push_tokens<Push>(&self, origin: &'a str, push: &mut Push)
+    where
+        Push: FnMut(Self::TokenGroup),
+    {
+        let words = origin
+            .split(|c| (self.is_delim)(c))
+            .filter(|w| !w.is_empty());
+        self.push_words(words, push)
+    }
+}
+
+impl<'a, F, P, O> Ftzr<&'a String> for WithoutStopwords<F, P>
+where
+    F: for<'b> Ftzr<&'b [&'a str], TokenGroup = O>,
+    P: Fn(char) -> bool,
+{
+    type TokenGroup = O;
+    fn push_tokens<Push>(&self, origin: &'a String, push: &mut Push)
+    where
+        Push: FnMut(Self::TokenGroup),
+    {
+        self.push_tokens(origin.as_str(), push)
+    }
+}
+
+impl<'a, 'c, F, P, O> Ftzr<&'c [&'a str]> for WithoutStopwords<F, P>
+where
+    F: for<'b> Ftzr<&'b [&'a str], TokenGroup = O>,
+{
+    type TokenGroup = O;
+    fn push_tokens<Push>(&self, origin: &'c [&'a str], push: &mut Push)
+    where
+        Push: FnMut(Self::TokenGroup),
+    {
+        self.push_words(origin.iter().copied(), push)
+    }
+}
+
+impl<'a, 'c, F, P, O> Ftzr<&'c Vec<&'a str>> for WithoutStopwords<F, P>
+where
+    F: for<'b> Ftzr<&'b [&'a str], TokenGroup = O>,
+{
+    type TokenGroup = O;
+    fn push_tokens<Push>(&self, origin: &'c Vec<&'a str>, push: &mut Push)
+    where
+        Push: FnMut(Self::TokenGroup),
+    {
+        self.push_tokens(&origin[..], push)
+    }
+}
+
+/// The minimum of `ftzr`, measured before stopwords are removed
+impl<F: MinLen, P> MinLen for WithoutStopwords<F, P> {
+    fn min_len(&self) -> usize {
+        self.ftzr.min_len()
+    }
+}
+
+const ENGLISH: &str = "
+i me my myself we our ours ourselves you you're you've you'll you'd your yours yourself
+yourselves he him his himself she she's her hers herself it it's its itself they them their
+theirs themselves what which who whom this that that'll these those am is are was were be been
+being have has had having do does did doing a an the and but if or because as until while of at
+by for with about against between into through during before after above below to from up down
+in out on off over under again further then once here there when where why how all any both
+each few more most other some such no nor not only own same so than too very s t can will just
+don don't should should've now d ll m o re ve y ain aren aren't couldn couldn't didn didn't
+doesn doesn't hadn hadn't hasn hasn't haven haven't isn isn't ma mightn mightn't mustn mustn't
+needn needn't shan shan't shouldn shouldn't wasn wasn't weren weren't won won't wouldn wouldn't
+";
+
+const FRENCH: &str = "
+au aux avec ce ces dans de des du elle en et eux il ils je la le les leur lui ma mais me même
+mes moi mon ne nos notre nous on ou par pas pour qu que qui sa se ses son sur ta te tes toi ton
+tu un une vos votre vous c d j l à m n s t y été étée étées étés étant étante étants étantes suis
+es est sommes êtes sont serai seras sera serons serez seront serais serait serions seriez
+seraient étais était étions étiez étaient fus fut fûmes fûtes furent sois soit soyons soyez
+soient fusse fusses fût fussions fussiez fussent ayant ayante ayantes ayants eu eue eues eus ai
+as avons avez ont aurai auras aura aurons aurez auront aurais aurait aurions auriez auraient
+avais avait avions aviez avaient eut eûmes eûtes eurent aie aies ait ayons ayez aient eusse
+eusses eût eussions eussiez eussent
+";
+
+const GERMAN: &str = "
+aber alle allem allen aller alles als also am an ander andere anderem anderen anderer anderes
+anderm andern anders auch auf aus bei bin bis bist da damit dann der den des dem die das dass
+daß derselbe derselben denselben desselben demselben dieselbe dieselben dasselbe dazu dein deine
+deinem deinen deiner deines denn derer dessen dich dir du dies diese diesem diesen dieser dieses
+doch dort durch ein eine einem einen einer eines einig einige einigem einigen einiger einiges
+einmal er ihn ihm es etwas euer eure eurem euren eurer eures für gegen gewesen hab habe haben
+hat hatte hatten hier hin hinter ich mich mir ihr ihre ihrem ihren ihrer ihres euch im in indem
+ins ist jede jedem jeden jeder jedes jene jenem jenen jener jenes jetzt kann kein keine keinem
+keinen keiner keines können könnte machen man manche manchem manchen mancher manches mein meine
+meinem meinen meiner meines mit muss musste nach nicht nichts noch nun nur ob oder ohne sehr
+sein seine seinem seinen seiner seines selbst sich sie ihnen sind so solche solchem solchen
+solcher solches soll sollte sondern sonst über um und uns unsere unserem unseren unser unseres
+unter viel vom von vor während war waren warst was weg weil weiter welche welchem welchen
+welcher welches wenn werde werden wie wieder will wir wird wirst wo wollen wollte würde würden
+zu zum zur zwar zwischen
+";
+
+const SPANISH: &str = "
+de la que el en y a los del se las por un para con no una su al lo como más pero sus le ya o
+este sí porque esta entre cuando muy sin sobre también me hasta hay donde quien desde todo nos
+durante todos uno les ni contra otros ese eso ante ellos e esto mí antes algunos qué unos yo
+otro otras otra él tanto esa estos mucho quienes nada muchos cual poco ella estar estas algunas
+algo nosotros mi mis tú te ti tu tus ellas nosotras vosotros vosotras os mío mía míos mías tuyo
+tuya tuyos tuyas suyo suya suyos suyas nuestro nuestra nuestros nuestras vuestro vuestra
+vuestros vuestras esos esas estoy estás está estamos estáis están esté estés estemos estéis
+estén estaré estarás estará estaremos estaréis estarán fue era eres es soy somos sois son ser
+sido siendo he has ha hemos habéis han había habían tengo tiene tienen tenemos
+";
@@ -0,0 +1,353 @@
+use crate::accum_ftzr::{Ftzr, IterFtzr, MinLen};
+use crate::internal::impl_ftrzs;
+use crate::n_gram::NGram;
+use crate::n_slice::SliceGram;
+use crate::padded::{Pad, PaddedSlice};
+use std::cmp;
+use std::convert::TryInto;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::ops::ControlFlow;
+
+/// The default tail policy of a strided featurizer: only whole windows are produced, and the last few tokens are dropped if they don't fill one.
+#[derive(Hash, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DropTail;
+
+/// A tail policy that produces a final, shorter window if some tokens at the end wouldn't be covered otherwise. Only for `n_slice`, since the windows of `n_gram` have a fixed length.
+#[derive(Hash, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ShortTail;
+
+/// A tail policy that produces a final window padded with `Pad::End`, if some tokens at the end wouldn't be covered otherwise. `n_gram` yields `[Pad<T>; N]` and `n_slice` yields `PaddedSlice<T>`, exactly like `padded`, so padding can never be mistaken for real data.
+#[derive(Hash, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PadTail;
+
+/// A featurizer combinator that advances its n-gram by `step` tokens instead of one. Created with `strided(ftzr, step)`, `n_slice(n).stride(step)` or `n_gram::<N>().stride(step)`
+///
+/// `P` is the policy for the ragged tail of the input: [`DropTail`], [`ShortTail`] or [`PadTail`].
+#[derive(Hash, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Strided<F, P = DropTail> {
+    ftzr: F,
+    step: usize,
+    tail: P,
+}
+
+/// `strided(ftzr, step)` will produce the same windows as `ftzr`, but only those starting every `step` tokens. It works with both `n_slice` and `n_gram`. A `step` of `0` is treated as `1`.
+/// ```
+/// use creature_feature::ftzrs::{n_gram, n_slice, strided};
+/// use creature_feature::ftzrs::misc::{Pad, PadTail, PaddedSlice, ShortTail};
+/// use creature_feature::traits::Ftzr;
+///
+/// let data = &[1, 2, 3, 4, 5, 6, 7];
+///
+/// let feats: Vec<&[i32]> = strided(n_slice(3), 2).featurize(data);
+/// assert_eq!(feats, vec![&[1, 2, 3][..], &[3, 4, 5], &[5, 6, 7]]);
+///
+/// // non-overlapping windows, with the ragged tail dropped
+/// let feats: Vec<[i32; 3]> = n_gram::<3>().stride(3).featurize(data);
+/// assert_eq!(feats, vec![[1, 2, 3], [4, 5, 6]]);
+///
+/// // ... or kept as a shorter window
+/// let feats: Vec<&[i32]> = n_slice(3).stride(3).tail(ShortTail).featurize(data);
+/// assert_eq!(feats, vec![&[1, 2, 3][..], &[4, 5, 6], &[7]]);
+///
+/// // ... or padded
+/// let feats: Vec<[Pad<i32>; 3]> = n_gram::<3>().stride(3).tail(PadTail).featurize(data);
+/// assert_eq!(feats[2], [Pad::Token(7), Pad::End, Pad::End]);
+///
+/// let feats: Vec<PaddedSlice<i32>> = n_slice(3).stride(3).tail(PadTail).featurize(data);
+/// assert_eq!(feats[2], PaddedSlice { front: 0, body: &[7], back: 2 });
+/// ```
+pub fn strided<F>(ftzr: F, step: usize) -> Strided<F> {
+    Strided {
+        ftzr,
+        step,
+        tail: DropTail,
+    }
+}
+
+impl<F, P> Strided<F, P> {
+    /// Set the policy for the last few tokens that don't fill a whole window: [`DropTail`], [`ShortTail`] or [`PadTail`]
+    pub fn tail<Q>(self, tail: Q) -> Strided<F, Q> {
+        Strided {
+            ftzr: self.ftzr,
+            step: self.step,
+            tail,
+        }
+    }
+
+    fn cursor(&self, n: usize, len: usize, keep_tail: bool) -> Cursor {
+        Cursor::new(n, self.step, len, keep_tail)
+    }
+}
+
+/// Keeps track of where the next window starts and ends
+#[derive(Hash, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+struct Cursor {
+    n: usize,
+    step: usize,
+    // `None` once a step would overflow, or after the tail
+    idx: Option<usize>,
+    len: usize,
+    keep_tail: bool,
+}
+
+impl Cursor {
+    fn new(n: usize, step: usize, len: usize, keep_tail: bool) -> Self {
+        Cursor {
+            n,
+            step: cmp::max(1, step),
+            idx: Some(0),
+            len,
+            keep_tail,
+        }
+    }
+
+    /// the bounds of the next window, which is only shorter than `n` if it's the tail
+    #[inline]
+    fn next_window(&mut self) -> Option<(usize, usize)> {
+        let (idx, len) = (self.idx?, self.len);
+        if let Some(end) = idx.checked_add(self.n).filter(|&end| end <= len) {
+            self.idx = idx.checked_add(self.step);
+            return Some((idx, end));
+        }
+        if !self.keep_tail || idx >= len {
+            return None;
+        }
+        // the tail is only produced if the previous window (if any) didn't reach the end,
+        // and the previous window fit, so it can't overflow
+        if idx == 0 || idx - self.step + self.n < len {
+            self.idx = None;
+            return Some((idx, len));
+        }
+        None
+    }
+}
+
+/// The associated iterator for `<Strided<SliceGram, P> as IterFtzr<T>>::Iter`, with `DropTail` or `ShortTail`
+#[derive(Hash, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Debug)]
+pub struct StridedSliceIter<'a, T> {
+    cursor: Cursor,
+    data: &'a [T],
+}
+
+impl<'a, T> Iterator for StridedSliceIter<'a, T> {
+    type Item = &'a [T];
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (idx, jdx) = self.cursor.next_window()?;
+        Some(&self.data[idx..jdx])
+    }
+}
+
+/// A whole window, whatever happens to the tail
+impl<F: MinLen, P> MinLen for Strided<F, P> {
+    fn min_len(&self) -> usize {
+        self.ftzr.min_len()
+    }
+}
+
+impl<'a, T> IterFtzr<&'a [T]> for Strided<SliceGram> {
+    type TokenGroup = &'a [T];
+    type Iter = StridedSliceIter<'a, T>;
+
+    fn iterate_features(&self, origin: &'a [T]) -> Self::Iter {
+        StridedSliceIter {
+            cursor: self.cursor(self.ftzr.n, origin.len(), false),
+            data: origin,
+        }
+    }
+}
+
+impl<'a, T> IterFtzr<&'a [T]> for Strided<SliceGram, ShortTail> {
+    type TokenGroup = &'a [T];
+    type Iter = StridedSliceIter<'a, T>;
+
+    fn iterate_features(&self, origin: &'a [T]) -> Self::Iter {
+        StridedSliceIter {
+            cursor: self.cursor(self.ftzr.n, origin.len(), true),
+            data: origin,
+        }
+    }
+}
+
+/// The associated iterator for `<Strided<SliceGram, PadTail> as IterFtzr<T>>::Iter`
+#[derive(Hash, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Debug)]
+pub struct StridedPaddedSliceIter<'a, T> {
+    cursor: Cursor,
+    data: &'a [T],
+}
+
+impl<'a, T> Iterator for StridedPaddedSliceIter<'a, T> {
+    type Item = PaddedSlice<'a, T>;
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (idx, jdx) = self.cursor.next_window()?;
+        Some(PaddedSlice {
+            front: 0,
+            body: &self.data[idx..jdx],
+            back: self.cursor.n - (jdx - idx),
+        })
+    }
+}
+
+impl<'a, T> IterFtzr<&'a [T]> for Strided<SliceGram, PadTail> {
+    type TokenGroup = PaddedSlice<'a, T>;
+    type Iter = StridedPaddedSliceIter<'a, T>;
+
+    fn iterate_features(&self, origin: &'a [T]) -> Self::Iter {
+        StridedPaddedSliceIter {
+            cursor: self.cursor(self.ftzr.n, origin.len(), true),
+            data: origin,
+        }
+    }
+}
+
+impl_ftrzs!(Strided<SliceGram>);
+impl_ftrzs!(Strided<SliceGram, ShortTail>);
+impl_ftrzs!(Strided<SliceGram, PadTail>);
+
+/// The associated iterator for `<Strided<NGram<N>> as IterFtzr<T>>::Iter`
+#[derive(Hash, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Debug)]
+pub struct StridedGramIter<'a, T, const N: usize> {
+    cursor: Cursor,
+    data: &'a [T],
+}
+
+impl<'a, T: Copy, const N: usize> Iterator for StridedGramIter<'a, T, N> {
+    type Item = [T; N];
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        // the tail is dropped, so every window is whole
+        let (idx, jdx) = self.cursor.next_window()?;
+        self.data[idx..jdx].try_into().ok()
+    }
+}
+
+impl<'a, T: Copy, const N: usize> IterFtzr<&'a [T]> for Strided<NGram<N>> {
+    type TokenGroup = [T; N];
+    type Iter = StridedGramIter<'a, T, N>;
+
+    fn iterate_features(&self, origin: &'a [T]) -> Self::Iter {
+        StridedGramIter {
+            cursor: self.cursor(N, origin.len(), false),
+            data: origin,
+        }
+    }
+}
+
+/// The associated iterator for `<Strided<NGram<N>, PadTail> as IterFtzr<T>>::Iter`
+#[derive(Hash, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Debug)]
+pub struct StridedPaddedGramIter<'a, T, const N: usize> {
+    cursor: Cursor,
+    data: &'a [T],
+}
+
+impl<'a, T: Copy, const N: usize> Iterator for StridedPaddedGramIter<'a, T, N> {
+    type Item = [Pad<T>; N];
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (idx, jdx) = self.cursor.next_window()?;
+        let mut gram = [Pad::End; N];
+        for (slot, token) in gram.iter_mut().zip(&self.data[idx..jdx]) {
+            *slot = Pad::Token(*token);
+        }
+        Some(gram)
+    }
+}
+
+impl<'a, T: Copy, const N: usize> IterFtzr<&'a [T]> for Strided<NGram<N>, PadTail> {
+    type TokenGroup = [Pad<T>; N];
+    type Iter = StridedPaddedGramIter<'a, T, N>;
+
+    fn iterate_features(&self, origin: &'a [T]) -> Self::Iter {
+        StridedPaddedGramIter {
+            cursor: self.cursor(N, origin.len(), true),
+            data: origin,
+        }
+    }
+}
+
+/// `impl_ftrzs!` for `Strided<NGram<N>, P>`, which has a const parameter
+macro_rules! impl_strided_gram {
+    ($tail:ty) => {
+        impl<'a, T, const N: usize> IterFtzr<&'a Vec<T>> for Strided<NGram<N>, $tail>
+        where
+            Self: IterFtzr<&'a [T]>,
+        {
+            type TokenGroup = <Self as IterFtzr<&'a [T]>>::TokenGroup;
+            type Iter = <Self as IterFtzr<&'a [T]>>::Iter;
+
+            fn iterate_features(&self, origin: &'a Vec<T>) -> Self::Iter {
+                self.iterate_features(origin.as_slice())
+            }
+        }
+
+        impl<'a, T, const N: usize, const M: usize> IterFtzr<&'a [T; M]>
+            for Strided<NGram<N>, $tail>
+        where
+            Self: IterFtzr<&'a [T]>,
+        {
+            type TokenGroup = <Self as IterFtzr<&'a [T]>>::TokenGroup;
+            type Iter = <Self as IterFtzr<&'a [T]>>::Iter;
+
+            fn iterate_features(&self, origin: &'a [T; M]) -> Self::Iter {
+                self.iterate_features(&origin[..])
+            }
+        }
+
+        impl<'a, const N: usize> IterFtzr<&'a str> for Strided<NGram<N>, $tail>
+        where
+            Self: IterFtzr<&'a [u8]>,
+        {
+            type TokenGroup = <Self as IterFtzr<&'a [u8]>>::TokenGroup;
+            type Iter = <Self as IterFtzr<&'a [u8]>>::Iter;
+
+            fn iterate_features(&self, origin: &'a str) -> Self::Iter {
+                self.iterate_features(origin.as_bytes())
+            }
+        }
+
+        impl<'a, const N: usize> IterFtzr<&'a String> for Strided<NGram<N>, $tail>
+        where
+            Self: IterFtzr<&'a [u8]>,
+        {
+            type TokenGroup = <Self as IterFtzr<&'a [u8]>>::TokenGroup;
+            type Iter = <Self as IterFtzr<&'a [u8]>>::Iter;
+
+            fn iterate_features(&self, origin: &'a String) -> Self::Iter {
+                self.iterate_features(origin.as_bytes())
+            }
+        }
+
+        impl<Origin, const N: usize> Ftzr<Origin> for Strided<NGram<N>, $tail>
+        where
+            Self: IterFtzr<Origin>,
+        {
+            type TokenGroup = <Self as IterFtzr<Origin>>::TokenGroup;
+            fn push_tokens<Push>(&self, origin: Origin, push: &mut Push)
+            where
+                Push: FnMut(Self::TokenGroup),
+            {
+                for t in self.iterate_features(origin) {
+                    push(t)
+                }
+            }
+
+            fn push_tokens_while<Push>(&self, origin: Origin, push: &mut Push) -> ControlFlow<()>
+            where
+                Push: FnMut(Self::TokenGroup) -> ControlFlow<()>,
+            {
+                self.iterate_features(origin).try_for_each(push)
+            }
+        }
+    };
+}
+
+impl_strided_gram!(DropTail);
+impl_strided_gram!(PadTail);
@@ -1,5 +1,6 @@
-use crate::accum_ftzr::{Ftzr, IterFtzr, LinearFixed};
-use crate::feature_from::FeatureFrom;
+use crate::accum_ftzr::{Ftzr, IterFtzr, LinearFixed, MinLen};
+use crate::error::FeatureError;
+use crate::feature_from::{try_via_from, FeatureFrom, TryFeatureFrom};
 use crate::internal::impl_ftrzs;
 use std::ops::Deref;
 
@@ -70,6 +71,55 @@ impl<T, Q: IntoIterator<Item = T>> FeatureFrom<WholeAtom<Q>> for Vec<T> {
     }
 }
 
+impl<'a, T> TryFeatureFrom<WholeAtom<T>> for &'a str
+where
+    &'a str: TryFeatureFrom<T>,
+{
+    fn try_from(t: WholeAtom<T>) -> Result<&'a str, FeatureError> {
+        TryFeatureFrom::try_from(t.0)
+    }
+}
+
+impl<'a, T, U> TryFeatureFrom<WholeAtom<T>> for &'a [U]
+where
+    &'a [U]: TryFeatureFrom<T>,
+{
+    fn try_from(t: WholeAtom<T>) -> Result<&'a [U], FeatureError> {
+        TryFeatureFrom::try_from(t.0)
+    }
+}
+
+impl<'a, T, U, const N: usize> TryFeatureFrom<WholeAtom<T>> for &'a [U; N]
+where
+    &'a [U; N]: TryFeatureFrom<T>,
+{
+    fn try_from(t: WholeAtom<T>) -> Result<&'a [U; N], FeatureError> {
+        TryFeatureFrom::try_from(t.0)
+    }
+}
+
+impl<T, U, const N: usize> TryFeatureFrom<WholeAtom<T>> for [U; N]
+where
+    [U; N]: TryFeatureFrom<T>,
+{
+    fn try_from(t: WholeAtom<T>) -> Result<[U; N], FeatureError> {
+        TryFeatureFrom::try_from(t.0)
+    }
+}
+
+impl<T> TryFeatureFrom<WholeAtom<T>> for String
+where
+    String: TryFeatureFrom<T>,
+{
+    fn try_from(t: WholeAtom<T>) -> Result<String, FeatureError> {
+        TryFeatureFrom::try_from(t.0)
+    }
+}
+
+try_via_from! {
+    [T, Q: IntoIterator<Item = T>] WholeAtom<Q> => Vec<T>;
+}
+
 impl<'a, T> IterFtzr<&'a [T]> for Whole {
     type TokenGroup = &'a [T];
     type Iter = std::option::IntoIter<Self::TokenGroup>;
@@ -101,12 +151,29 @@ impl<T> FeatureFrom<EmptyAtom> for Option<T> {
     }
 }
 
+try_via_from! {
+    [] EmptyAtom => String;
+    [T] EmptyAtom => Option<T>;
+}
+
 impl LinearFixed for Empty {
     fn chunk_size(&self) -> usize {
         0
     }
 }
 
+impl MinLen for Empty {
+    fn min_len(&self) -> usize {
+        0
+    }
+}
+
+impl MinLen for Whole {
+    fn min_len(&self) -> usize {
+        0
+    }
+}
+
 impl<'a, T> IterFtzr<&'a [T]> for Empty {
     type TokenGroup = EmptyAtom;
     type Iter = std::option::IntoIter<Self::TokenGroup>;
@@ -0,0 +1,158 @@
+use crate::accum_ftzr::{Ftzr, MinLen};
+use crate::feature_from::{try_via_from, FeatureFrom};
+use crate::hashedfeature::HashedAs;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// A featurizer combinator that selects document fingerprints from the hashes of another featurizer. Created with `winnow(ftzr, window)`
+#[derive(Hash, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Winnow<F> {
+    ftzr: F,
+    window: usize,
+}
+
+/// MOSS-style winnowing (Schleimer, Wilkerson & Aiken, 2003). Every token group of `ftzr` is hashed as `HashedAs<u64>` and, for each run of `window` consecutive hashes, the smallest is selected as a `Fingerprint` (the rightmost one if there's a tie). A fingerprint is only produced once, even if it's the minimum of several windows. Inputs with fewer than `window` hashes produce their single smallest hash. A `window` of `0` is the same as `1`.
+///
+/// With `winnow(n_slice(k), w)` (or `rolling_hash(k)`), any substring of length `w + k - 1` or more that's shared by two inputs yields at least one shared fingerprint. Nothing shorter than `k` is ever detected.
+/// ```
+/// use creature_feature::ftzrs::{n_slice, rolling_hash, winnow};
+/// use creature_feature::HashedAs;
+/// use creature_feature::traits::Ftzr;
+/// use std::collections::HashSet;
+///
+/// let ftzr = winnow(rolling_hash(5), 4);
+///
+/// let a: HashSet<HashedAs<u64>> = ftzr.featurize("a do run run run, a do run run");
+/// let b: HashSet<HashedAs<u64>> = ftzr.featurize("yada yada yada, a do run run");
+/// assert!(a.intersection(&b).count() > 0);
+///
+/// // fingerprints know where they came from: the rightmost minimum of each window of 3 bigram hashes
+/// let hashes: Vec<HashedAs<u64>> = n_slice(2).featurize("abcabd");
+/// let mut expected: Vec<(HashedAs<u64>, usize)> = Vec::new();
+/// for start in 0..=hashes.len() - 3 {
+///     let window = &hashes[start..start + 3];
+///     let min = *window.iter().min().unwrap();
+///     let position = start + window.iter().rposition(|h| *h == min).unwrap();
+///     if expected.last().map(|(_, p)| *p) != Some(position) {
+///         expected.push((min, position));
+///     }
+/// }
+/// let prints: Vec<(HashedAs<u64>, usize)> = winnow(n_slice(2), 3).featurize("abcabd");
+/// assert_eq!(prints, expected);
+///
+/// // inputs sharing "abcd" (at least 3 + 2 - 1 long) select the same fingerprint, from the same bigram
+/// let (a, b) = ("xyzabcd", "abcdpq");
+/// let prints_a: Vec<(HashedAs<u64>, usize)> = winnow(n_slice(2), 3).featurize(a);
+/// let prints_b: Vec<(HashedAs<u64>, usize)> = winnow(n_slice(2), 3).featurize(b);
+/// let mut shared = Vec::new();
+/// for (hash, i) in &prints_a {
+///     for (_, j) in prints_b.iter().filter(|(other, _)| other == hash) {
+///         shared.push((&a[*i..*i + 2], &b[*j..*j + 2]));
+///     }
+/// }
+/// assert!(!shared.is_empty());
+/// assert!(shared.iter().all(|(x, y)| x == y && "abcd".contains(*x)));
+/// ```
+pub fn winnow<F>(ftzr: F, window: usize) -> Winnow<F> {
+    Winnow { ftzr, window }
+}
+
+/// The token group of `winnow`: a selected hash and the index of the token group of the inner featurizer that it came from. (See [`winnow`])
+#[derive(Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Fingerprint {
+    /// The selected hash
+    pub hash: u64,
+    /// The index of the hash among all of those produced by the inner featurizer
+    pub position: usize,
+}
+
+impl<Origin, F> Ftzr<Origin> for Winnow<F>
+where
+    F: Ftzr<Origin>,
+    HashedAs<u64>: FeatureFrom<F::TokenGroup>,
+{
+    type TokenGroup = Fingerprint;
+    fn push_tokens<Push>(&self, origin: Origin, push: &mut Push)
+    where
+        Push: FnMut(Self::TokenGroup),
+    {
+        let window = self.window.max(1);
+        // candidates for the minimum of the current window, with strictly increasing hashes
+        let mut candidates: VecDeque<Fingerprint> = VecDeque::with_capacity(window);
+        let mut last: Option<usize> = None;
+        let mut position = 0;
+        self.ftzr.push_tokens(origin, &mut |t| {
+            let hash = <HashedAs<u64> as FeatureFrom<F::TokenGroup>>::from(t).0;
+            while candidates.back().is_some_and(|c| c.hash >= hash) {
+                candidates.pop_back();
+            }
+            candidates.push_back(Fingerprint { hash, position });
+            while candidates
+                .front()
+                .is_some_and(|c| c.position + window <= position)
+            {
+                candidates.pop_front();
+            }
+            if position + 1 >= window {
+                let min = candidates[0];
+                if last != Some(min.position) {
+                    last = Some(min.position);
+                    push(min);
+                }
+            }
+            position += 1;
+        });
+        // too short for a whole window
+        if position < window {
+            if let Some(min) = candidates.front() {
+                push(*min)
+            }
+        }
+    }
+}
+
+macro_rules! impl_fingerprint {
+    ($u_type:ty) => {
+        impl FeatureFrom<Fingerprint> for HashedAs<$u_type> {
+            fn from(token_group: Fingerprint) -> Self {
+                HashedAs(token_group.hash as $u_type)
+            }
+        }
+
+        try_via_from! {
+            [] Fingerprint => HashedAs<$u_type>;
+        }
+    };
+}
+
+impl_fingerprint!(u8);
+impl_fingerprint!(u16);
+impl_fingerprint!(u32);
+impl_fingerprint!(u64);
+
+impl FeatureFrom<Fingerprint> for u64 {
+    fn from(token_group: Fingerprint) -> Self {
+        token_group.hash
+    }
+}
+
+impl FeatureFrom<Fingerprint> for (HashedAs<u64>, usize) {
+    fn from(token_group: Fingerprint) -> Self {
+        (HashedAs(token_group.hash), token_group.position)
+    }
+}
+
+try_via_from! {
+    [] Fingerprint => u64;
+    [] Fingerprint => (HashedAs<u64>, usize);
+}
+
+/// Enough token groups of `ftzr` for a whole window
+impl<F: MinLen> MinLen for Winnow<F> {
+    fn min_len(&self) -> usize {
+        self.ftzr.min_len() + self.window.max(1) - 1
+    }
+}
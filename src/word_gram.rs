@@ -0,0 +1,350 @@
+use crate::accum_ftzr::{Ftzr, IterFtzr, MinLen};
+use crate::n_gram::{NGram, NGramIter};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::ops::ControlFlow;
+
+/// The default word boundary for `word_slice` and `word_gram`: any whitespace or ASCII punctuation.
+pub fn is_word_delimiter(c: char) -> bool {
+    c.is_whitespace() || c.is_ascii_punctuation()
+}
+
+/// An iterator over the byte ranges `(start, end)` of each word of a `&str`. Used by `WordSlice` and `WordGram`.
+#[derive(Hash, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Debug)]
+pub struct WordSpans<'a, P> {
+    data: &'a str,
+    idx: usize,
+    is_delim: P,
+}
+
+impl<'a, P: Fn(char) -> bool> Iterator for WordSpans<'a, P> {
+    type Item = (usize, usize);
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let rest = &self.data[self.idx..];
+        let start = self.idx + rest.find(|c| !(self.is_delim)(c))?;
+        let end = self.data[start..]
+            .find(|c| (self.is_delim)(c))
+            .map_or(self.data.len(), |len| start + len);
+        self.idx = end;
+        Some((start, end))
+    }
+}
+
+/// The words of a `&str` (split on whitespace) along with their byte offsets, like `str::char_indices` for words. Created with `word_indices(text)`
+#[derive(Hash, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Debug)]
+pub struct WordIndices<'a>(WordSpans<'a, fn(char) -> bool>);
+
+impl<'a> Iterator for WordIndices<'a> {
+    type Item = (usize, &'a str);
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (start, end) = self.0.next()?;
+        Some((start, &self.0.data[start..end]))
+    }
+}
+
+/// `word_indices(text)` is `text.split_whitespace()`, but with the byte offset of each word. It's the input for `with_spans(for_each(ftzr))`, so that spans are relative to `text`.
+/// ```
+/// use creature_feature::ftzrs::{bislice, for_each, with_spans, word_indices};
+/// use creature_feature::traits::Ftzr;
+/// use std::ops::Range;
+///
+/// let text = "  one fish";
+/// let words: Vec<(usize, &str)> = word_indices(text).collect();
+/// assert_eq!(words, vec![(2, "one"), (6, "fish")]);
+///
+/// let feats: Vec<(Range<usize>, &str)> = with_spans(for_each(bislice())).featurize(word_indices(text));
+/// assert_eq!(feats[2], (6..8, "fi"));
+/// ```
+pub fn word_indices(text: &str) -> WordIndices<'_> {
+    WordIndices(WordSpans {
+        data: text,
+        idx: 0,
+        is_delim: char::is_whitespace,
+    })
+}
+
+/// word n-grams over a `&str`, produces borrowed `&str` spans of the original text covering exactly `n` words (including whatever separates them). Created with `word_slice(n)`
+#[derive(Hash, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct WordSlice<P = fn(char) -> bool> {
+    n: usize,
+    is_delim: P,
+}
+
+/// word n-grams over referenced text, produces borrowed `&str` spans of `n` words. Words are split on whitespace and ASCII punctuation, use `.split_on(your_fn)` to change that. (Compare to `word_gram`)
+/// ```
+/// use creature_feature::ftzrs::word_slice;
+/// use creature_feature::traits::Ftzr;
+///
+/// let text = "one fish, two fish";
+///
+/// let feats: Vec<&str> = word_slice(2).featurize(text);
+/// assert_eq!(feats, vec!["one fish", "fish, two", "two fish"]);
+///
+/// let feats: Vec<&str> = word_slice(2).split_on(char::is_whitespace).featurize(text);
+/// assert_eq!(feats, vec!["one fish,", "fish, two", "two fish"]);
+/// ```
+///
+/// Like `char_slice(0)` and `grapheme_slice(0)`, `word_slice(0)` produces one empty window per position (before each word and at the end).
+/// ```
+/// use creature_feature::ftzrs::{char_slice, word_slice};
+/// use creature_feature::traits::Ftzr;
+///
+/// let feats: Vec<&str> = word_slice(0).featurize("one fish");
+/// assert_eq!(feats, vec!["", "", ""]);
+///
+/// let feats: Vec<&str> = char_slice(0).featurize("ab");
+/// assert_eq!(feats, vec!["", "", ""]);
+///
+/// let feats: Vec<&str> = word_slice(0).featurize("");
+/// assert_eq!(feats, vec![""]);
+/// ```
+pub fn word_slice(n: usize) -> WordSlice {
+    WordSlice {
+        n,
+        is_delim: is_word_delimiter,
+    }
+}
+
+impl<P> WordSlice<P> {
+    /// Use a different predicate to decide which characters separate words.
+    pub fn split_on<Q: Fn(char) -> bool>(self, is_delim: Q) -> WordSlice<Q> {
+        WordSlice {
+            n: self.n,
+            is_delim,
+        }
+    }
+}
+
+/// The associated iterator for `<WordSlice<P> as IterFtzr<&str>>::Iter`
+#[derive(Hash, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Debug)]
+pub struct WordSliceIter<'a, P> {
+    starts: WordSpans<'a, P>,
+    // `None` for empty windows
+    ends: Option<WordSpans<'a, P>>,
+    done: bool,
+}
+
+impl<'a, P: Fn(char) -> bool> Iterator for WordSliceIter<'a, P> {
+    type Item = &'a str;
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let data = self.starts.data;
+        match self.ends.as_mut() {
+            Some(ends) => {
+                let (_, end) = ends.next()?;
+                let (start, _) = self.starts.next()?;
+                Some(&data[start..end])
+            }
+            None => match self.starts.next() {
+                Some((start, _)) => Some(&data[start..start]),
+                None if !self.done => {
+                    self.done = true;
+                    Some(&data[data.len()..])
+                }
+                None => None,
+            },
+        }
+    }
+}
+
+impl<'a, P: Fn(char) -> bool + Clone> IterFtzr<&'a str> for WordSlice<P> {
+    type TokenGroup = &'a str;
+    type Iter = WordSliceIter<'a, P>;
+
+    fn iterate_features(&self, origin: &'a str) -> Self::Iter {
+        let starts = WordSpans {
+            data: origin,
+            idx: 0,
+            is_delim: self.is_delim.clone(),
+        };
+        let mut ends = starts.clone();
+        // `ends` runs ahead, starting on the last word of the first window
+        if self.n > 1 {
+            ends.nth(self.n - 2);
+        }
+        WordSliceIter {
+            starts,
+            ends: Some(ends).filter(|_| self.n > 0),
+            done: false,
+        }
+    }
+}
+
+impl<'a, P: Fn(char) -> bool + Clone> IterFtzr<&'a String> for WordSlice<P> {
+    type TokenGroup = &'a str;
+    type Iter = WordSliceIter<'a, P>;
+
+    fn iterate_features(&self, origin: &'a String) -> Self::Iter {
+        self.iterate_features(origin.as_str())
+    }
+}
+
+impl<Origin, P> Ftzr<Origin> for WordSlice<P>
+where
+    Self: IterFtzr<Origin>,
+{
+    type TokenGroup = <Self as IterFtzr<Origin>>::TokenGroup;
+    fn push_tokens<Push>(&self, origin: Origin, push: &mut Push)
+    where
+        Push: FnMut(Self::TokenGroup),
+    {
+        for t in self.iterate_features(origin) {
+            push(t)
+        }
+    }
+
+    fn push_tokens_while<Push>(&self, origin: Origin, push: &mut Push) -> ControlFlow<()>
+    where
+        Push: FnMut(Self::TokenGroup) -> ControlFlow<()>,
+    {
+        self.iterate_features(origin).try_for_each(push)
+    }
+}
+
+/// `n` words, which take at least `n` bytes of a `&str`
+impl<P> MinLen for WordSlice<P> {
+    fn min_len(&self) -> usize {
+        self.n
+    }
+}
+
+/// word n-grams over a `&str`, produces `[&str; N]` of the individual words, or owned data like `Joined`. Created with `word_gram::<N>()`
+#[derive(Hash, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct WordGram<const N: usize, P = fn(char) -> bool> {
+    is_delim: P,
+}
+
+/// word n-grams that yield each word separately as `[&str; N]`. Words are split on whitespace and ASCII punctuation, use `.split_on(your_fn)` to change that. Words that are already split can be given as a `&[&str]`. Use `convert::Joined` for owned features with a configurable separator. (Compare to `word_slice`)
+/// ```
+/// use creature_feature::convert::Joined;
+/// use creature_feature::ftzrs::word_gram;
+/// use creature_feature::traits::Ftzr;
+///
+/// let text = "one fish, two fish";
+///
+/// let feats: Vec<[&str; 2]> = word_gram::<2>().featurize(text);
+/// assert_eq!(feats, vec![["one", "fish"], ["fish", "two"], ["two", "fish"]]);
+///
+/// let feats: Vec<Joined<'+'>> = word_gram::<2>().featurize(text);
+/// assert_eq!(*feats[2], "two+fish");
+/// ```
+pub fn word_gram<const N: usize>() -> WordGram<N> {
+    WordGram {
+        is_delim: is_word_delimiter,
+    }
+}
+
+impl<P, const N: usize> WordGram<N, P> {
+    /// Use a different predicate to decide which characters separate words.
+    pub fn split_on<Q: Fn(char) -> bool>(self, is_delim: Q) -> WordGram<N, Q> {
+        WordGram { is_delim }
+    }
+}
+
+/// The associated iterator for `<WordGram<N, P> as IterFtzr<&str>>::Iter`
+#[derive(Hash, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Debug)]
+pub struct WordGramIter<'a, P, const N: usize> {
+    primed: bool,
+    window: [&'a str; N],
+    words: WordSpans<'a, P>,
+}
+
+impl<'a, P: Fn(char) -> bool, const N: usize> Iterator for WordGramIter<'a, P, N> {
+    type Item = [&'a str; N];
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let data = self.words.data;
+        if !self.primed {
+            for slot in self.window.iter_mut() {
+                let (start, end) = self.words.next()?;
+                *slot = &data[start..end];
+            }
+            self.primed = true;
+            return Some(self.window);
+        }
+        let (start, end) = self.words.next()?;
+        if N > 0 {
+            self.window.rotate_left(1);
+            self.window[N - 1] = &data[start..end];
+        }
+        Some(self.window)
+    }
+}
+
+impl<'a, P: Fn(char) -> bool + Clone, const N: usize> IterFtzr<&'a str> for WordGram<N, P> {
+    type TokenGroup = [&'a str; N];
+    type Iter = WordGramIter<'a, P, N>;
+
+    fn iterate_features(&self, origin: &'a str) -> Self::Iter {
+        WordGramIter {
+            primed: false,
+            window: [""; N],
+            words: WordSpans {
+                data: origin,
+                idx: 0,
+                is_delim: self.is_delim.clone(),
+            },
+        }
+    }
+}
+
+impl<'a, P: Fn(char) -> bool + Clone, const N: usize> IterFtzr<&'a String> for WordGram<N, P> {
+    type TokenGroup = [&'a str; N];
+    type Iter = WordGramIter<'a, P, N>;
+
+    fn iterate_features(&self, origin: &'a String) -> Self::Iter {
+        self.iterate_features(origin.as_str())
+    }
+}
+
+impl<'a, 'b, P, const N: usize> IterFtzr<&'b [&'a str]> for WordGram<N, P> {
+    type TokenGroup = [&'a str; N];
+    type Iter = NGramIter<'b, &'a str, N>;
+
+    fn iterate_features(&self, origin: &'b [&'a str]) -> Self::Iter {
+        NGram::<N>().iterate_features(origin)
+    }
+}
+
+impl<'a, 'b, P, const N: usize> IterFtzr<&'b Vec<&'a str>> for WordGram<N, P> {
+    type TokenGroup = [&'a str; N];
+    type Iter = NGramIter<'b, &'a str, N>;
+
+    fn iterate_features(&self, origin: &'b Vec<&'a str>) -> Self::Iter {
+        self.iterate_features(&origin[..])
+    }
+}
+
+impl<Origin, P, const N: usize> Ftzr<Origin> for WordGram<N, P>
+where
+    Self: IterFtzr<Origin>,
+{
+    type TokenGroup = <Self as IterFtzr<Origin>>::TokenGroup;
+    fn push_tokens<Push>(&self, origin: Origin, push: &mut Push)
+    where
+        Push: FnMut(Self::TokenGroup),
+    {
+        for t in self.iterate_features(origin) {
+            push(t)
+        }
+    }
+
+    fn push_tokens_while<Push>(&self, origin: Origin, push: &mut Push) -> ControlFlow<()>
+    where
+        Push: FnMut(Self::TokenGroup) -> ControlFlow<()>,
+    {
+        self.iterate_features(origin).try_for_each(push)
+    }
+}
+
+/// `N` words, whether they're split from a `&str` (at least `N` bytes) or already a `&[&str]`
+impl<P, const N: usize> MinLen for WordGram<N, P> {
+    fn min_len(&self) -> usize {
+        N
+    }
+}
@@ -0,0 +1,240 @@
+use crate::accum_ftzr::{Ftzr, MinLen};
+use crate::feature_from::{try_via_from, FeatureFrom};
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// The prefix of vocabulary entries that continue a word
+const CONTINUATION: &str = "##";
+
+/// A WordPiece vocabulary: one entry per id, where entries starting with `##` continue a word. Created with `WordPieceVocab::new` or `WordPieceVocab::from_file`, and used with `word_piece(&vocab)`
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct WordPieceVocab {
+    ids: HashMap<String, u32>,
+    size: u32,
+}
+
+impl WordPieceVocab {
+    /// A vocabulary where the id of each entry is its position in `tokens`. Empty entries are skipped (but still take an id), and only the first of repeated entries is used.
+    pub fn new<I, S>(tokens: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut vocab = WordPieceVocab::default();
+        for token in tokens {
+            let token = token.as_ref();
+            if !token.is_empty() {
+                vocab.ids.entry(token.to_owned()).or_insert(vocab.size);
+            }
+            vocab.size += 1;
+        }
+        vocab
+    }
+
+    /// Reads a vocabulary file with one entry per line, like BERT's `vocab.txt`. Ids are line numbers, counting from `0`.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let text = fs::read_to_string(path)?;
+        Ok(WordPieceVocab::new(text.lines().map(str::trim)))
+    }
+
+    /// The id of an entry
+    pub fn id(&self, token: &str) -> Option<u32> {
+        self.ids.get(token).copied()
+    }
+
+    /// The number of ids, which is one more than the largest id
+    pub fn len(&self) -> usize {
+        self.size as usize
+    }
+
+    /// Whether there are no ids at all
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    fn get(&self, token: &str) -> Option<Subword<'_>> {
+        self.ids
+            .get_key_value(token)
+            .map(|(piece, id)| Subword { piece, id: *id })
+    }
+}
+
+/// A WordPiece tokenizer over a borrowed vocabulary. Created with `word_piece(&vocab)`
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct WordPiece<'v> {
+    vocab: &'v WordPieceVocab,
+    unk: Subword<'v>,
+    max_chars: usize,
+}
+
+/// The unknown token given to `word_piece` or `WordPiece::unk` isn't in the vocabulary, so it has no id
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct MissingUnkError {
+    /// The unknown token that's missing
+    pub token: String,
+}
+
+impl fmt::Display for MissingUnkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "the unknown token {:?} isn't in the vocabulary",
+            self.token
+        )
+    }
+}
+
+impl std::error::Error for MissingUnkError {}
+
+/// The token group of `WordPiece`: a vocabulary entry and its id. (See [`word_piece`])
+#[derive(Hash, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Debug)]
+pub struct Subword<'v> {
+    /// The vocabulary entry, like `"play"`, `"##ing"` or `"[UNK]"`
+    pub piece: &'v str,
+    /// The id of the entry
+    pub id: u32,
+}
+
+/// Splits each input word into the longest pieces found in `vocab`, from left to right, exactly like BERT's WordPiece. Pieces after the first are looked up with a `##` prefix. A word that can't be covered by the vocabulary, or that's longer than `max_chars` chars (`100` by default), becomes a single unknown token, `"[UNK]"` by default. The unknown token must be in `vocab`, or a [`MissingUnkError`] is returned.
+///
+/// Each input is one word, so use `for_each(word_piece(&vocab))` over `split_whitespace()` (or another word splitter) for whole sentences.
+/// ```
+/// use creature_feature::ftzrs::misc::WordPieceVocab;
+/// use creature_feature::ftzrs::{for_each, word_piece};
+/// use creature_feature::traits::Ftzr;
+///
+/// let vocab = WordPieceVocab::new(vec!["[UNK]", "un", "play", "##play", "##able", "##ing"]);
+/// let tokenizer = word_piece(&vocab).unwrap();
+///
+/// let pieces: Vec<&str> = tokenizer.featurize("unplayable");
+/// assert_eq!(pieces, vec!["un", "##play", "##able"]);
+///
+/// let sentence = "playing unplayable xyz";
+/// let ids: Vec<u32> = for_each(tokenizer).featurize(sentence.split_whitespace());
+/// assert_eq!(ids, vec![2, 5, 1, 3, 4, 0]);
+///
+/// assert!(tokenizer.unk("<?>").is_err());
+/// assert!(word_piece(&WordPieceVocab::new(vec!["play"])).is_err());
+/// ```
+pub fn word_piece(vocab: &WordPieceVocab) -> Result<WordPiece<'_>, MissingUnkError> {
+    WordPiece {
+        vocab,
+        unk: Subword { piece: "", id: 0 },
+        max_chars: 100,
+    }
+    .unk("[UNK]")
+}
+
+impl<'v> WordPiece<'v> {
+    /// Use `token` for words that aren't covered by the vocabulary. It must be in the vocabulary.
+    pub fn unk(self, token: &str) -> Result<Self, MissingUnkError> {
+        match self.vocab.get(token) {
+            Some(unk) => Ok(WordPiece { unk, ..self }),
+            None => Err(MissingUnkError {
+                token: token.to_owned(),
+            }),
+        }
+    }
+
+    /// Words with more than `max_chars` chars are unknown without being split
+    pub fn max_chars(self, max_chars: usize) -> Self {
+        WordPiece { max_chars, ..self }
+    }
+}
+
+impl<'v, 'a> Ftzr<&'a str> for WordPiece<'v> {
+    type TokenGroup = Subword<'v>;
+    fn push_tokens<Push>(&self, word: &'a str, push: &mut Push)
+    where
+        Push: FnMut(Self::TokenGroup),
+    {
+        if word.is_empty() {
+            return;
+        }
+        if word.chars().count() > self.max_chars {
+            return push(self.unk);
+        }
+        let mut pieces = Vec::new();
+        let mut key = String::with_capacity(word.len() + CONTINUATION.len());
+        let mut start = 0;
+        while start < word.len() {
+            let mut end = word.len();
+            let found = loop {
+                if end == start {
+                    break None;
+                }
+                key.clear();
+                if start > 0 {
+                    key.push_str(CONTINUATION);
+                }
+                key.push_str(&word[start..end]);
+                if let Some(subword) = self.vocab.get(&key) {
+                    break Some(subword);
+                }
+                end -= 1;
+                while !word.is_char_boundary(end) {
+                    end -= 1;
+                }
+            };
+            match found {
+                Some(subword) => pieces.push(subword),
+                None => return push(self.unk),
+            }
+            start = end;
+        }
+        for subword in pieces {
+            push(subword)
+        }
+    }
+}
+
+impl<'v, 'a> Ftzr<&'a String> for WordPiece<'v> {
+    type TokenGroup = Subword<'v>;
+    fn push_tokens<Push>(&self, origin: &'a String, push: &mut Push)
+    where
+        Push: FnMut(Self::TokenGroup),
+    {
+        self.push_tokens(origin.as_str(), push)
+    }
+}
+
+/// Every input, even an empty one, is tokenized in full
+impl<'v> MinLen for WordPiece<'v> {
+    fn min_len(&self) -> usize {
+        0
+    }
+}
+
+impl<'v> FeatureFrom<Subword<'v>> for Subword<'v> {
+    fn from(token_group: Subword<'v>) -> Self {
+        token_group
+    }
+}
+
+impl<'v> FeatureFrom<Subword<'v>> for u32 {
+    fn from(token_group: Subword<'v>) -> Self {
+        token_group.id
+    }
+}
+
+impl<'v> FeatureFrom<Subword<'v>> for &'v str {
+    fn from(token_group: Subword<'v>) -> Self {
+        token_group.piece
+    }
+}
+
+impl<'v> FeatureFrom<Subword<'v>> for String {
+    fn from(token_group: Subword<'v>) -> Self {
+        token_group.piece.to_owned()
+    }
+}
+
+try_via_from! {
+    ['v] Subword<'v> => Subword<'v>;
+    ['v] Subword<'v> => u32;
+    ['v] Subword<'v> => &'v str;
+    ['v] Subword<'v> => String;
+}